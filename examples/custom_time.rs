@@ -4,7 +4,8 @@ use bevy::{
     light::light_consts::lux,
     pbr::{Atmosphere, AtmosphereSettings, ScatteringMedium},
     post_process::bloom::Bloom,
-    prelude::*, render::view::Hdr,
+    prelude::*,
+    render::view::Hdr,
 };
 use bevy_egui::*;
 use bevy_sun_move::{random_stars::*, *};
@@ -12,19 +13,23 @@ use bevy_sun_move::{random_stars::*, *};
 fn main() {
     App::new()
         .add_plugins(DefaultPlugins)
-        .add_plugins(TypedSunMovePlugin::<Time<CustomTime>>::default())
+        .add_plugins(TypedSunMovePlugin::<CustomTime>::default())
         .add_plugins(RandomStarsPlugin)
         .add_plugins(EguiPlugin::default())
-        .insert_resource(Time::<CustomTime>::default())
+        .init_resource::<CustomTime>()
         .add_systems(Startup, (setup_camera_fog, setup_terrain_scene))
-        .add_systems(Update, update_custom_time)
+        .add_systems(Update, update_custom_time.before(SunMoveSet))
         .add_systems(EguiPrimaryContextPass, ui_custom_time)
         .run();
 }
 
+// A clock that can run backwards, which `Time<T>` (built on `Duration`) can't.
+#[derive(Resource)]
 pub struct CustomTime {
     pub relative_speed: f32,
     last_set_speed: f32, // Speed (and direction) to use when unpausing or changing mode.
+    delta_secs: f32,
+    elapsed_secs: f32,
 }
 
 impl Default for CustomTime {
@@ -32,18 +37,30 @@ impl Default for CustomTime {
         Self {
             relative_speed: 1.0, // Start playing forward
             last_set_speed: 1.0, // Default play speed is 1.0 forward
+            delta_secs: 0.0,
+            elapsed_secs: 0.0,
         }
     }
 }
 
-fn update_custom_time(mut custom_time: ResMut<Time<CustomTime>>, time: Res<Time>) {
-    let delta = time.delta().mul_f32(custom_time.context().relative_speed);
-    custom_time.advance_by(delta);
+impl SunClock for CustomTime {
+    fn delta_secs(&self) -> f32 {
+        self.delta_secs
+    }
+
+    fn elapsed_secs(&self) -> f32 {
+        self.elapsed_secs
+    }
+}
+
+fn update_custom_time(mut custom_time: ResMut<CustomTime>, time: Res<Time>) {
+    custom_time.delta_secs = time.delta_secs() * custom_time.relative_speed;
+    custom_time.elapsed_secs += custom_time.delta_secs;
 }
 
 fn ui_custom_time(
     mut commands: Commands,
-    mut custom_time: ResMut<Time<CustomTime>>,
+    mut custom_time: ResMut<CustomTime>,
     time: Res<Time>,
     mut egui_context: EguiContexts,
 ) -> Result {
@@ -53,24 +70,18 @@ fn ui_custom_time(
     egui::Window::new("Custom Time").show(egui_context.ctx_mut()?, |ui| {
         ui.label(format!(
             "Custom time: {:.2} seconds",
-            custom_time.elapsed_secs()
+            custom_time.elapsed_secs
         ));
         ui.label(format!("Time: {:.2} seconds", time.elapsed_secs()));
-        ui.label(format!(
-            "Relative speed: {:.3}",
-            custom_time.context().relative_speed
-        ));
-        ui.label(format!(
-            "Last set speed: {:.3}",
-            custom_time.context().last_set_speed
-        ));
+        ui.label(format!("Relative speed: {:.3}", custom_time.relative_speed));
+        ui.label(format!("Last set speed: {:.3}", custom_time.last_set_speed));
 
         if ui.button("Reset").clicked() {
-            commands.insert_resource(Time::<CustomTime>::default());
+            commands.insert_resource(CustomTime::default());
         }
 
         ui.horizontal(|ui| {
-            let ctx = custom_time.context_mut();
+            let ctx = &mut *custom_time;
 
             // Slower button
             if ui.button("⏪ Slower").clicked() {
@@ -182,7 +193,9 @@ fn setup_terrain_scene(
         Visibility::Visible,
         StarSpawner {
             star_count: 1000,
-            spawn_radius: 5000.0,
+            fit_to_camera_far: Some(0.9), // Just inside the camera far plane
+            exposure_compensation: true,  // The camera uses Exposure::SUNLIGHT
+            ..default()
         },
     ));
 
@@ -18,7 +18,7 @@ use egui_plot::{Line, Plot};
 fn main() {
     App::new()
         .add_plugins(DefaultPlugins)
-        .add_plugins(SunMovePlugin)
+        .add_plugins(SunMovePlugin::default())
         .add_plugins(RandomStarsPlugin)
         .add_plugins(EguiPlugin::default())
         .add_systems(Startup, (setup_camera_fog, setup_terrain_scene))
@@ -93,7 +93,9 @@ fn setup_terrain_scene(
         Visibility::Visible,
         StarSpawner {
             star_count: 1000,
-            spawn_radius: 5000.0,
+            fit_to_camera_far: Some(0.9), // Just inside the camera far plane
+            exposure_compensation: true,  // The camera uses Exposure::SUNLIGHT
+            ..default()
         },
     ));
 
@@ -137,11 +139,7 @@ fn setup_terrain_scene(
     ));
 }
 
-fn ui_system(
-    mut contexts: EguiContexts,
-    mut q_sky_center: Query<&mut SkyCenter>,
-    q_transform: Query<&Transform>,
-) -> Result {
+fn ui_system(mut contexts: EguiContexts, mut q_sky_center: Query<&mut SkyCenter>) -> Result {
     let mut sky_center = match q_sky_center.single_mut() {
         Ok(sc) => sc,
         Err(_) => return Ok(()),
@@ -165,68 +163,45 @@ fn ui_system(
                 .text("Day/Night Duration (s)"),
         ); // Shorter max duration for faster cycles
 
-        // Option to pause/play time
-        let is_paused = sky_center.cycle_duration_secs == 0.0;
+        ui.add(egui::Slider::new(&mut sky_center.time_scale, 0.0..=10.0).text("Time Scale"));
+
+        // Option to pause/play time, the cycle duration stays as it is
         if ui
-            .button(if is_paused { "Play" } else { "Pause" })
+            .button(if sky_center.paused { "Play" } else { "Pause" })
             .clicked()
         {
-            if is_paused {
-                sky_center.cycle_duration_secs = 30.0;
-                sky_center.current_cycle_time %= sky_center.cycle_duration_secs.max(1.0);
-            } else {
-                sky_center.cycle_duration_secs = 0.0;
-            }
+            sky_center.paused = !sky_center.paused;
         }
 
-        if sky_center.cycle_duration_secs > 0.0 {
-            // Only show time slider if not paused
-            let mut current_cycle_time = sky_center.current_cycle_time;
-            if ui
-                .add(
-                    egui::Slider::new(
-                        &mut current_cycle_time,
-                        0.0..=sky_center.cycle_duration_secs,
-                    )
-                    .text("Current Cycle Time (s)"),
+        let mut current_cycle_time = sky_center.current_cycle_time;
+        if ui
+            .add(
+                egui::Slider::new(
+                    &mut current_cycle_time,
+                    0.0..=sky_center.cycle_duration_secs,
                 )
-                .changed()
-            {
-                sky_center.current_cycle_time = current_cycle_time;
-            }
+                .text("Current Cycle Time (s)"),
+            )
+            .changed()
+        {
+            sky_center.current_cycle_time = current_cycle_time;
         }
 
         ui.separator();
 
-        // Get current sun info from its transform
-        let sun_transform = q_transform.get(sky_center.sun).ok();
-
         ui.heading("Current Sun Info");
-        if let Some(sun_transform) = sun_transform {
-            let current_sun_position = sun_transform.translation.normalize();
-
-            // Calculate Elevation (Altitude)
-            let elevation_rad = current_sun_position.y.asin();
-            let elevation_degrees = elevation_rad * RADIANS_TO_DEGREES;
-            ui.label(format!("Sun Elevation: {:.1}°", elevation_degrees));
-
-            // Calculate Heading (Azimuth from North towards East)
-            // Bevy's X is East, Z is North in our calculation frame
-            let heading_rad = current_sun_position.x.atan2(current_sun_position.z);
-            let mut heading_degrees = heading_rad * RADIANS_TO_DEGREES;
-            // Normalize heading to 0-360 degrees if preferred, or keep -180 to 180
-            if heading_degrees < 0.0 {
-                heading_degrees += 360.0;
-            }
-            ui.label(format!("Sun Heading (from North): {:.1}°", heading_degrees));
-
-            let hour_fraction =
-                sky_center.current_cycle_time / sky_center.cycle_duration_secs.max(1.0); // Use max(1.0) to avoid division by zero if paused
-            let hour_of_day = hour_fraction * 24.0;
-            ui.label(format!("Time of Day: {:.2} hours", hour_of_day));
-        } else {
-            ui.label("Sun entity not found or query error.");
-        }
+        ui.label(format!(
+            "Sun Elevation: {:.1}°",
+            sky_center.sun_altitude_deg()
+        ));
+        ui.label(format!(
+            "Sun Heading (from North): {:.1}°",
+            sky_center.sun_azimuth_deg()
+        ));
+
+        let hour_fraction = sky_center.current_cycle_time / sky_center.cycle_duration_secs;
+        let hour_of_day = hour_fraction * 24.0;
+        ui.label(format!("Time of Day: {:.2} hours", hour_of_day));
 
         ui.separator();
 
@@ -235,6 +210,10 @@ fn ui_system(
         let latitude_rad = sky_center.latitude_degrees * DEGREES_TO_RADIANS;
         let axial_tilt_rad = sky_center.planet_tilt_degrees * DEGREES_TO_RADIANS;
         let year_fraction = sky_center.year_fraction;
+        let noon_azimuth_deg = calculate_noon_azimuth_deg(
+            latitude_rad,
+            calculate_declination(axial_tilt_rad, year_fraction),
+        );
 
         let mut sun_elevation_points: Vec<[f64; 2]> = Vec::new();
         let mut sun_heading_points: Vec<[f64; 2]> = Vec::new();
@@ -250,9 +229,8 @@ fn ui_system(
             sun_elevation_points.push([hour_fraction as f64, elevation_degrees as f64]);
 
             // Heading (Azimuth from North towards East) for plot
-            let heading_rad = sun_direction.x.atan2(sun_direction.z);
-            let heading_degrees = heading_rad * RADIANS_TO_DEGREES;
-            // Normalize heading for plot continuity if needed (-180 to 180 is fine for egui_plot default)
+            // Centered on the culmination so the daytime arc doesn't wrap, in both hemispheres
+            let heading_degrees = azimuth_around_deg(sun_direction, noon_azimuth_deg);
             sun_heading_points.push([hour_fraction as f64, heading_degrees as f64]);
         }
 
@@ -0,0 +1,101 @@
+// Shows how a physics crate can consume `SurfaceConditions`.
+// `RoadFriction` stands in for the friction component of your physics crate,
+// e.g. `avian3d::prelude::Friction` or `bevy_rapier3d::prelude::Friction`.
+
+use bevy::{light::light_consts::lux, prelude::*};
+use bevy_sun_move::{surface_conditions::*, *};
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(SunMovePlugin::default())
+        .add_plugins(SurfaceConditionsPlugin)
+        .add_systems(Startup, setup_scene)
+        .add_systems(Update, (apply_road_friction, tint_icy_road))
+        .run();
+}
+
+#[derive(Component)]
+struct RoadFriction {
+    base: f32,
+    current: f32,
+}
+
+fn setup_scene(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    commands.spawn((
+        Camera3d::default(),
+        Transform::from_xyz(-4.0, 2.0, 0.0).looking_at(Vec3::ZERO, Vec3::Y),
+    ));
+
+    let sun_id = commands
+        .spawn((
+            DirectionalLight {
+                shadows_enabled: true,
+                illuminance: lux::AMBIENT_DAYLIGHT,
+                ..default()
+            },
+            Transform::default(),
+        ))
+        .id();
+
+    commands.spawn(SkyCenter {
+        sun: sun_id,
+        latitude_degrees: 60.0,
+        planet_tilt_degrees: 23.5,
+        year_fraction: 0.0,
+        cycle_duration_secs: 30.0, // A 30-second day
+        ..default()
+    });
+
+    // Road
+    commands.spawn((
+        Mesh3d(meshes.add(Plane3d::new(Vec3::Y, Vec2::new(1.5, 10.0)))),
+        MeshMaterial3d(materials.add(StandardMaterial {
+            base_color: Color::srgb(0.2, 0.2, 0.2),
+            ..default()
+        })),
+        RoadFriction {
+            base: 0.8,
+            current: 0.8,
+        },
+    ));
+}
+
+// The adapter: scale the friction of your colliders by the published multiplier
+fn apply_road_friction(conditions: Res<SurfaceConditions>, mut q_roads: Query<&mut RoadFriction>) {
+    if !conditions.is_changed() {
+        return;
+    }
+    for mut friction in q_roads.iter_mut() {
+        friction.current = friction.base * conditions.friction_multiplier;
+    }
+}
+
+fn tint_icy_road(
+    conditions: Res<SurfaceConditions>,
+    q_roads: Query<(&RoadFriction, &MeshMaterial3d<StandardMaterial>)>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut last_logged_ice: Local<bool>,
+) {
+    for (friction, material) in q_roads.iter() {
+        if let Some(material) = materials.get_mut(&material.0) {
+            material.base_color = Color::srgb(0.2, 0.2, 0.2)
+                .mix(&Color::srgb(0.75, 0.85, 1.0), conditions.ice_factor);
+        }
+
+        let icy = conditions.ice_factor > 0.5;
+        if icy != *last_logged_ice {
+            *last_logged_ice = icy;
+            info!(
+                "Road is {} ({:.1} C), friction {:.2}",
+                if icy { "icy" } else { "dry" },
+                conditions.temperature_c,
+                friction.current
+            );
+        }
+    }
+}
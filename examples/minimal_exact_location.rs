@@ -1,18 +1,17 @@
 use bevy::{
     camera::Exposure,
     core_pipeline::tonemapping::Tonemapping,
-    light::light_consts::lux,
     pbr::{Atmosphere, AtmosphereSettings, ScatteringMedium},
     post_process::bloom::Bloom,
     prelude::*,
     render::view::Hdr,
 };
-use bevy_sun_move::{random_stars::*, *};
+use bevy_sun_move::{random_stars::*, sky_spawn::*, *};
 
 fn main() {
     App::new()
         .add_plugins(DefaultPlugins)
-        .add_plugins(SunMovePlugin)
+        .add_plugins(SunMovePlugin::default())
         .add_plugins(RandomStarsPlugin)
         .add_systems(Startup, (setup_camera_fog, setup_terrain_scene))
         .run();
@@ -45,22 +44,9 @@ fn setup_terrain_scene(
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<StandardMaterial>>,
 ) {
-    // Sun
-    let sun_id = commands
-        .spawn((
-            DirectionalLight {
-                shadows_enabled: true,
-                illuminance: lux::RAW_SUNLIGHT, // Full sunlight illuminance
-                ..default()
-            },
-            Transform::default(),
-        ))
-        .id();
-
-    // -- Create the SkyCenter entity
-    commands.spawn((
-        SkyCenter {
-            sun: sun_id,
+    // Sun light and SkyCenter, linked together
+    commands.spawn_sky(SkySettings {
+        sky_center: SkyCenter {
             latitude_degrees: 51.5,    // Approximate latitude of London
             planet_tilt_degrees: 23.5, // Earth's axial tilt
             year_fraction: 0.0,
@@ -68,12 +54,14 @@ fn setup_terrain_scene(
             current_cycle_time: 0.0,   // Start at midnight
             ..default()
         },
-        Visibility::Visible,
-        StarSpawner {
+        stars: Some(StarSpawner {
             star_count: 1000,
-            spawn_radius: 5000.0,
-        },
-    ));
+            fit_to_camera_far: Some(0.9), // Just inside the camera far plane
+            exposure_compensation: true,  // The camera uses Exposure::SUNLIGHT
+            ..default()
+        }),
+        ..default()
+    });
 
     let sphere_mesh = meshes.add(Mesh::from(Sphere { radius: 1.0 }));
 
@@ -12,7 +12,7 @@ use bevy_sun_move::{random_stars::*, *};
 fn main() {
     App::new()
         .add_plugins(DefaultPlugins)
-        .add_plugins(SunMovePlugin)
+        .add_plugins(SunMovePlugin::default())
         .add_plugins(RandomStarsPlugin)
         .add_systems(Startup, (setup_camera_fog, setup_terrain_scene))
         .run();
@@ -71,7 +71,9 @@ fn setup_terrain_scene(
         Visibility::Visible,
         StarSpawner {
             star_count: 1000,
-            spawn_radius: 5000.0,
+            fit_to_camera_far: Some(0.9), // Just inside the camera far plane
+            exposure_compensation: true,  // The camera uses Exposure::SUNLIGHT
+            ..default()
         },
     ));
 
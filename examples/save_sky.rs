@@ -0,0 +1,116 @@
+//! Saves the sky (time of day, day index, season) to `assets/saves/sky.scn.ron` with `S`
+//! and restores it with `L`.
+
+use std::{fs, path::Path};
+
+use bevy::{light::light_consts::lux, prelude::*};
+use bevy_sun_move::{
+    scene::{SkySun, SunRef, sky_save_scene},
+    *,
+};
+
+const SAVE_PATH: &str = "saves/sky.scn.ron";
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(SunMovePlugin::default())
+        .add_systems(Startup, setup)
+        .add_systems(Update, (save_sky, load_sky))
+        .run();
+}
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    commands.spawn((
+        Camera3d::default(),
+        Transform::from_xyz(-4.0, 2.0, 4.0).looking_at(Vec3::ZERO, Vec3::Y),
+    ));
+
+    // The sun isn't saved, the loaded sky finds it again through the marker
+    let sun_id = commands
+        .spawn((
+            DirectionalLight {
+                shadows_enabled: true,
+                illuminance: lux::FULL_DAYLIGHT,
+                ..default()
+            },
+            SkySun,
+        ))
+        .id();
+
+    commands.spawn((
+        Name::new("Sky"),
+        SkyCenter {
+            sun: sun_id,
+            latitude_degrees: 51.5,
+            cycle_duration_secs: 30.0,
+            days_per_year: Some(12.0),
+            ..default()
+        },
+        SunRef::Marker,
+    ));
+
+    commands.spawn((
+        Mesh3d(meshes.add(Plane3d::new(Vec3::Y, Vec2::splat(5.0)))),
+        MeshMaterial3d(materials.add(Color::srgb(0.3, 0.5, 0.3))),
+    ));
+    commands.spawn((
+        Mesh3d(meshes.add(Cuboid::new(1.0, 2.0, 1.0))),
+        MeshMaterial3d(materials.add(Color::srgb(0.8, 0.7, 0.6))),
+        Transform::from_xyz(0.0, 1.0, 0.0),
+    ));
+}
+
+fn save_sky(world: &mut World) {
+    if !world
+        .resource::<ButtonInput<KeyCode>>()
+        .just_pressed(KeyCode::KeyS)
+    {
+        return;
+    }
+
+    let scene = sky_save_scene(world);
+    let type_registry = world.resource::<AppTypeRegistry>().read();
+    let serialized = match scene.serialize(&type_registry) {
+        Ok(serialized) => serialized,
+        Err(err) => {
+            error!("Failed to serialize the sky: {err}");
+            return;
+        }
+    };
+
+    let path = Path::new("assets").join(SAVE_PATH);
+    if let Err(err) = path
+        .parent()
+        .map_or(Ok(()), fs::create_dir_all)
+        .and_then(|_| fs::write(&path, serialized))
+    {
+        error!("Failed to write {}: {err}", path.display());
+        return;
+    }
+    info!("Saved the sky to {}", path.display());
+}
+
+fn load_sky(
+    mut commands: Commands,
+    keys: Res<ButtonInput<KeyCode>>,
+    asset_server: Res<AssetServer>,
+    q_sky_center: Query<Entity, With<SkyCenter>>,
+) {
+    if !keys.just_pressed(KeyCode::KeyL) {
+        return;
+    }
+
+    for sky in q_sky_center.iter() {
+        commands.entity(sky).despawn();
+    }
+    // The file may have changed since it was first loaded
+    let scene = asset_server.load(SAVE_PATH);
+    asset_server.reload(SAVE_PATH);
+    commands.spawn(DynamicSceneRoot(scene));
+    info!("Loading the sky from {SAVE_PATH}");
+}
@@ -18,7 +18,7 @@ use egui_plot::{Line, Plot};
 fn main() {
     App::new()
         .add_plugins(DefaultPlugins)
-        .add_plugins(SunMovePlugin)
+        .add_plugins(SunMovePlugin::default())
         .add_plugins(RandomStarsPlugin)
         .add_plugins(EguiPlugin::default())
         .add_systems(Startup, (setup_camera_fog, setup_terrain_scene))
@@ -81,16 +81,19 @@ fn setup_terrain_scene(
         day_duration_secs: 10.0,
         night_duration_secs: 10.0,
         max_sun_height_deg: 45.0, // Usual value for pretty shadow in middle of the day
+        ..default()
     };
 
+    // The SkyCenter is solved from the config and inserted by the plugin
     commands.spawn((
-        sky_config.clone(),
-        SkyCenter::from_timed_config(&sky_config).unwrap(),
+        sky_config,
         Transform::default(),
         Visibility::Visible,
         StarSpawner {
             star_count: 1000,
-            spawn_radius: 5000.0,
+            fit_to_camera_far: Some(0.9), // Just inside the camera far plane
+            exposure_compensation: true,  // The camera uses Exposure::SUNLIGHT
+            ..default()
         },
     ));
 
@@ -134,228 +137,204 @@ fn setup_terrain_scene(
 // --- Система UI ---
 fn ui_system(
     mut contexts: EguiContexts,
-    mut commands: Commands,
-    mut q_sky_entity: Query<(Entity, &mut TimedSkyConfig, Option<&mut SkyCenter>)>,
-    q_sun_transform: Query<&Transform, Without<SkyCenter>>,
+    mut draft: Local<Option<TimedSkyConfig>>,
+    mut q_sky_entity: Query<(&mut TimedSkyConfig, Option<&mut SkyCenter>)>,
 ) -> Result {
-    let (entity, mut timed_config, mut sky_center_option) = match q_sky_entity.single_mut() {
+    let (mut timed_config, sky_center_option) = match q_sky_entity.single_mut() {
         Ok(data) => data,
         Err(_) => return Ok(()),
     };
+    // Edited by the sliders, written to the TimedSkyConfig on "Apply Config".
+    // The plugin then re-solves the SkyCenter from it.
+    let draft = draft.get_or_insert_with(|| timed_config.clone());
 
     egui::Window::new("Sky Cycle Settings").show(contexts.ctx_mut()?, |ui| {
         ui.heading("Timed Sky Config");
         ui.label("Configure desired day/night durations and max sun height.");
 
-        ui.add(egui::Slider::new(&mut timed_config.planet_tilt_degrees, 0.0..=90.0).text("Planet Tilt (°)"));
-        ui.add(egui::Slider::new(&mut timed_config.day_duration_secs, 0.0..=120.0).text("Desired Day Duration (s)"));
-        ui.add(egui::Slider::new(&mut timed_config.night_duration_secs, 0.0..=120.0).text("Desired Night Duration (s)"));
-        ui.add(egui::Slider::new(&mut timed_config.max_sun_height_deg, 0.0..=90.0).text("Desired Max Sun Height (°)")); // New slider
+        ui.add(
+            egui::Slider::new(&mut draft.planet_tilt_degrees, 0.0..=90.0).text("Planet Tilt (°)"),
+        );
+        ui.add(
+            egui::Slider::new(&mut draft.day_duration_secs, 0.0..=120.0)
+                .text("Desired Day Duration (s)"),
+        );
+        ui.add(
+            egui::Slider::new(&mut draft.night_duration_secs, 0.0..=120.0)
+                .text("Desired Night Duration (s)"),
+        );
+        ui.add(
+            egui::Slider::new(&mut draft.max_sun_height_deg, 0.0..=90.0)
+                .text("Desired Max Sun Height (°)"),
+        ); // New slider
 
         ui.separator();
 
-        // Calculate *potential* resulting parameters based on current TimedSkyConfig values
+        // Calculate *potential* resulting parameters based on the edited values
         let calculation_result = calculate_latitude_yearfraction(
-            timed_config.planet_tilt_degrees,
-            timed_config.day_duration_secs,
-            timed_config.night_duration_secs,
-            timed_config.max_sun_height_deg,
+            draft.planet_tilt_degrees,
+            draft.day_duration_secs,
+            draft.night_duration_secs,
+            draft.max_sun_height_deg,
+            draft.hemisphere,
         );
 
         ui.heading("Calculated Parameters");
-        if let Some((lat, year, dec)) = calculation_result {
-             ui.label(egui::RichText::new(format!("Required Latitude: {:.2}°", lat)).size(18.0));
-             ui.label(egui::RichText::new(format!("Resulting Declination: {:.2}°", dec)).size(18.0));
-             ui.label(egui::RichText::new(format!("Required Year Fraction: {:.4}", year)).size(18.0));
-             ui.label(egui::RichText::new(format!("Total Cycle Duration: {:.2} s", timed_config.day_duration_secs + timed_config.night_duration_secs)).size(18.0));
-
-             if ui.button("Apply Config").clicked() {
-                 let total_duration = timed_config.day_duration_secs + timed_config.night_duration_secs;
-                 let new_sky_center = SkyCenter {
-                     latitude_degrees: lat,
-                     planet_tilt_degrees: timed_config.planet_tilt_degrees, // Use configured tilt
-                     year_fraction: year,
-                     cycle_duration_secs: total_duration,
-                     sun: timed_config.sun_entity,
-                     current_cycle_time: 0.0, // Reset time to midnight when applying
-                 };
-
-                 if let Some(sky_center) = sky_center_option.as_mut() {
-                    // Rewrite the existing SkyCenter
-                    sky_center.latitude_degrees = lat;
-                    sky_center.planet_tilt_degrees = timed_config.planet_tilt_degrees;
-                    sky_center.year_fraction = year;
-                    sky_center.cycle_duration_secs = total_duration;
-                    sky_center.sun = timed_config.sun_entity;
-                 } else {
-                    commands.entity(entity).insert(new_sky_center);
-                 }
-
-                 info!("Applied new SkyCenter settings: Lat {:.2}°, Dec {:.2}°, YF {:.4}, Cycle {:.2}s", lat, dec, year, total_duration);
-             }
-        } else {
-             ui.label(egui::RichText::new("Cannot calculate parameters for this configuration.").color(egui::Color32::RED));
-             // Provide hints based on common issues
-             let total = timed_config.day_duration_secs + timed_config.night_duration_secs;
-             if total <= 0.0 {
-                 ui.label(egui::RichText::new("Error: Total duration must be positive.").color(egui::Color32::RED));
-             } else if timed_config.max_sun_height_deg < 0.0 || timed_config.max_sun_height_deg > 90.0 {
-                  ui.label(egui::RichText::new("Error: Max height must be between 0 and 90 degrees.").color(egui::Color32::RED));
-             } else if timed_config.planet_tilt_degrees.abs() < f32::EPSILON && (timed_config.day_duration_secs / total - 0.5).abs() > f32::EPSILON {
-                  ui.label(egui::RichText::new("Error: With 0 tilt, day/night must be equal duration (12/12).").color(egui::Color32::RED));
-             } else if timed_config.day_duration_secs < f32::EPSILON && timed_config.max_sun_height_deg > f32::EPSILON {
-                  ui.label(egui::RichText::new("Error: Perpetual night is requested, but max height > 0 is impossible.").color(egui::Color32::RED));
-             } else if timed_config.night_duration_secs < f32::EPSILON && timed_config.max_sun_height_deg < f32::EPSILON {
-                 ui.label(egui::RichText::new("Error: Perpetual day is requested, but max height <= 0 is impossible (unless 12/12 max height 0).").color(egui::Color32::RED));
-             }
-              else {
-                  ui.label(egui::RichText::new("Impossible combination of day/night ratio and max height for the given tilt.").color(egui::Color32::RED));
-                  ui.label(egui::RichText::new("Try adjusting tilt, durations, or max height.").color(egui::Color32::RED));
-              }
+        match calculation_result {
+            Ok((lat, year, dec)) => {
+                ui.label(egui::RichText::new(format!("Required Latitude: {:.2}°", lat)).size(18.0));
+                ui.label(
+                    egui::RichText::new(format!("Resulting Declination: {:.2}°", dec)).size(18.0),
+                );
+                ui.label(
+                    egui::RichText::new(format!("Required Year Fraction: {:.4}", year)).size(18.0),
+                );
+                ui.label(
+                    egui::RichText::new(format!(
+                        "Total Cycle Duration: {:.2} s",
+                        draft.day_duration_secs + draft.night_duration_secs
+                    ))
+                    .size(18.0),
+                );
+
+                if ui.button("Apply Config").clicked() {
+                    *timed_config = draft.clone();
+                    info!(
+                        "Applied new timed config: Lat {:.2}°, Dec {:.2}°, YF {:.4}",
+                        lat, dec, year
+                    );
+                }
+            }
+            Err(err) => {
+                ui.label(
+                    egui::RichText::new("Cannot calculate parameters for this configuration.")
+                        .color(egui::Color32::RED),
+                );
+                // The solver tells why, e.g. a max height too high for the day length
+                ui.label(egui::RichText::new(format!("Error: {err}")).color(egui::Color32::RED));
+            }
         }
 
         ui.separator();
 
         // --- Display information from the active SkyCenter (if present) ---
         ui.heading("Current Active Sky Center Info");
-        if let Some(mut sky_center) = sky_center_option { // Need mut to allow slider changes
-
-            ui.label(format!("Actual Latitude: {:.2}°", sky_center.latitude_degrees));
-            ui.label(format!("Actual Planet Tilt: {:.2}°", sky_center.planet_tilt_degrees));
-            ui.label(format!("Actual Year Fraction: {:.4}", sky_center.year_fraction));
-             let actual_dec_deg = sky_center.planet_tilt_degrees * (sky_center.year_fraction * 2.0 * PI).sin() * RADIANS_TO_DEGREES;
-             ui.label(format!("Actual Declination: {:.2}°", actual_dec_deg));
-            ui.add(egui::Slider::new(&mut sky_center.cycle_duration_secs, 0.0..=120.0).text("Actual Cycle Duration (s)")); // Allow changing actual duration
-
-            // Pause/Play option
-            let is_paused = sky_center.cycle_duration_secs <= f32::EPSILON;
+        if let Some(mut sky_center) = sky_center_option {
+            // Need mut to allow slider changes
+
+            ui.label(format!(
+                "Actual Latitude: {:.2}°",
+                sky_center.latitude_degrees
+            ));
+            ui.label(format!(
+                "Actual Planet Tilt: {:.2}°",
+                sky_center.planet_tilt_degrees
+            ));
+            ui.label(format!(
+                "Actual Year Fraction: {:.4}",
+                sky_center.year_fraction
+            ));
+            let actual_dec_deg = sky_center.planet_tilt_degrees
+                * (sky_center.year_fraction * 2.0 * PI).sin()
+                * RADIANS_TO_DEGREES;
+            ui.label(format!("Actual Declination: {:.2}°", actual_dec_deg));
+            ui.add(
+                egui::Slider::new(&mut sky_center.cycle_duration_secs, 1.0..=120.0)
+                    .text("Actual Cycle Duration (s)"),
+            ); // Allow changing actual duration
+            ui.add(egui::Slider::new(&mut sky_center.time_scale, 0.0..=10.0).text("Time Scale"));
+
+            // Pause/Play option, the cycle duration stays as it is
+            let is_paused = sky_center.paused;
             let pause_text = if is_paused { "Play" } else { "Pause" };
             if ui.button(pause_text).clicked() {
-                 if is_paused {
-                      // Restore a default value or previous value? Let's use 30s if it was 0
-                      if sky_center.cycle_duration_secs <= f32::EPSILON {
-                         sky_center.cycle_duration_secs = 30.0;
-                      }
-                       // Ensure current_cycle_time is a valid fraction if it was a fraction
-                      sky_center.current_cycle_time = sky_center.current_cycle_time.fract().max(0.0);
-                       if sky_center.cycle_duration_secs > f32::EPSILON { // Normalize if duration is now positive
-                          sky_center.current_cycle_time *= sky_center.cycle_duration_secs;
-                       }
-                 } else {
-                     // Pause: Store the current state as a fraction [0, 1)
-                      if sky_center.cycle_duration_secs > f32::EPSILON {
-                        sky_center.current_cycle_time /= sky_center.cycle_duration_secs;
-                      } // else it might already be a fraction if it was paused before
-                      sky_center.cycle_duration_secs = 0.0; // Pause
-                 }
+                sky_center.paused = !is_paused;
             }
 
-            // Time slider for active cycle
-            let hour_fraction = if sky_center.cycle_duration_secs > f32::EPSILON {
-                 sky_center.current_cycle_time / sky_center.cycle_duration_secs
-            } else {
-                 sky_center.current_cycle_time.clamp(0.0, 1.0) // Treat as fraction 0-1 when paused
-            };
-
-            if sky_center.cycle_duration_secs > f32::EPSILON {
-                let cycle_duration = sky_center.cycle_duration_secs;
-                 ui.add(egui::Slider::new(&mut sky_center.current_cycle_time, 0.0..=cycle_duration).text("Current Cycle Time (s)"))
-            } else {
-                // Slide using hour representation when paused (current_cycle_time stores the fraction)
-                 let mut display_fraction = hour_fraction;
-                 let response = ui.add(egui::Slider::new(&mut display_fraction, 0.0..=1.0).text("Current Cycle Fraction (0-1)"));
-                 if response.changed() {
-                     sky_center.current_cycle_time = display_fraction.clamp(0.0, 1.0);
-                 }
-                 response
-            };
+            // Time slider
+            let cycle_duration = sky_center.cycle_duration_secs;
+            ui.add(
+                egui::Slider::new(&mut sky_center.current_cycle_time, 0.0..=cycle_duration)
+                    .text("Current Cycle Time (s)"),
+            );
+            let hour_fraction = sky_center.current_cycle_time / cycle_duration;
 
             // Show time of day in 24hr format
             if !is_paused {
-                 ui.label(format!("Time of Day: {:02.0}:{:02.0} ({:.2} hours)",
+                ui.label(format!(
+                    "Time of Day: {:02.0}:{:02.0} ({:.2} hours)",
                     (hour_fraction * 24.0) as u32,
                     ((hour_fraction * 24.0).fract() * 60.0) as u32,
                     hour_fraction * 24.0
-                 ));
+                ));
             } else {
-                 ui.label(format!("Time of Day: {:.2} hours (Paused)", hour_fraction * 24.0));
+                ui.label(format!(
+                    "Time of Day: {:.2} hours (Paused)",
+                    hour_fraction * 24.0
+                ));
             }
 
-
-            // Get current sun info from its transform
+            // Current sun info, straight from the SkyCenter
             ui.separator();
             ui.heading("Current Sun Info");
-            let sun_transform_actual = q_sun_transform.get(sky_center.sun).ok();
-
-
-            if let Some(sun_transform) = sun_transform_actual {
-                 let current_sun_direction = sun_transform.translation.normalize();
-
-                 let elevation_rad = current_sun_direction.y.asin(); // Y is Up
-                 let elevation_degrees = elevation_rad * RADIANS_TO_DEGREES;
-                 ui.label(format!("Sun Elevation: {:.1}°", elevation_degrees));
-
-                 // X is East, Z is North. Azimuth from North towards East.
-                 let horizontal_direction = Vec2::new(current_sun_direction.x, current_sun_direction.z);
-                 let heading_rad = horizontal_direction.x.atan2(horizontal_direction.y); // atan2(East, North)
-                 let mut heading_degrees = heading_rad * RADIANS_TO_DEGREES;
-                 if heading_degrees < 0.0 { heading_degrees += 360.0; } // Normalize 0-360
-                  ui.label(format!("Sun Heading (from North): {:.1}°", heading_degrees));
-
-
-                  ui.separator();
-                  ui.heading("Sun Trajectory Plot (Active Settings)");
-
-                  let n_points = 100;
-                  let latitude_rad = sky_center.latitude_degrees * DEGREES_TO_RADIANS;
-                  let axial_tilt_rad = sky_center.planet_tilt_degrees * DEGREES_TO_RADIANS;
-                  let year_fraction = sky_center.year_fraction; // Use actual year fraction
-
-                  let mut sun_elevation_points: Vec<[f64; 2]> = Vec::new();
-                  let mut sun_heading_points: Vec<[f64; 2]> = Vec::new();
-
-                  for i in 0..=n_points {
-                      let hour_fraction_plot = i as f32 / n_points as f32;
-                      let sun_direction = calculate_sun_direction(
-                          hour_fraction_plot,
-                          latitude_rad,
-                          axial_tilt_rad,
-                          year_fraction,
-                      );
-
-                      let elevation_rad = sun_direction.y.asin();
-                      let elevation_degrees = elevation_rad * RADIANS_TO_DEGREES;
-                      sun_elevation_points.push([hour_fraction_plot as f64, elevation_degrees as f64]);
-
-                      let horizontal_direction_plot = Vec2::new(sun_direction.x, sun_direction.z);
-                      let heading_rad = horizontal_direction_plot.x.atan2(horizontal_direction_plot.y);
-                       let mut heading_degrees = heading_rad * RADIANS_TO_DEGREES;
-                       if heading_degrees < 0.0 { heading_degrees += 360.0; }
-                      sun_heading_points.push([hour_fraction_plot as f64, heading_degrees as f64]);
-                  }
-
-                  let sun_elevation_line = Line::new("Elevation (°)", sun_elevation_points);
-                  let sun_heading_line = Line::new("Heading (°)", sun_heading_points);
-
-                  Plot::new("sun_trajectory_plot")
-                      .legend(egui_plot::Legend::default())
-                      .view_aspect(2.0)
-                      .set_margin_fraction(egui::vec2(0.1, 0.1))
-                      .x_axis_label("Day Fraction (0=Mid, 0.5=Noon, 1=Mid)")
-                      .y_axis_label("Angle (°)")
-                      .show(ui, |plot_ui| {
-                          plot_ui.line(sun_elevation_line);
-                          plot_ui.line(sun_heading_line);
-                      });
+            ui.label(format!(
+                "Sun Elevation: {:.1}°",
+                sky_center.sun_altitude_deg()
+            ));
+            ui.label(format!(
+                "Sun Heading (from North): {:.1}°",
+                sky_center.sun_azimuth_deg()
+            ));
 
-            } else {
-                ui.label("Sun entity transform not found.");
+            ui.separator();
+            ui.heading("Sun Trajectory Plot (Active Settings)");
+
+            let n_points = 100;
+            let latitude_rad = sky_center.latitude_degrees * DEGREES_TO_RADIANS;
+            let axial_tilt_rad = sky_center.planet_tilt_degrees * DEGREES_TO_RADIANS;
+            let year_fraction = sky_center.year_fraction; // Use actual year fraction
+            let noon_azimuth_deg = calculate_noon_azimuth_deg(
+                latitude_rad,
+                calculate_declination(axial_tilt_rad, year_fraction),
+            );
+
+            let mut sun_elevation_points: Vec<[f64; 2]> = Vec::new();
+            let mut sun_heading_points: Vec<[f64; 2]> = Vec::new();
+
+            for i in 0..=n_points {
+                let hour_fraction_plot = i as f32 / n_points as f32;
+                let sun_direction = calculate_sun_direction(
+                    hour_fraction_plot,
+                    latitude_rad,
+                    axial_tilt_rad,
+                    year_fraction,
+                );
+
+                let elevation_rad = sun_direction.y.asin();
+                let elevation_degrees = elevation_rad * RADIANS_TO_DEGREES;
+                sun_elevation_points.push([hour_fraction_plot as f64, elevation_degrees as f64]);
+
+                // Centered on the culmination so the daytime arc doesn't wrap, in both hemispheres
+                let heading_degrees = azimuth_around_deg(sun_direction, noon_azimuth_deg);
+                sun_heading_points.push([hour_fraction_plot as f64, heading_degrees as f64]);
             }
 
+            let sun_elevation_line = Line::new("Elevation (°)", sun_elevation_points);
+            let sun_heading_line = Line::new("Heading (°)", sun_heading_points);
+
+            Plot::new("sun_trajectory_plot")
+                .legend(egui_plot::Legend::default())
+                .view_aspect(2.0)
+                .set_margin_fraction(egui::vec2(0.1, 0.1))
+                .x_axis_label("Day Fraction (0=Mid, 0.5=Noon, 1=Mid)")
+                .y_axis_label("Angle (°)")
+                .show(ui, |plot_ui| {
+                    plot_ui.line(sun_elevation_line);
+                    plot_ui.line(sun_heading_line);
+                });
         } else {
             ui.label("SkyCenter component not active yet. Apply config first.");
         }
-
     });
 
     Ok(())
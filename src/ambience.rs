@@ -0,0 +1,80 @@
+// Reference bevy_audio integration: crossfades a day and a night ambience loop
+// following the daylight. The sounds themselves are spawned by the user, this only
+// drives the volume of their `AudioSink`s.
+
+use bevy::{
+    audio::{AudioSink, AudioSinkPlayback, Volume},
+    prelude::*,
+};
+
+use crate::{SunMoveSet, SunState, daylight_factor, primary_sky::PrimarySkyQuery};
+
+pub struct AmbienceCrossfadePlugin;
+
+impl Plugin for AmbienceCrossfadePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<AmbienceCrossfade>();
+        app.add_systems(PostUpdate, crossfade_ambience.after(SunMoveSet));
+    }
+}
+
+/// Entities of the two ambience sounds (spawned with `AudioPlayer`, usually looping)
+/// and how to blend them.
+#[derive(Resource, Debug, Clone)]
+pub struct AmbienceCrossfade {
+    pub day_sound: Option<Entity>,
+    pub night_sound: Option<Entity>,
+    /// Linear volume of a sound when it's fully faded in.
+    pub volume: f32,
+    /// Seconds (real time) a full crossfade takes at least. Smooths out jumps of the sun,
+    /// e.g. when skipping time. 0.0 follows the daylight instantly.
+    pub fade_secs: f32,
+}
+
+impl Default for AmbienceCrossfade {
+    fn default() -> Self {
+        Self {
+            day_sound: None,
+            night_sound: None,
+            volume: 1.0,
+            fade_secs: 3.0,
+        }
+    }
+}
+
+fn crossfade_ambience(
+    crossfade: Res<AmbienceCrossfade>,
+    time: Res<Time<Real>>,
+    mut day_mix: Local<Option<f32>>,
+    primary_sky: PrimarySkyQuery,
+    q_sun_state: Query<&SunState>,
+    mut q_sinks: Query<&mut AudioSink>,
+) {
+    let Some(sun_state) = primary_sky
+        .entity()
+        .and_then(|sky| q_sun_state.get(sky).ok())
+    else {
+        return;
+    };
+
+    let target = daylight_factor(sun_state.direction);
+    let mix = match *day_mix {
+        Some(mix) if crossfade.fade_secs > 0.0 => {
+            let max_step = time.delta_secs() / crossfade.fade_secs;
+            mix + (target - mix).clamp(-max_step, max_step)
+        }
+        _ => target,
+    };
+    *day_mix = Some(mix);
+
+    for (sound, gain) in [
+        (crossfade.day_sound, mix),
+        (crossfade.night_sound, 1.0 - mix),
+    ] {
+        // The sink only exists once the audio source has loaded
+        let Some(mut sink) = sound.and_then(|entity| q_sinks.get_mut(entity).ok()) else {
+            continue;
+        };
+        sink.set_volume(Volume::Linear(crossfade.volume * gain));
+    }
+}
@@ -0,0 +1,39 @@
+// Driver for the third-party bevy_atmosphere skybox: keeps the sun position of its
+// Nishita model in sync with the sun simulated by SkyCenter.
+
+use bevy::prelude::*;
+use bevy_atmosphere::prelude::{AtmosphereMut, Nishita};
+
+use crate::{SkyCenter, SunMoveSet, primary_sky::PrimarySkyQuery};
+
+pub struct AtmosphereSyncPlugin;
+
+impl Plugin for AtmosphereSyncPlugin {
+    fn build(&self, app: &mut App) {
+        // After update_sky_center has moved the sun this frame
+        app.add_systems(PostUpdate, sync_nishita_sun_position.after(SunMoveSet));
+    }
+}
+
+fn sync_nishita_sun_position(
+    mut atmosphere: AtmosphereMut<Nishita>,
+    primary_sky: PrimarySkyQuery,
+    q_sky_center: Query<&SkyCenter>,
+    q_transforms: Query<&Transform>,
+) {
+    let Some(sky_center) = primary_sky
+        .entity()
+        .and_then(|sky| q_sky_center.get(sky).ok())
+    else {
+        return;
+    };
+    let Ok(sun_transform) = q_transforms.get(sky_center.sun) else {
+        return;
+    };
+
+    // The sun translation is the direction towards the sun, which is what Nishita expects
+    let sun_position = sun_transform.translation.normalize_or_zero();
+    if atmosphere.sun_position != sun_position {
+        atmosphere.sun_position = sun_position;
+    }
+}
@@ -0,0 +1,248 @@
+// Game calendar counting sky cycles into days, months and years, so survival and farming
+// games don't have to duplicate the cycle bookkeeping.
+
+use bevy::prelude::*;
+
+use crate::{SkyCenter, SunMoveSet, primary_sky::PrimarySkyQuery};
+
+pub struct CalendarPlugin;
+
+impl Plugin for CalendarPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GameCalendar>();
+        // The sky clock is advanced in Update, so read it afterwards.
+        app.add_systems(PostUpdate, update_game_calendar.after(SunMoveSet));
+    }
+}
+
+/// Date of the [`PrimarySky`](crate::primary_sky::PrimarySky), following its
+/// `SkyCenter::day_index`: time skips and catch-ups count every day they cross, and a
+/// rewinding clock takes the days back.
+///
+/// Day 0 of the calendar year is the Vernal Equinox (`year_fraction` 0.0). The date starts
+/// from the sky's `year_fraction` and, with `sync_year_fraction`, writes the season back
+/// every new day.
+#[derive(Resource, Debug, Clone)]
+pub struct GameCalendar {
+    /// Length of each month in days; the year is their sum.
+    pub month_lengths: Vec<u32>,
+    /// Set `SkyCenter::year_fraction` to the start of the current calendar day every new day.
+    /// Skipped while `SkyCenter::days_per_year` is set: the sky then moves its own seasons.
+    pub sync_year_fraction: bool,
+    /// Years passed since the calendar started (0 during the first year).
+    pub year: u32,
+    /// Day within the year, starting at 0.
+    pub day_of_year: u32,
+    /// Days passed since the calendar started.
+    pub days_elapsed: u64,
+    /// Sky followed and its `day_index` at the last update.
+    last_day: Option<(Entity, u64)>,
+}
+
+impl Default for GameCalendar {
+    fn default() -> Self {
+        Self::with_month_lengths(vec![31, 28, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31])
+    }
+}
+
+impl GameCalendar {
+    pub fn with_month_lengths(month_lengths: Vec<u32>) -> Self {
+        Self {
+            month_lengths,
+            sync_year_fraction: true,
+            year: 0,
+            day_of_year: 0,
+            days_elapsed: 0,
+            last_day: None,
+        }
+    }
+
+    pub fn days_in_year(&self) -> u32 {
+        self.month_lengths.iter().sum::<u32>().max(1)
+    }
+
+    /// Month of the year, starting at 0.
+    pub fn month(&self) -> u32 {
+        self.month_and_day().0
+    }
+
+    /// Day within the month, starting at 0.
+    pub fn day_of_month(&self) -> u32 {
+        self.month_and_day().1
+    }
+
+    /// `(month, day_of_month)`, both starting at 0.
+    pub fn month_and_day(&self) -> (u32, u32) {
+        let mut day = self.day_of_year;
+        for (month, length) in self.month_lengths.iter().enumerate() {
+            if day < *length {
+                return (month as u32, day);
+            }
+            day -= length;
+        }
+        (self.month_lengths.len().saturating_sub(1) as u32, day)
+    }
+
+    /// Year fraction at the start of the current calendar day.
+    pub fn year_fraction(&self) -> f32 {
+        self.day_of_year as f32 / self.days_in_year() as f32
+    }
+
+    /// Moves the calendar forward by `days`.
+    pub fn advance_days(&mut self, days: u32) {
+        let days_in_year = self.days_in_year();
+        let day = self.day_of_year + days;
+        self.year += day / days_in_year;
+        self.day_of_year = day % days_in_year;
+        self.days_elapsed += days as u64;
+    }
+
+    /// Moves the calendar back by `days`, no further than the day it started from.
+    pub fn rewind_days(&mut self, days: u32) {
+        let days = (days as u64).min(self.days_elapsed) as u32;
+        let days_in_year = self.days_in_year();
+        let day = (self.year * days_in_year + self.day_of_year).saturating_sub(days);
+        self.year = day / days_in_year;
+        self.day_of_year = day % days_in_year;
+        self.days_elapsed -= days as u64;
+    }
+}
+
+fn update_game_calendar(
+    mut calendar: ResMut<GameCalendar>,
+    primary_sky: PrimarySkyQuery,
+    mut q_sky_center: Query<(Entity, &mut SkyCenter)>,
+) {
+    let Some((entity, mut sky_center)) = primary_sky
+        .entity()
+        .and_then(|sky| q_sky_center.get_mut(sky).ok())
+    else {
+        return;
+    };
+    let day_index = sky_center.day_index;
+
+    let last_day_index = match calendar.last_day {
+        Some((last_entity, last_day_index)) if last_entity == entity => last_day_index,
+        // Another sky became the primary one: continue the date from its current day
+        Some(_) => {
+            calendar.last_day = Some((entity, day_index));
+            return;
+        }
+        None => {
+            // Start from the season the sky is configured with
+            let days_in_year = calendar.days_in_year();
+            calendar.day_of_year = (sky_center.year_fraction.rem_euclid(1.0) * days_in_year as f32)
+                as u32
+                % days_in_year;
+            calendar.last_day = Some((entity, day_index));
+            return;
+        }
+    };
+    calendar.last_day = Some((entity, day_index));
+    if day_index == last_day_index {
+        return;
+    }
+
+    if day_index > last_day_index {
+        calendar.advance_days((day_index - last_day_index).min(u32::MAX as u64) as u32);
+    } else {
+        calendar.rewind_days((last_day_index - day_index).min(u32::MAX as u64) as u32);
+    }
+    if calendar.sync_year_fraction && sky_center.days_per_year.is_none() {
+        sky_center.year_fraction = calendar.year_fraction();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy::time::TimeUpdateStrategy;
+
+    use super::*;
+    use crate::SunMovePlugin;
+
+    /// App with a calendar following one sky, the clock advancing 100 ms per update.
+    fn calendar_app(sky_center: SkyCenter) -> (App, Entity) {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, SunMovePlugin::default(), CalendarPlugin))
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+                100,
+            )));
+        let sun = app.world_mut().spawn(Transform::default()).id();
+        let sky = app.world_mut().spawn(SkyCenter { sun, ..sky_center }).id();
+        app.update();
+        (app, sky)
+    }
+
+    #[test]
+    fn skipping_to_sunrise_counts_the_day() {
+        let (mut app, sky) = calendar_app(SkyCenter {
+            paused: true,
+            ..default()
+        });
+        let mut sky_center = app.world_mut().get_mut::<SkyCenter>(sky).unwrap();
+        sky_center.set_hour_of_day(13.0);
+        sky_center.advance_to_next_sunrise().unwrap();
+        app.update();
+
+        let calendar = app.world().resource::<GameCalendar>();
+        assert_eq!(calendar.days_elapsed, 1);
+        assert_eq!(calendar.day_of_year, 1);
+    }
+
+    #[test]
+    fn fast_clock_counts_every_day() {
+        // 0.65 cycles per update: below the catch-up threshold, above half a cycle
+        let (mut app, sky) = calendar_app(SkyCenter {
+            cycle_duration_secs: 10.0,
+            time_scale: 65.0,
+            ..default()
+        });
+        let start_day_index = app.world().get::<SkyCenter>(sky).unwrap().day_index;
+        for _ in 0..9 {
+            app.update();
+        }
+
+        let days = app.world().get::<SkyCenter>(sky).unwrap().day_index - start_day_index;
+        assert!(days >= 5);
+        assert_eq!(app.world().resource::<GameCalendar>().days_elapsed, days);
+    }
+
+    #[test]
+    fn sky_seasons_are_left_alone() {
+        let (mut app, sky) = calendar_app(SkyCenter {
+            cycle_duration_secs: 1.0,
+            year_fraction: 0.0,
+            days_per_year: Some(4.0),
+            ..default()
+        });
+        for _ in 0..25 {
+            app.update();
+        }
+
+        let sky_center = app.world().get::<SkyCenter>(sky).unwrap();
+        let calendar = app.world().resource::<GameCalendar>();
+        assert!(calendar.days_elapsed >= 2);
+        // The sky's 4 day year, not the calendar's 365 day one
+        let expected = (sky_center.day_index as f32 / 4.0).fract();
+        assert!((sky_center.year_fraction - expected).abs() < 1e-4);
+    }
+
+    #[test]
+    fn rewinding_goes_back_across_years() {
+        let mut calendar = GameCalendar::with_month_lengths(vec![2, 3]);
+        calendar.day_of_year = 4;
+        calendar.advance_days(3);
+        assert_eq!((calendar.year, calendar.day_of_year), (1, 2));
+
+        calendar.rewind_days(2);
+        assert_eq!((calendar.year, calendar.day_of_year), (1, 0));
+        // No further back than where it started
+        calendar.rewind_days(10);
+        assert_eq!(
+            (calendar.year, calendar.day_of_year, calendar.days_elapsed),
+            (0, 4, 0)
+        );
+    }
+}
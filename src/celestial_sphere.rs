@@ -0,0 +1,145 @@
+// The celestial sphere as a real mesh: an inward-facing sphere around the sky center,
+// turning with the sky, for planetarium textures or custom sky materials without writing
+// a full sky shader.
+
+use bevy::{
+    light::{NotShadowCaster, NotShadowReceiver},
+    mesh::VertexAttributeValues,
+    prelude::*,
+};
+
+use crate::SkyCenter;
+
+pub struct CelestialSpherePlugin;
+
+impl Plugin for CelestialSpherePlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<CelestialSphere>()
+            .register_type::<CelestialSphereMesh>();
+        app.add_systems(
+            Update,
+            (despawn_removed_celestial_spheres, sync_celestial_spheres),
+        );
+    }
+}
+
+/// Add to a `SkyCenter` entity to give it a sphere mesh of `radius`, seen from the inside
+/// and turning with the sky like the stars. The mesh is a child entity carrying
+/// [`CelestialSphereMesh`]: insert your own `MeshMaterial3d` on it for custom sky
+/// materials, or set `texture` for an unlit equirectangular texture (e.g. a star map).
+#[derive(Component, Reflect, Debug, Clone)]
+#[reflect(Component, Default, Debug)]
+#[require(SkyCenter)]
+pub struct CelestialSphere {
+    /// Radius of the sphere. Keep it behind the stars and inside the camera far plane.
+    pub radius: f32,
+    /// Equirectangular texture drawn unlit on the sphere, `None` to bring your own material.
+    pub texture: Option<Handle<Image>>,
+}
+
+impl Default for CelestialSphere {
+    fn default() -> Self {
+        Self {
+            radius: 6000.0,
+            texture: None,
+        }
+    }
+}
+
+/// The sphere mesh of a [`CelestialSphere`], a child of its `SkyCenter`.
+#[derive(Component, Reflect, Debug, Clone, Copy)]
+#[reflect(Component, Debug)]
+pub struct CelestialSphereMesh {
+    /// The `SkyCenter` entity.
+    pub sky: Entity,
+}
+
+/// Unit sphere with its triangles and normals turned inwards.
+fn inverted_sphere_mesh() -> Mesh {
+    let mut mesh = Sphere::new(1.0).mesh().uv(64, 32);
+    if let Some(VertexAttributeValues::Float32x3(normals)) =
+        mesh.attribute_mut(Mesh::ATTRIBUTE_NORMAL)
+    {
+        for normal in normals.iter_mut() {
+            *normal = normal.map(|component| -component);
+        }
+    }
+    // A UV sphere is an indexed triangle list, which can always be inverted
+    let _ = mesh.invert_winding();
+    mesh
+}
+
+fn sync_celestial_spheres(
+    mut commands: Commands,
+    q_sky_center: Query<(Entity, &CelestialSphere, Option<&Children>), Changed<CelestialSphere>>,
+    mut q_sphere_mesh: Query<&mut Transform, With<CelestialSphereMesh>>,
+    meshes: Option<ResMut<Assets<Mesh>>>,
+    materials: Option<ResMut<Assets<StandardMaterial>>>,
+    mut sphere_mesh: Local<Option<Handle<Mesh>>>,
+) {
+    // No render assets in this world, there is nothing to draw the sphere with
+    let (Some(mut meshes), Some(mut materials)) = (meshes, materials) else {
+        return;
+    };
+
+    for (entity, sphere, children) in q_sky_center.iter() {
+        let transform = Transform::from_scale(Vec3::splat(sphere.radius));
+        let material = sphere.texture.clone().map(|texture| {
+            MeshMaterial3d(materials.add(StandardMaterial {
+                base_color_texture: Some(texture),
+                unlit: true,
+                ..default()
+            }))
+        });
+
+        let existing = children
+            .and_then(|children| children.iter().find(|child| q_sphere_mesh.contains(*child)));
+        let mesh_entity = match existing {
+            Some(mesh_entity) => {
+                if let Ok(mut mesh_transform) = q_sphere_mesh.get_mut(mesh_entity) {
+                    *mesh_transform = transform;
+                }
+                mesh_entity
+            }
+            None => {
+                let mesh = sphere_mesh
+                    .get_or_insert_with(|| meshes.add(inverted_sphere_mesh()))
+                    .clone();
+                let mesh_entity = commands
+                    .spawn((
+                        Name::new("Celestial sphere"),
+                        CelestialSphereMesh { sky: entity },
+                        transform,
+                        Mesh3d(mesh),
+                        NotShadowCaster,
+                        NotShadowReceiver,
+                    ))
+                    .id();
+                commands.entity(entity).add_child(mesh_entity);
+                mesh_entity
+            }
+        };
+        // Without a texture the material is left to the user
+        if let Some(material) = material {
+            commands.entity(mesh_entity).insert(material);
+        }
+    }
+}
+
+fn despawn_removed_celestial_spheres(
+    mut commands: Commands,
+    mut removed: RemovedComponents<CelestialSphere>,
+    q_sphere_mesh: Query<(Entity, &CelestialSphereMesh)>,
+    q_sphere: Query<(), With<CelestialSphere>>,
+) {
+    for sky in removed.read() {
+        if q_sphere.contains(sky) {
+            continue;
+        }
+        for (mesh_entity, sphere_mesh) in q_sphere_mesh.iter() {
+            if sphere_mesh.sky == sky {
+                commands.entity(mesh_entity).despawn();
+            }
+        }
+    }
+}
@@ -0,0 +1,240 @@
+// Scriptable rare sky event: a comet that shows up for a few cycles, rides the sky like the
+// stars and always points its tail away from the sun.
+
+use bevy::{light::NotShadowCaster, platform::collections::HashSet, prelude::*};
+
+use crate::{
+    DEGREES_TO_RADIANS, SkyCenter, SkyTimestamp, SunMoveSet, SunState,
+    calculate_direction_from_hour_angle,
+};
+
+pub struct CometPlugin;
+
+impl Plugin for CometPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<Comet>();
+        app.add_message::<CometArrived>()
+            .add_message::<CometDeparted>();
+        app.add_systems(Update, spawn_comet_visuals);
+        // The sun is moved in Update, so read it afterwards (and before the transforms
+        // are propagated).
+        app.add_systems(
+            PostUpdate,
+            update_comets
+                .after(SunMoveSet)
+                .before(TransformSystems::Propagate),
+        );
+    }
+}
+
+/// A comet in the sky of `sky` (a `SkyCenter` entity), visible from cycle `arrival_day`
+/// for `visible_cycles` cycles. Spawn it as a root entity: its transform is written every
+/// frame, with the tail (local +Y) pointing away from the sun. With render assets, a glowing
+/// head and tail are added as children.
+#[derive(Component, Reflect, Debug, Clone)]
+#[reflect(Component, Default, Debug)]
+#[require(Transform, Visibility)]
+pub struct Comet {
+    #[entities]
+    pub sky: Entity,
+    /// Angle east of the sun along the celestial equator, like a right ascension measured
+    /// from the sun. Fixed on the star dome.
+    pub elongation_deg: f32,
+    /// Angle above the celestial equator.
+    pub declination_deg: f32,
+    /// Distance of the head from the sky center, inside the star dome.
+    pub distance: f32,
+    /// `SkyCenter::day_index` of the first cycle with the comet.
+    pub arrival_day: u64,
+    pub visible_cycles: u32,
+    pub head_radius: f32,
+    /// Tail length at the middle of the visit. It grows on arrival and fades on departure.
+    pub tail_length: f32,
+    pub tail_width: f32,
+    pub color: Color,
+}
+
+impl Default for Comet {
+    fn default() -> Self {
+        Self {
+            sky: Entity::PLACEHOLDER,
+            elongation_deg: 150.0,
+            declination_deg: 20.0,
+            distance: 4000.0,
+            arrival_day: 0,
+            visible_cycles: 5,
+            head_radius: 15.0,
+            tail_length: 600.0,
+            tail_width: 60.0,
+            color: Color::srgb(0.75, 0.9, 1.0),
+        }
+    }
+}
+
+impl Comet {
+    /// Progress through the visit at `day_index` and `hour_fraction` (0.0 arrival, 1.0
+    /// departure), `None` while the comet is away.
+    pub fn visit_progress(&self, day_index: u64, hour_fraction: f32) -> Option<f32> {
+        let cycles = self.visible_cycles.max(1) as f32;
+        let day = day_index.checked_sub(self.arrival_day)? as f32;
+        let progress = (day + hour_fraction) / cycles;
+        (progress < 1.0).then_some(progress)
+    }
+
+    /// Tail length (and glow) factor over the visit, 0.0 at arrival and departure.
+    pub fn strength(progress: f32) -> f32 {
+        (progress.clamp(0.0, 1.0) * std::f32::consts::PI).sin()
+    }
+}
+
+/// Sent when a comet appears.
+#[derive(Message, Debug, Clone, Copy)]
+pub struct CometArrived {
+    pub comet: Entity,
+    pub sky: Entity,
+    pub at: SkyTimestamp,
+}
+
+/// Sent when a comet leaves, despawn it here if it won't come back.
+#[derive(Message, Debug, Clone, Copy)]
+pub struct CometDeparted {
+    pub comet: Entity,
+    pub sky: Entity,
+    pub at: SkyTimestamp,
+}
+
+/// Tail child of a comet, a unit cone stretched to the tail length.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct CometTail;
+
+/// Glowing material of a comet's head and tail.
+#[derive(Component, Debug, Clone)]
+pub struct CometMaterial(pub Handle<StandardMaterial>);
+
+fn spawn_comet_visuals(
+    mut commands: Commands,
+    q_comet: Query<(Entity, &Comet), Added<Comet>>,
+    meshes: Option<ResMut<Assets<Mesh>>>,
+    materials: Option<ResMut<Assets<StandardMaterial>>>,
+) {
+    // No render assets in this world, there is nothing to draw comets with
+    let (Some(mut meshes), Some(mut materials)) = (meshes, materials) else {
+        return;
+    };
+
+    for (entity, comet) in q_comet.iter() {
+        let material = materials.add(StandardMaterial {
+            base_color: Color::BLACK,
+            alpha_mode: AlphaMode::Add,
+            unlit: true,
+            ..default()
+        });
+        let head = commands
+            .spawn((
+                Mesh3d(meshes.add(Sphere::new(comet.head_radius))),
+                MeshMaterial3d(material.clone()),
+                NotShadowCaster,
+            ))
+            .id();
+        let tail = commands
+            .spawn((
+                CometTail,
+                Transform::default(),
+                Mesh3d(meshes.add(Cone::new(0.5, 1.0))),
+                MeshMaterial3d(material.clone()),
+                NotShadowCaster,
+            ))
+            .id();
+        commands
+            .entity(entity)
+            .insert(CometMaterial(material))
+            .add_children(&[head, tail]);
+    }
+}
+
+fn update_comets(
+    time: Res<Time>,
+    mut arrivals: MessageWriter<CometArrived>,
+    mut departures: MessageWriter<CometDeparted>,
+    q_sky_center: Query<(&SkyCenter, &SunState, &Transform), Without<Comet>>,
+    mut q_comet: Query<
+        (
+            Entity,
+            &Comet,
+            &mut Transform,
+            &mut Visibility,
+            Option<&CometMaterial>,
+            Option<&Children>,
+        ),
+        Without<CometTail>,
+    >,
+    mut q_tail: Query<&mut Transform, (With<CometTail>, Without<SkyCenter>)>,
+    mut materials: Option<ResMut<Assets<StandardMaterial>>>,
+    mut visible: Local<HashSet<Entity>>,
+) {
+    for (entity, comet, mut transform, mut visibility, material, children) in q_comet.iter_mut() {
+        let Ok((sky_center, sun_state, sky_transform)) = q_sky_center.get(comet.sky) else {
+            continue;
+        };
+        if !(sky_center.cycle_duration_secs > 0.0) {
+            continue;
+        }
+
+        let hour_fraction = sky_center.current_cycle_time / sky_center.cycle_duration_secs;
+        let progress = comet.visit_progress(sky_center.day_index, hour_fraction);
+        let at = sky_center.timestamp(time.elapsed_secs());
+        let Some(progress) = progress else {
+            visibility.set_if_neq(Visibility::Hidden);
+            if visible.remove(&entity) {
+                departures.write(CometDeparted {
+                    comet: entity,
+                    sky: comet.sky,
+                    at,
+                });
+            }
+            continue;
+        };
+        visibility.set_if_neq(Visibility::Inherited);
+        if visible.insert(entity) {
+            arrivals.write(CometArrived {
+                comet: entity,
+                sky: comet.sky,
+                at,
+            });
+        }
+
+        // Fixed on the star dome: trails the sun's hour angle by the elongation
+        let sun_hour_angle_rad =
+            (sky_center.solar_hour_fraction(hour_fraction) - 0.5) * std::f32::consts::TAU;
+        let direction = calculate_direction_from_hour_angle(
+            sun_hour_angle_rad - comet.elongation_deg * DEGREES_TO_RADIANS,
+            sky_center.latitude_degrees * DEGREES_TO_RADIANS,
+            comet.declination_deg * DEGREES_TO_RADIANS,
+        );
+        transform.translation = sky_transform.translation + direction * comet.distance;
+        transform.rotation =
+            Quat::from_rotation_arc(Vec3::Y, (-sun_state.direction).normalize_or(Vec3::Y));
+
+        let strength = Comet::strength(progress);
+        let tail_length = (comet.tail_length * strength).max(f32::EPSILON);
+        if let Some(children) = children {
+            for child in children.iter() {
+                if let Ok(mut tail_transform) = q_tail.get_mut(child) {
+                    // Cone tip at the head, base at the end of the tail
+                    *tail_transform = Transform::from_translation(Vec3::Y * tail_length * 0.5)
+                        .with_rotation(Quat::from_rotation_x(std::f32::consts::PI))
+                        .with_scale(Vec3::new(comet.tail_width, tail_length, comet.tail_width));
+                }
+            }
+        }
+
+        // Fades out in daylight, like the stars
+        let night = ((0.1 - sun_state.direction.y) / 0.2).clamp(0.0, 1.0);
+        if let (Some(material), Some(materials)) = (material, materials.as_mut())
+            && let Some(material) = materials.get_mut(material.0.id())
+        {
+            material.emissive = comet.color.to_linear() * (strength * night);
+        }
+    }
+    visible.retain(|entity| q_comet.contains(*entity));
+}
@@ -0,0 +1,182 @@
+// Brute force exploration of latitude/year_fraction space.
+// Unlike calculate_latitude_yearfraction, which solves for one exact answer,
+// this scans a grid and ranks every config against soft design goals.
+
+use bevy::prelude::*;
+use std::ops::RangeInclusive;
+
+use crate::{
+    DEGREES_TO_RADIANS, RADIANS_TO_DEGREES, SkyCenter, calculate_day_fraction,
+    calculate_declination,
+};
+
+/// Soft goals for [`sweep_sky_configs`].
+#[derive(Debug, Clone)]
+pub struct SweepGoals {
+    pub planet_tilt_degrees: f32,
+    /// Acceptable fraction of the cycle with the sun above the horizon.
+    pub day_fraction: RangeInclusive<f32>,
+    /// Acceptable noon altitude in degrees.
+    pub max_sun_height_deg: RangeInclusive<f32>,
+    /// How far (in degrees) the sunset may drift north or south of due West.
+    pub max_sunset_offset_from_west_deg: f32,
+    /// Grid resolution over latitude [-90°, 90°].
+    pub latitude_steps: u32,
+    /// Grid resolution over year fraction [0, 1).
+    pub year_fraction_steps: u32,
+    /// Maximum number of ranked candidates returned.
+    pub max_candidates: usize,
+}
+
+impl Default for SweepGoals {
+    fn default() -> Self {
+        Self {
+            planet_tilt_degrees: 23.5,
+            day_fraction: 0.5..=0.7,
+            max_sun_height_deg: 35.0..=60.0,
+            max_sunset_offset_from_west_deg: 20.0,
+            latitude_steps: 180,
+            year_fraction_steps: 96,
+            max_candidates: 10,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SweepCandidate {
+    pub latitude_degrees: f32,
+    pub year_fraction: f32,
+    pub declination_degrees: f32,
+    /// Fraction of the cycle with the sun above the horizon.
+    pub day_fraction: f32,
+    pub max_sun_height_deg: f32,
+    /// Sunset azimuth relative to due West, positive towards North.
+    pub sunset_offset_from_west_deg: f32,
+    /// Lower is better. 0.0 means every goal is met and the config sits at the middle of each range.
+    pub score: f32,
+}
+
+impl SweepCandidate {
+    /// True when the candidate satisfies every goal (not just approaches it).
+    pub fn meets_goals(&self, goals: &SweepGoals) -> bool {
+        goals.day_fraction.contains(&self.day_fraction)
+            && goals.max_sun_height_deg.contains(&self.max_sun_height_deg)
+            && self.sunset_offset_from_west_deg.abs() <= goals.max_sunset_offset_from_west_deg
+    }
+
+    pub fn to_sky_center(
+        &self,
+        planet_tilt_degrees: f32,
+        cycle_duration_secs: f32,
+        sun: Entity,
+    ) -> SkyCenter {
+        SkyCenter {
+            latitude_degrees: self.latitude_degrees,
+            planet_tilt_degrees,
+            year_fraction: self.year_fraction,
+            cycle_duration_secs,
+            sun,
+            ..default()
+        }
+    }
+}
+
+/// Scans the latitude/year_fraction grid described by `goals` and returns the best
+/// matching configs, sorted by score (best first).
+///
+/// Candidates violating a goal are still returned if nothing better exists, with a
+/// score that grows with the distance from the acceptable range.
+pub fn sweep_sky_configs(goals: &SweepGoals) -> Vec<SweepCandidate> {
+    let tilt_rad = goals.planet_tilt_degrees * DEGREES_TO_RADIANS;
+    let latitude_steps = goals.latitude_steps.max(1);
+    let year_fraction_steps = goals.year_fraction_steps.max(1);
+
+    let mut candidates = Vec::with_capacity(((latitude_steps + 1) * year_fraction_steps) as usize);
+
+    for lat_i in 0..=latitude_steps {
+        let latitude_degrees = -90.0 + 180.0 * lat_i as f32 / latitude_steps as f32;
+        let latitude_rad = latitude_degrees * DEGREES_TO_RADIANS;
+
+        for yf_i in 0..year_fraction_steps {
+            let year_fraction = yf_i as f32 / year_fraction_steps as f32;
+            let dec_rad = calculate_declination(tilt_rad, year_fraction);
+
+            let day_fraction = calculate_day_fraction(latitude_rad, dec_rad);
+            let max_sun_height_deg = 90.0 - (latitude_degrees - dec_rad * RADIANS_TO_DEGREES).abs();
+            let sunset_offset_from_west_deg = sunset_offset_from_west(latitude_rad, dec_rad);
+
+            let score = range_score(&goals.day_fraction, day_fraction)
+                + range_score(&goals.max_sun_height_deg, max_sun_height_deg) / 90.0
+                + (sunset_offset_from_west_deg.abs() - goals.max_sunset_offset_from_west_deg)
+                    .max(0.0)
+                    / 90.0;
+
+            candidates.push(SweepCandidate {
+                latitude_degrees,
+                year_fraction,
+                declination_degrees: dec_rad * RADIANS_TO_DEGREES,
+                day_fraction,
+                max_sun_height_deg,
+                sunset_offset_from_west_deg,
+                score,
+            });
+        }
+    }
+
+    candidates.sort_by(|a, b| a.score.total_cmp(&b.score));
+    candidates.truncate(goals.max_candidates);
+    candidates
+}
+
+/// Sunset direction relative to due West from `cos(azimuth) = sin(dec) / cos(lat)`.
+/// Saturates at ±90° where the sun never sets or never rises.
+fn sunset_offset_from_west(latitude_rad: f32, declination_rad: f32) -> f32 {
+    let cos_lat = latitude_rad.cos();
+    if cos_lat < f32::EPSILON {
+        return 90.0_f32.copysign(declination_rad);
+    }
+    (declination_rad.sin() / cos_lat).clamp(-1.0, 1.0).asin() * RADIANS_TO_DEGREES
+}
+
+/// 0 at the middle of the range, up to 0.5 at its edges (relative to its width),
+/// growing linearly outside of it.
+fn range_score(range: &RangeInclusive<f32>, value: f32) -> f32 {
+    let width = (range.end() - range.start()).max(f32::EPSILON);
+    let center = (range.start() + range.end()) * 0.5;
+    let centering = ((value - center).abs() / width).min(0.5);
+    let outside = (range.start() - value).max(value - range.end()).max(0.0);
+    // Outside distance dominates, centering only breaks ties between valid configs
+    centering * 0.1 + outside * 10.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn centered_config_ranks_first() {
+        // Goals centered on the summer solstice at 40°N, which sits on the grid
+        let (latitude_rad, declination_rad) =
+            (40.0 * DEGREES_TO_RADIANS, 23.5 * DEGREES_TO_RADIANS);
+        let day_fraction = calculate_day_fraction(latitude_rad, declination_rad);
+        let goals = SweepGoals {
+            day_fraction: day_fraction - 0.002..=day_fraction + 0.002,
+            max_sun_height_deg: 73.2..=73.8,
+            max_sunset_offset_from_west_deg: 35.0,
+            ..default()
+        };
+        let candidates = sweep_sky_configs(&goals);
+
+        assert!(candidates.is_sorted_by(|a, b| a.score <= b.score));
+        // Mirrored by the southern winter solstice, which shares every goal
+        let mut best: Vec<_> = candidates[..2]
+            .iter()
+            .map(|candidate| (candidate.latitude_degrees, candidate.year_fraction))
+            .collect();
+        best.sort_by(|a, b| a.0.total_cmp(&b.0));
+        assert_eq!(best, [(-40.0, 0.75), (40.0, 0.25)]);
+        assert!(candidates[..2].iter().all(|candidate| {
+            candidate.meets_goals(&goals) && candidate.score < candidates[2].score
+        }));
+    }
+}
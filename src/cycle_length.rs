@@ -0,0 +1,80 @@
+// Typed cycle lengths, so configs are written in minutes or real seconds per game hour
+// instead of hand-computed seconds (and the classic 60x-off mistakes).
+
+use crate::{SkyCenter, TimedSkyConfig};
+
+/// Length of a full day/night cycle, always 24 game hours.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CycleLength {
+    /// A real day: 24 hours of real time.
+    RealTime,
+    Seconds(f32),
+    Minutes(f32),
+    Hours(f32),
+    /// Real seconds per game hour, e.g. 50.0 for a 20 minute cycle.
+    Ratio {
+        real_secs_per_game_hour: f32,
+    },
+}
+
+impl CycleLength {
+    /// Real seconds per cycle.
+    pub fn secs(self) -> f32 {
+        match self {
+            CycleLength::RealTime => 24.0 * 3600.0,
+            CycleLength::Seconds(secs) => secs,
+            CycleLength::Minutes(minutes) => minutes * 60.0,
+            CycleLength::Hours(hours) => hours * 3600.0,
+            CycleLength::Ratio {
+                real_secs_per_game_hour,
+            } => real_secs_per_game_hour * 24.0,
+        }
+    }
+
+    /// Real seconds per game hour.
+    pub fn secs_per_game_hour(self) -> f32 {
+        self.secs() / 24.0
+    }
+}
+
+impl TimedSkyConfig {
+    /// Config with `day_hours` game hours of daylight out of 24 and the sun culminating at
+    /// `max_sun_height_deg`. Solve it with `SkyCenter::from_timed_config`.
+    pub fn from_game_hours(cycle: CycleLength, day_hours: f32, max_sun_height_deg: f32) -> Self {
+        let day_hours = day_hours.clamp(0.0, 24.0);
+        Self {
+            day_duration_secs: day_hours * cycle.secs_per_game_hour(),
+            night_duration_secs: (24.0 - day_hours) * cycle.secs_per_game_hour(),
+            max_sun_height_deg,
+            ..Self::default()
+        }
+    }
+
+    /// Rescales the day and night durations to `cycle`, keeping their ratio.
+    pub fn with_cycle_length(mut self, cycle: CycleLength) -> Self {
+        let total_secs = self.day_duration_secs + self.night_duration_secs;
+        let day_fraction = if total_secs > 0.0 {
+            self.day_duration_secs / total_secs
+        } else {
+            0.5
+        };
+        self.day_duration_secs = day_fraction * cycle.secs();
+        self.night_duration_secs = (1.0 - day_fraction) * cycle.secs();
+        self
+    }
+}
+
+impl SkyCenter {
+    /// Sets `cycle_duration_secs` to `cycle`, keeping the time of day.
+    pub fn with_cycle_length(mut self, cycle: CycleLength) -> Self {
+        let hour_fraction = if self.cycle_duration_secs > 0.0 {
+            self.current_cycle_time / self.cycle_duration_secs
+        } else {
+            0.0
+        };
+        self.cycle_duration_secs = cycle.secs();
+        self.set_hour_fraction(hour_fraction);
+        self
+    }
+}
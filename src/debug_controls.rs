@@ -0,0 +1,75 @@
+// Optional keyboard shortcuts for scrubbing the sky during development.
+// Everything goes through SkyCommand, so it behaves exactly like gameplay driven changes.
+
+use bevy::prelude::*;
+
+use crate::{
+    SunMoveSet,
+    sky_command::{SkyAction, SkyCommand},
+};
+
+pub struct SkyDebugControlsPlugin;
+
+impl Plugin for SkyDebugControlsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SkyDebugControls>();
+        // Sent before the sky applies its commands, so a key press shows the same frame
+        app.add_systems(Update, send_debug_sky_commands.before(SunMoveSet));
+    }
+}
+
+#[derive(Resource, Debug, Clone)]
+pub struct SkyDebugControls {
+    pub advance_hour: KeyCode,
+    pub jump_to_dawn: KeyCode,
+    pub jump_to_noon: KeyCode,
+    pub jump_to_dusk: KeyCode,
+    pub toggle_pause: KeyCode,
+    pub speed_up: KeyCode,
+    pub slow_down: KeyCode,
+    /// Factor applied to `SkyCenter::time_scale` by the speed keys.
+    pub speed_factor: f32,
+}
+
+impl Default for SkyDebugControls {
+    fn default() -> Self {
+        Self {
+            advance_hour: KeyCode::KeyT,
+            jump_to_dawn: KeyCode::Digit1,
+            jump_to_noon: KeyCode::Digit2,
+            jump_to_dusk: KeyCode::Digit3,
+            toggle_pause: KeyCode::KeyP,
+            speed_up: KeyCode::Equal,
+            slow_down: KeyCode::Minus,
+            speed_factor: 2.0,
+        }
+    }
+}
+
+fn send_debug_sky_commands(
+    controls: Res<SkyDebugControls>,
+    keys: Res<ButtonInput<KeyCode>>,
+    mut sky_commands: MessageWriter<SkyCommand>,
+) {
+    let bindings = [
+        (controls.advance_hour, SkyAction::AdvanceHours(1.0)),
+        (controls.jump_to_dawn, SkyAction::JumpToSunrise),
+        (controls.jump_to_noon, SkyAction::JumpToNoon),
+        (controls.jump_to_dusk, SkyAction::JumpToSunset),
+        (controls.toggle_pause, SkyAction::TogglePause),
+        (
+            controls.speed_up,
+            SkyAction::MultiplyTimeScale(controls.speed_factor),
+        ),
+        (
+            controls.slow_down,
+            SkyAction::MultiplyTimeScale(1.0 / controls.speed_factor),
+        ),
+    ];
+
+    for (key, action) in bindings {
+        if keys.just_pressed(key) {
+            sky_commands.write(SkyCommand::all(action));
+        }
+    }
+}
@@ -0,0 +1,184 @@
+// Cadence control for expensive environment lighting updates (generated cubemaps,
+// baked ambient / spherical harmonics). The sun moves every frame, but lighting
+// derived from the whole sky rarely needs to be recomputed that often.
+
+use bevy::prelude::*;
+
+use crate::{SkyCenter, SunMoveSet, SunState, primary_sky::PrimarySkyQuery};
+
+pub struct SkyEnvironmentPlugin;
+
+impl Plugin for SkyEnvironmentPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<EnvironmentUpdateCadence>();
+        app.init_resource::<EnvironmentUpdateTracker>();
+        app.add_systems(
+            PostUpdate,
+            track_environment_update_cadence
+                .in_set(EnvironmentCadenceSet)
+                .after(SunMoveSet),
+        );
+    }
+}
+
+/// Systems recomputing environment lighting should run `.after(EnvironmentCadenceSet)`
+/// with `.run_if(environment_update_due)`.
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct EnvironmentCadenceSet;
+
+/// When environment lighting should be recomputed. Either condition triggers an update,
+/// `None` disables it. With both disabled the environment is only computed once.
+#[derive(Resource, Debug, Clone)]
+pub struct EnvironmentUpdateCadence {
+    /// Seconds of sky time (`SkyCenter::current_cycle_time`, so pausing the sky pauses
+    /// updates). Rewinding the sky doesn't count.
+    pub sky_time_interval_secs: Option<f32>,
+    /// Change of sun altitude in degrees since the last update.
+    pub altitude_threshold_deg: Option<f32>,
+}
+
+impl Default for EnvironmentUpdateCadence {
+    fn default() -> Self {
+        Self {
+            sky_time_interval_secs: None,
+            altitude_threshold_deg: Some(2.0),
+        }
+    }
+}
+
+#[derive(Resource, Debug, Default)]
+pub struct EnvironmentUpdateTracker {
+    /// True for the frame in which an update is due.
+    pub due: bool,
+    /// Sky `day_index` and `current_cycle_time` at the last frame.
+    last_clock: Option<(u64, f32)>,
+    sky_time_since_update: f32,
+    last_altitude_deg: Option<f32>,
+}
+
+impl EnvironmentUpdateTracker {
+    /// Forces an update on the next frame, e.g. after replacing the sky config.
+    pub fn request_update(&mut self) {
+        self.last_altitude_deg = None;
+    }
+}
+
+/// Run condition for systems that recompute environment lighting.
+pub fn environment_update_due(tracker: Res<EnvironmentUpdateTracker>) -> bool {
+    tracker.due
+}
+
+fn track_environment_update_cadence(
+    cadence: Res<EnvironmentUpdateCadence>,
+    mut tracker: ResMut<EnvironmentUpdateTracker>,
+    primary_sky: PrimarySkyQuery,
+    q_sky_center: Query<(&SkyCenter, &SunState)>,
+) {
+    tracker.due = false;
+
+    let Some((sky_center, sun_state)) = primary_sky
+        .entity()
+        .and_then(|sky| q_sky_center.get(sky).ok())
+    else {
+        return;
+    };
+
+    // Accumulate sky time across cycle wraps, rewinds don't count
+    if let Some((last_day_index, last_cycle_time)) = tracker.last_clock {
+        let delta_secs = (sky_center.day_index as f64 - last_day_index as f64)
+            * sky_center.cycle_duration_secs as f64
+            + (sky_center.current_cycle_time - last_cycle_time) as f64;
+        tracker.sky_time_since_update += delta_secs.max(0.0) as f32;
+    }
+    tracker.last_clock = Some((sky_center.day_index, sky_center.current_cycle_time));
+
+    let altitude_deg = sun_state.altitude_deg;
+
+    let due = match tracker.last_altitude_deg {
+        None => true, // First frame or explicitly requested
+        Some(last_altitude_deg) => {
+            cadence
+                .sky_time_interval_secs
+                .is_some_and(|interval| tracker.sky_time_since_update >= interval)
+                || cadence
+                    .altitude_threshold_deg
+                    .is_some_and(|threshold| (altitude_deg - last_altitude_deg).abs() >= threshold)
+        }
+    };
+
+    if due {
+        tracker.due = true;
+        tracker.sky_time_since_update = 0.0;
+        tracker.last_altitude_deg = Some(altitude_deg);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy::time::TimeUpdateStrategy;
+
+    use super::*;
+    use crate::SunMovePlugin;
+
+    /// App with a paused sky at 6:00 and the given cadence, after its first update.
+    fn environment_app(cadence: EnvironmentUpdateCadence) -> (App, Entity) {
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            SunMovePlugin::default(),
+            SkyEnvironmentPlugin,
+        ))
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+            100,
+        )))
+        .insert_resource(cadence);
+        let sun = app.world_mut().spawn(Transform::default()).id();
+        let sky = app
+            .world_mut()
+            .spawn(SkyCenter {
+                sun,
+                current_cycle_time: 150.0,
+                paused: true,
+                ..default()
+            })
+            .id();
+        app.update();
+        assert!(app.world().resource::<EnvironmentUpdateTracker>().due);
+        (app, sky)
+    }
+
+    /// Moves the sky clock by `delta_secs`, updates, returns whether an update was due.
+    fn step(app: &mut App, sky: Entity, delta_secs: f32) -> bool {
+        app.world_mut()
+            .get_mut::<SkyCenter>(sky)
+            .unwrap()
+            .current_cycle_time += delta_secs;
+        app.update();
+        app.world().resource::<EnvironmentUpdateTracker>().due
+    }
+
+    #[test]
+    fn rewinds_do_not_count_as_sky_time() {
+        let (mut app, sky) = environment_app(EnvironmentUpdateCadence {
+            sky_time_interval_secs: Some(100.0),
+            altitude_threshold_deg: None,
+        });
+        assert!(!step(&mut app, sky, -50.0));
+        assert!(!step(&mut app, sky, 60.0));
+        assert!(step(&mut app, sky, 60.0));
+    }
+
+    #[test]
+    fn altitude_changes_trigger_updates() {
+        let (mut app, sky) = environment_app(EnvironmentUpdateCadence {
+            sky_time_interval_secs: None,
+            altitude_threshold_deg: Some(2.0),
+        });
+        // The equinox sun at the equator climbs 0.6 degrees per second of a 600 s cycle
+        assert!(!step(&mut app, sky, 1.0));
+        assert!(step(&mut app, sky, 3.0));
+        assert!(!step(&mut app, sky, 0.0));
+    }
+}
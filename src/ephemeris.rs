@@ -0,0 +1,200 @@
+// Yearly ephemeris tables (sunrise, sunset, day length, noon altitude) for the
+// current sky config, exported as CSV or JSON so designers can review and diff
+// sky behavior outside the engine.
+
+use bevy::prelude::*;
+use std::{fmt::Write as _, path::PathBuf};
+
+use crate::{
+    DEGREES_TO_RADIANS, RADIANS_TO_DEGREES, SkyCenter, calculate_declination,
+    calculate_times_at_altitude, equation_of_time, orbital_year_fraction,
+};
+
+pub struct EphemerisPlugin;
+
+impl Plugin for EphemerisPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_message::<ExportEphemeris>();
+        app.add_systems(Update, export_ephemeris_on_request);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EphemerisFormat {
+    Csv,
+    Json,
+}
+
+/// One simulated day of the year.
+#[derive(Debug, Clone)]
+pub struct EphemerisRow {
+    pub day: u32,
+    pub year_fraction: f32,
+    pub declination_deg: f32,
+    /// Hour fraction of sunrise, `None` during polar day or night.
+    pub sunrise_hour_fraction: Option<f32>,
+    /// Hour fraction of sunset, `None` during polar day or night.
+    pub sunset_hour_fraction: Option<f32>,
+    pub day_length_secs: f32,
+    pub noon_altitude_deg: f32,
+}
+
+/// Samples `days_per_year` days starting at the Vernal Equinox (year fraction 0.0),
+/// using the latitude, tilt and cycle duration of `sky_center`.
+pub fn year_ephemeris(sky_center: &SkyCenter, days_per_year: u32) -> Vec<EphemerisRow> {
+    let days_per_year = days_per_year.max(1);
+    (0..days_per_year)
+        .map(|day| ephemeris_row(sky_center, day, day as f32 / days_per_year as f32))
+        .collect()
+}
+
+/// The ephemeris of `sky_center` on the day at `year_fraction`.
+pub(crate) fn ephemeris_row(sky_center: &SkyCenter, day: u32, year_fraction: f32) -> EphemerisRow {
+    let latitude_rad = sky_center.latitude_degrees * DEGREES_TO_RADIANS;
+    let tilt_rad = sky_center.planet_tilt_degrees * DEGREES_TO_RADIANS;
+    let dec_rad = calculate_declination(
+        tilt_rad,
+        orbital_year_fraction(
+            year_fraction,
+            sky_center.orbital_eccentricity,
+            sky_center.perihelion_year_fraction,
+        ),
+    );
+    let crossings = calculate_times_at_altitude(
+        -sky_center.sun_radius_deg() * DEGREES_TO_RADIANS,
+        latitude_rad,
+        dec_rad,
+    );
+    // Clock times: the sun runs ahead of the clock by the equation of time
+    let solar_offset = if sky_center.equation_of_time {
+        equation_of_time(year_fraction) / (24.0 * 60.0)
+    } else {
+        0.0
+    };
+    let clock_time = |solar: f32| (solar - solar_offset).rem_euclid(1.0);
+    let noon_altitude_deg = 90.0 - (latitude_rad - dec_rad).abs() * RADIANS_TO_DEGREES;
+    // From the same upper limb crossings as sunrise and sunset
+    let (sunrise_hour_fraction, sunset_hour_fraction, day_fraction) = match crossings.as_slice() {
+        [sunrise, sunset] => (
+            Some(clock_time(*sunrise)),
+            Some(clock_time(*sunset)),
+            (sunset - sunrise).rem_euclid(1.0),
+        ),
+        // The limb only touches the horizon, at noon or at midnight
+        [touch] => (
+            None,
+            None,
+            if (touch - 0.5).abs() < 0.25 { 0.0 } else { 1.0 },
+        ),
+        // Polar day or night: the limb stays on one side of the horizon
+        _ if noon_altitude_deg > -sky_center.sun_radius_deg() => (None, None, 1.0),
+        _ => (None, None, 0.0),
+    };
+
+    EphemerisRow {
+        day,
+        year_fraction,
+        declination_deg: dec_rad * RADIANS_TO_DEGREES,
+        sunrise_hour_fraction,
+        sunset_hour_fraction,
+        day_length_secs: day_fraction * sky_center.cycle_duration_secs,
+        noon_altitude_deg,
+    }
+}
+
+pub fn ephemeris_to_csv(rows: &[EphemerisRow]) -> String {
+    let mut out = String::from(
+        "day,year_fraction,declination_deg,sunrise_hour_fraction,sunset_hour_fraction,day_length_secs,noon_altitude_deg\n",
+    );
+    let optional = |value: Option<f32>| value.map(|v| format!("{v:.5}")).unwrap_or_default();
+    for row in rows {
+        let _ = writeln!(
+            out,
+            "{},{:.5},{:.4},{},{},{:.3},{:.4}",
+            row.day,
+            row.year_fraction,
+            row.declination_deg,
+            optional(row.sunrise_hour_fraction),
+            optional(row.sunset_hour_fraction),
+            row.day_length_secs,
+            row.noon_altitude_deg
+        );
+    }
+    out
+}
+
+pub fn ephemeris_to_json(rows: &[EphemerisRow]) -> String {
+    let optional = |value: Option<f32>| {
+        value
+            .map(|v| format!("{v:.5}"))
+            .unwrap_or_else(|| "null".to_string())
+    };
+    let entries: Vec<String> = rows
+        .iter()
+        .map(|row| {
+            format!(
+                "  {{\"day\": {}, \"year_fraction\": {:.5}, \"declination_deg\": {:.4}, \"sunrise_hour_fraction\": {}, \"sunset_hour_fraction\": {}, \"day_length_secs\": {:.3}, \"noon_altitude_deg\": {:.4}}}",
+                row.day,
+                row.year_fraction,
+                row.declination_deg,
+                optional(row.sunrise_hour_fraction),
+                optional(row.sunset_hour_fraction),
+                row.day_length_secs,
+                row.noon_altitude_deg
+            )
+        })
+        .collect();
+    format!("[\n{}\n]\n", entries.join(",\n"))
+}
+
+/// Writes the ephemeris of `sky_center` to `path`.
+pub fn export_ephemeris(
+    sky_center: &SkyCenter,
+    days_per_year: u32,
+    format: EphemerisFormat,
+    path: impl AsRef<std::path::Path>,
+) -> std::io::Result<()> {
+    let rows = year_ephemeris(sky_center, days_per_year);
+    let content = match format {
+        EphemerisFormat::Csv => ephemeris_to_csv(&rows),
+        EphemerisFormat::Json => ephemeris_to_json(&rows),
+    };
+    std::fs::write(path, content)
+}
+
+/// Request to export the ephemeris of a `SkyCenter` entity, handled by [`EphemerisPlugin`].
+#[derive(Message, Debug, Clone)]
+pub struct ExportEphemeris {
+    pub sky: Entity,
+    pub days_per_year: u32,
+    pub format: EphemerisFormat,
+    pub path: PathBuf,
+}
+
+fn export_ephemeris_on_request(
+    mut requests: MessageReader<ExportEphemeris>,
+    q_sky_center: Query<&SkyCenter>,
+) {
+    for request in requests.read() {
+        let Ok(sky_center) = q_sky_center.get(request.sky) else {
+            warn!(
+                "Ephemeris export requested for {} which has no SkyCenter.",
+                request.sky
+            );
+            continue;
+        };
+        match export_ephemeris(
+            sky_center,
+            request.days_per_year,
+            request.format,
+            &request.path,
+        ) {
+            Ok(()) => info!("Exported sky ephemeris to {}", request.path.display()),
+            Err(err) => warn!(
+                "Failed to export sky ephemeris to {}: {}",
+                request.path.display(),
+                err
+            ),
+        }
+    }
+}
@@ -0,0 +1,52 @@
+// Floating origin support (big_space and similar crates). When the world origin is
+// recentered, everything in the world is translated by the opposite offset; the sky
+// has to follow or the star dome and sun drift away from the observer.
+
+use bevy::prelude::*;
+
+use crate::SkyCenter;
+
+pub struct FloatingOriginPlugin;
+
+impl Plugin for FloatingOriginPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_message::<OriginShift>();
+        // Before update_sky_center reads SkyOrigin in Update
+        app.add_systems(PreUpdate, apply_origin_shift);
+    }
+}
+
+/// Send when the floating origin moves. `offset` is the displacement of the new origin
+/// in the old world coordinates, so world positions change by `-offset`.
+#[derive(Message, Debug, Clone, Copy)]
+pub struct OriginShift {
+    pub offset: Vec3,
+}
+
+/// World position the sky is centered on. Without it the sky is centered on `Vec3::ZERO`.
+/// The star dome (children of the `SkyCenter`) moves with it. The sun keeps its unit
+/// direction translation: a directional light's position doesn't affect lighting, and
+/// other systems read the sun direction straight from it.
+#[derive(Component, Reflect, Debug, Default, Clone, Copy)]
+#[reflect(Component, Default)]
+pub struct SkyOrigin(pub Vec3);
+
+fn apply_origin_shift(
+    mut shifts: MessageReader<OriginShift>,
+    mut commands: Commands,
+    mut q_sky_center: Query<(Entity, Option<&mut SkyOrigin>), With<SkyCenter>>,
+) {
+    let offset: Vec3 = shifts.read().map(|shift| shift.offset).sum();
+    if offset == Vec3::ZERO {
+        return;
+    }
+
+    for (entity, origin) in q_sky_center.iter_mut() {
+        match origin {
+            Some(mut origin) => origin.0 -= offset,
+            None => {
+                commands.entity(entity).insert(SkyOrigin(-offset));
+            }
+        }
+    }
+}
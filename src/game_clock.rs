@@ -0,0 +1,105 @@
+// The time of day of the sky as a wall clock for HUDs ("07:45", "Day 3"), instead of every
+// game re-deriving hours and minutes from the hour fraction.
+
+use std::fmt::Write;
+
+use bevy::prelude::*;
+
+use crate::{SkyCenter, SunMoveSet, primary_sky::PrimarySkyQuery};
+
+pub struct GameClockPlugin;
+
+impl Plugin for GameClockPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GameClock>();
+        // The sky clock is advanced in Update, so read it afterwards.
+        app.add_systems(PostUpdate, update_game_clock.after(SunMoveSet));
+    }
+}
+
+/// Clock time of a sky, updated every frame by the [`GameClockPlugin`]. A day of the sky
+/// always reads 24 hours, whatever its `cycle_duration_secs`.
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq)]
+pub struct GameClock {
+    /// The `SkyCenter` to follow, `None` for the
+    /// [`PrimarySky`](crate::primary_sky::PrimarySky).
+    pub sky: Option<Entity>,
+    /// Clock hour fraction (0.0 midnight, 0.5 noon).
+    pub hour_fraction: f32,
+    /// Completed cycles of the sky, see `SkyCenter::day_index`.
+    pub day_index: u64,
+}
+
+impl GameClock {
+    /// Hours since midnight, with the fraction.
+    pub fn hour_of_day(&self) -> f32 {
+        self.hour_fraction * 24.0
+    }
+
+    /// Seconds since midnight on the 24 hour clock, rounded down.
+    fn clock_secs(&self) -> u32 {
+        ((self.hour_fraction * 86400.0) as u32).min(86399)
+    }
+
+    /// Hour in `0..24`.
+    pub fn hours(&self) -> u32 {
+        self.clock_secs() / 3600
+    }
+
+    /// Minute in `0..60`.
+    pub fn minutes(&self) -> u32 {
+        self.clock_secs() / 60 % 60
+    }
+
+    /// Second in `0..60`.
+    pub fn seconds(&self) -> u32 {
+        self.clock_secs() % 60
+    }
+
+    /// The clock time following `pattern`: `%H` hours (00-23), `%I` hours (01-12), `%p`
+    /// AM/PM, `%M` minutes, `%S` seconds, `%d` day index and `%%` a percent sign, e.g.
+    /// `formatted("Day %d, %H:%M")`. Other characters are copied as is.
+    pub fn formatted(&self, pattern: &str) -> String {
+        let mut text = String::with_capacity(pattern.len() + 8);
+        let mut chars = pattern.chars();
+        while let Some(c) = chars.next() {
+            if c != '%' {
+                text.push(c);
+                continue;
+            }
+            let _ = match chars.next() {
+                Some('H') => write!(text, "{:02}", self.hours()),
+                Some('I') => write!(text, "{:02}", (self.hours() + 11) % 12 + 1),
+                Some('p') => write!(text, "{}", if self.hours() < 12 { "AM" } else { "PM" }),
+                Some('M') => write!(text, "{:02}", self.minutes()),
+                Some('S') => write!(text, "{:02}", self.seconds()),
+                Some('d') => write!(text, "{}", self.day_index),
+                Some('%') => write!(text, "%"),
+                Some(other) => write!(text, "%{other}"),
+                None => write!(text, "%"),
+            };
+        }
+        text
+    }
+}
+
+fn update_game_clock(
+    mut game_clock: ResMut<GameClock>,
+    primary_sky: PrimarySkyQuery,
+    q_sky_center: Query<&SkyCenter>,
+) {
+    let Some(sky_center) = game_clock
+        .sky
+        .or_else(|| primary_sky.entity())
+        .and_then(|sky| q_sky_center.get(sky).ok())
+    else {
+        return;
+    };
+    let hour_fraction = sky_center.current_cycle_time / sky_center.cycle_duration_secs;
+    let day_index = sky_center.day_index;
+    // Only flag the resource as changed when the clock moved
+    if game_clock.hour_fraction != hour_fraction || game_clock.day_index != day_index {
+        game_clock.hour_fraction = hour_fraction;
+        game_clock.day_index = day_index;
+    }
+}
@@ -0,0 +1,87 @@
+// Ground-plane shadow parameters for games that fake shadows with sprites
+// (2D, 2.5D or billboarded characters) instead of using shadow maps.
+
+use bevy::prelude::*;
+
+use crate::{SunMoveSet, SunState, primary_sky::PrimarySkyQuery};
+
+pub struct GroundShadowPlugin;
+
+impl Plugin for GroundShadowPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GroundShadowParams>();
+        app.add_systems(PostUpdate, update_ground_shadow_params.after(SunMoveSet));
+    }
+}
+
+/// Sun shadow projected onto the ground plane (XZ in the sky frame).
+///
+/// `direction` is in ground coordinates where `x` is East and `y` is North,
+/// and points from an object towards the tip of its shadow.
+/// `length_factor` is the shadow length per unit of object height
+/// (`cot(altitude)`), clamped to `max_length_factor` near the horizon.
+#[derive(Resource, Debug, Clone, Copy)]
+pub struct GroundShadowParams {
+    pub direction: Vec2,
+    pub length_factor: f32,
+    /// Upper bound for `length_factor`, avoids infinite shadows at sunrise/sunset.
+    pub max_length_factor: f32,
+    /// False when the sun is below the horizon. Shadows should usually be hidden then.
+    pub sun_above_horizon: bool,
+}
+
+impl Default for GroundShadowParams {
+    fn default() -> Self {
+        Self {
+            direction: Vec2::ZERO,
+            length_factor: 0.0,
+            max_length_factor: 10.0,
+            sun_above_horizon: false,
+        }
+    }
+}
+
+/// Projects a sun direction (pointing *towards* the sun, X east, Y up, Z north)
+/// onto the ground plane.
+///
+/// Returns `(direction, length_factor)`, see [`GroundShadowParams`].
+pub fn project_ground_shadow(sun_direction: Vec3, max_length_factor: f32) -> (Vec2, f32) {
+    let sun_direction = sun_direction.normalize_or_zero();
+    let horizontal = Vec2::new(sun_direction.x, sun_direction.z);
+    let horizontal_len = horizontal.length();
+
+    // Shadow falls away from the sun
+    let direction = if horizontal_len > f32::EPSILON {
+        -horizontal / horizontal_len
+    } else {
+        Vec2::ZERO // Sun at zenith or nadir, no preferred direction
+    };
+
+    let length_factor = if sun_direction.y > f32::EPSILON {
+        (horizontal_len / sun_direction.y).min(max_length_factor)
+    } else {
+        max_length_factor
+    };
+
+    (direction, length_factor)
+}
+
+fn update_ground_shadow_params(
+    mut params: ResMut<GroundShadowParams>,
+    primary_sky: PrimarySkyQuery,
+    q_sun_state: Query<&SunState>,
+) {
+    let Some(sun_state) = primary_sky
+        .entity()
+        .and_then(|sky| q_sun_state.get(sky).ok())
+    else {
+        return;
+    };
+
+    let sun_direction = sun_state.direction;
+    let (direction, length_factor) = project_ground_shadow(sun_direction, params.max_length_factor);
+
+    params.direction = direction;
+    params.length_factor = length_factor;
+    params.sun_above_horizon = sun_direction.y > 0.0;
+}
@@ -0,0 +1,127 @@
+// Mixed HDRI + dynamic light setups: finds the yaw and time of day that put the procedural
+// sun where the sun of an HDRI skybox is, and rotates the `Skybox` (and its environment map)
+// to match.
+
+use bevy::{core_pipeline::Skybox, pbr::EnvironmentMapLight, prelude::*};
+
+use crate::{DEGREES_TO_RADIANS, RADIANS_TO_DEGREES, SkyCenter, SunMoveSet};
+
+pub struct HdriMatchPlugin;
+
+impl Plugin for HdriMatchPlugin {
+    fn build(&self, app: &mut App) {
+        // The clock is set before the sun is moved, so both line up the same frame
+        app.add_systems(Update, align_hdri_skyboxes.before(SunMoveSet));
+    }
+}
+
+/// How to line up an HDRI with a sky, see [`SkyCenter::match_hdri_sun`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HdriSunMatch {
+    /// Clock hour fraction at which the procedural sun reaches the HDRI sun's altitude.
+    pub hour_fraction: f32,
+    /// Yaw (degrees, counterclockwise seen from above) bringing the HDRI sun to the
+    /// procedural sun's azimuth.
+    pub yaw_deg: f32,
+}
+
+impl HdriSunMatch {
+    /// Rotation to apply to the HDRI, as `Skybox::rotation` and `EnvironmentMapLight::rotation`.
+    pub fn rotation(&self) -> Quat {
+        Quat::from_rotation_y(self.yaw_deg * DEGREES_TO_RADIANS)
+    }
+}
+
+impl SkyCenter {
+    /// Time of day and HDRI yaw aligning this sky's sun with `hdri_sun_direction`, the
+    /// direction of the sun in the unrotated HDRI (Y up). The sun reaches an altitude
+    /// twice a day, the crossing closest to the current time is used.
+    ///
+    /// Returns `None` if the sun never reaches the HDRI sun's altitude with the current
+    /// latitude and season (e.g. an HDRI shot at noon in summer, on a winter sky).
+    pub fn match_hdri_sun(&self, hdri_sun_direction: Vec3) -> Option<HdriSunMatch> {
+        let direction = hdri_sun_direction.try_normalize()?;
+        let altitude_deg = direction.y.clamp(-1.0, 1.0).asin() * RADIANS_TO_DEGREES;
+        let current = self.current_cycle_time / self.cycle_duration_secs;
+        let cycle_distance = |hour_fraction: f32| {
+            let distance = (hour_fraction - current).rem_euclid(1.0);
+            distance.min(1.0 - distance)
+        };
+        let hour_fraction = self
+            .times_at_altitude(altitude_deg)
+            .into_iter()
+            .min_by(|a, b| cycle_distance(*a).total_cmp(&cycle_distance(*b)))?;
+
+        let hdri_azimuth_deg = direction.x.atan2(direction.z) * RADIANS_TO_DEGREES;
+        let sun_azimuth_deg = self.sun_state_at(hour_fraction).azimuth_deg;
+        Some(HdriSunMatch {
+            hour_fraction,
+            yaw_deg: (sun_azimuth_deg - hdri_azimuth_deg + 180.0).rem_euclid(360.0) - 180.0,
+        })
+    }
+}
+
+/// Lines up the `Skybox` (and `EnvironmentMapLight`) of this camera with `sky`: the HDRI is
+/// yawed under the procedural sun and, with `set_time_of_day`, the sky clock is moved to
+/// the HDRI's time of day. Applied when inserted or changed.
+///
+/// The HDRI is static, so it only stays aligned while the sky is paused.
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct HdriSunAlignment {
+    /// The `SkyCenter` to match.
+    pub sky: Entity,
+    /// Direction of the sun in the unrotated HDRI (Y up), e.g. the brightest texel.
+    pub sun_direction: Vec3,
+    /// Move the sky clock to the HDRI's time of day. Without it, only the yaw is matched,
+    /// for the current time.
+    pub set_time_of_day: bool,
+}
+
+fn align_hdri_skyboxes(
+    mut q_camera: Query<
+        (
+            &HdriSunAlignment,
+            Option<&mut Skybox>,
+            Option<&mut EnvironmentMapLight>,
+        ),
+        Changed<HdriSunAlignment>,
+    >,
+    mut q_sky_center: Query<&mut SkyCenter>,
+) {
+    for (alignment, skybox, environment_map) in q_camera.iter_mut() {
+        let Ok(mut sky_center) = q_sky_center.get_mut(alignment.sky) else {
+            warn!(
+                "HdriSunAlignment targets {}, which has no SkyCenter.",
+                alignment.sky
+            );
+            continue;
+        };
+        let rotation = if alignment.set_time_of_day {
+            let Some(hdri_match) = sky_center.match_hdri_sun(alignment.sun_direction) else {
+                warn!(
+                    "The sun of sky {} never reaches the HDRI sun altitude this season.",
+                    alignment.sky
+                );
+                continue;
+            };
+            sky_center.set_hour_fraction(hdri_match.hour_fraction);
+            hdri_match.rotation()
+        } else {
+            // Yaw only: the current sun azimuth against the HDRI one
+            let Some(direction) = alignment.sun_direction.try_normalize() else {
+                continue;
+            };
+            let hdri_azimuth_rad = direction.x.atan2(direction.z);
+            Quat::from_rotation_y(
+                sky_center.sun_azimuth_deg() * DEGREES_TO_RADIANS - hdri_azimuth_rad,
+            )
+        };
+
+        if let Some(mut skybox) = skybox {
+            skybox.rotation = rotation;
+        }
+        if let Some(mut environment_map) = environment_map {
+            environment_map.rotation = rotation;
+        }
+    }
+}
@@ -0,0 +1,143 @@
+// Heliodon overlay for architecture visualization: the sun paths of every month
+// and the hour lines crossing them, drawn as gizmo lines around the sky center,
+// like the arcs of a physical heliodon.
+
+use bevy::prelude::*;
+
+use crate::{
+    DEGREES_TO_RADIANS, SkyCenter, calculate_sun_direction, equation_of_time, orbital_year_fraction,
+};
+
+pub struct HeliodonPlugin;
+
+impl Plugin for HeliodonPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, draw_heliodon);
+    }
+}
+
+/// Add to a `SkyCenter` entity to draw its heliodon.
+#[derive(Component, Debug, Clone)]
+pub struct Heliodon {
+    pub enabled: bool,
+    /// Radius of the drawn sky dome in world units.
+    pub radius: f32,
+    /// Number of sun path arcs over the year (12 = one per month).
+    pub paths_per_year: u32,
+    /// Draw one line per in-game hour across all arcs.
+    pub hour_lines: bool,
+    /// Draw the arc of the current `year_fraction` highlighted.
+    pub current_path: bool,
+    /// Segments per arc.
+    pub samples: u32,
+    pub winter_color: Color,
+    pub summer_color: Color,
+    pub hour_line_color: Color,
+    pub current_path_color: Color,
+}
+
+impl Default for Heliodon {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            radius: 5.0,
+            paths_per_year: 12,
+            hour_lines: true,
+            current_path: true,
+            samples: 96,
+            winter_color: Color::srgb(0.3, 0.5, 1.0),
+            summer_color: Color::srgb(1.0, 0.6, 0.2),
+            hour_line_color: Color::srgba(0.8, 0.8, 0.8, 0.5),
+            current_path_color: Color::WHITE,
+        }
+    }
+}
+
+fn draw_heliodon(
+    mut gizmos: Gizmos,
+    q_sky_center: Query<(&SkyCenter, &Heliodon, &GlobalTransform)>,
+) {
+    for (sky_center, heliodon, sky_transform) in q_sky_center.iter() {
+        if !heliodon.enabled {
+            continue;
+        }
+
+        let center = sky_transform.translation();
+        let latitude_rad = sky_center.latitude_degrees * DEGREES_TO_RADIANS;
+        let tilt_rad = sky_center.planet_tilt_degrees * DEGREES_TO_RADIANS;
+        let samples = heliodon.samples.max(4);
+        let paths = heliodon.paths_per_year.max(1);
+
+        let point = |hour_fraction: f32, year_fraction: f32| {
+            // With the equation of time, hour lines become analemmas
+            let solar_hour_fraction = if sky_center.equation_of_time {
+                hour_fraction + equation_of_time(year_fraction) / (24.0 * 60.0)
+            } else {
+                hour_fraction
+            };
+            center
+                + calculate_sun_direction(
+                    solar_hour_fraction,
+                    latitude_rad,
+                    tilt_rad,
+                    orbital_year_fraction(
+                        year_fraction,
+                        sky_center.orbital_eccentricity,
+                        sky_center.perihelion_year_fraction,
+                    ),
+                ) * heliodon.radius
+        };
+
+        // Horizon circle for reference
+        gizmos.circle(
+            Isometry3d::new(center, Quat::from_rotation_x(std::f32::consts::FRAC_PI_2)),
+            heliodon.radius,
+            heliodon.hour_line_color,
+        );
+
+        for path in 0..paths {
+            let year_fraction = path as f32 / paths as f32;
+            // sin of the year angle: -1 at winter solstice, 1 at summer solstice
+            let season = (year_fraction * std::f32::consts::TAU).sin() * 0.5 + 0.5;
+            let color = heliodon.winter_color.mix(&heliodon.summer_color, season);
+            let arc = (0..=samples).map(|i| point(i as f32 / samples as f32, year_fraction));
+            draw_above_horizon(&mut gizmos, center.y, arc, color);
+        }
+
+        if heliodon.current_path {
+            let arc =
+                (0..=samples).map(|i| point(i as f32 / samples as f32, sky_center.year_fraction));
+            draw_above_horizon(&mut gizmos, center.y, arc, heliodon.current_path_color);
+        }
+
+        if heliodon.hour_lines {
+            for hour in 0..24 {
+                let hour_fraction = hour as f32 / 24.0;
+                let line = (0..=samples).map(|i| point(hour_fraction, i as f32 / samples as f32));
+                draw_above_horizon(&mut gizmos, center.y, line, heliodon.hour_line_color);
+            }
+        }
+    }
+}
+
+/// Draws the parts of a polyline above the horizon plane as separate line strips.
+fn draw_above_horizon(
+    gizmos: &mut Gizmos,
+    horizon_y: f32,
+    points: impl Iterator<Item = Vec3>,
+    color: Color,
+) {
+    let mut strip: Vec<Vec3> = Vec::new();
+    for point in points {
+        if point.y >= horizon_y {
+            strip.push(point);
+        } else if strip.len() > 1 {
+            gizmos.linestrip(strip.drain(..), color);
+        } else {
+            strip.clear();
+        }
+    }
+    if strip.len() > 1 {
+        gizmos.linestrip(strip, color);
+    }
+}
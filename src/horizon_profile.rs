@@ -0,0 +1,111 @@
+// Terrain shadow proxy: the skyline elevation around the observer, so a valley base sees
+// the sun rise later and set earlier than a hilltop, without any shadow map.
+
+use bevy::prelude::*;
+use smallvec::{SmallVec, smallvec};
+
+use crate::SkyCenter;
+
+/// Samples per cycle used to find where the sun crosses the skyline.
+const SKYLINE_SCAN_STEPS: usize = 288;
+
+/// Elevation of the skyline (mountains, valley walls) around the observer. Add to a
+/// `SkyCenter` entity: sunrise and sunset events, `SunState::horizon_elevation_deg` and
+/// `SunIlluminance` then follow the sun crossing the skyline instead of the flat horizon.
+/// Time of day phases keep following the flat horizon, twilight lights the whole sky.
+#[derive(Component, Reflect, Debug, Default, Clone, PartialEq)]
+#[reflect(Component, Default, Debug)]
+pub struct HorizonProfile {
+    /// Skyline elevation in degrees at evenly spaced azimuths, the first one due North,
+    /// then clockwise (towards East). Linearly interpolated, empty is a flat horizon.
+    pub elevation_deg: Vec<f32>,
+}
+
+impl HorizonProfile {
+    pub fn new(elevation_deg: Vec<f32>) -> Self {
+        Self { elevation_deg }
+    }
+
+    /// Samples `elevation_deg(azimuth_deg)` at `samples` evenly spaced azimuths, e.g. from
+    /// a heightmap raycast around the observer.
+    pub fn from_fn(samples: usize, elevation_deg: impl Fn(f32) -> f32) -> Self {
+        Self::new(
+            (0..samples)
+                .map(|index| elevation_deg(index as f32 * 360.0 / samples as f32))
+                .collect(),
+        )
+    }
+
+    /// Skyline elevation in degrees at `azimuth_deg` (from North towards East).
+    pub fn elevation_at(&self, azimuth_deg: f32) -> f32 {
+        let count = self.elevation_deg.len();
+        if count == 0 {
+            return 0.0;
+        }
+        let position = azimuth_deg.rem_euclid(360.0) / 360.0 * count as f32;
+        let index = (position.floor() as usize).min(count - 1);
+        let next = self.elevation_deg[(index + 1) % count];
+        self.elevation_deg[index].lerp(next, position - index as f32)
+    }
+}
+
+impl SkyCenter {
+    /// Hour fractions at which the sun's upper limb rises over and sets behind the skyline
+    /// of `profile`: the first rise and the last set of the cycle, so a peak briefly hiding
+    /// the sun around noon doesn't count. Empty if the sun never clears the skyline (or never
+    /// drops behind it). Same as [`SkyCenter::sunrise_and_sunset`] without a profile.
+    pub fn sunrise_and_sunset_over(&self, profile: Option<&HorizonProfile>) -> SmallVec<[f32; 2]> {
+        let Some(profile) = profile.filter(|profile| !profile.elevation_deg.is_empty()) else {
+            return self.sunrise_and_sunset();
+        };
+
+        let clearance = |hour_fraction: f32| {
+            let state = self.sun_state_at(hour_fraction);
+            state.upper_limb_altitude_deg - profile.elevation_at(state.azimuth_deg)
+        };
+
+        let mut rise = None;
+        let mut set = None;
+        let mut previous = clearance(0.0);
+        for step in 1..=SKYLINE_SCAN_STEPS {
+            let (start, end) = (
+                (step - 1) as f32 / SKYLINE_SCAN_STEPS as f32,
+                step as f32 / SKYLINE_SCAN_STEPS as f32,
+            );
+            let current = clearance(end);
+            if (previous <= 0.0) != (current <= 0.0) {
+                let crossing = bisect_crossing(&clearance, start, end, previous);
+                if current > 0.0 {
+                    rise.get_or_insert(crossing);
+                } else {
+                    set = Some(crossing);
+                }
+            }
+            previous = current;
+        }
+
+        match (rise, set) {
+            (Some(rise), Some(set)) => smallvec![rise, set],
+            _ => SmallVec::new(),
+        }
+    }
+}
+
+/// Refines a sign change of `clearance` between `start` and `end`.
+fn bisect_crossing(
+    clearance: &impl Fn(f32) -> f32,
+    mut start: f32,
+    mut end: f32,
+    start_value: f32,
+) -> f32 {
+    let start_above = start_value > 0.0;
+    for _ in 0..16 {
+        let middle = (start + end) * 0.5;
+        if (clearance(middle) > 0.0) == start_above {
+            start = middle;
+        } else {
+            end = middle;
+        }
+    }
+    (start + end) * 0.5
+}
@@ -0,0 +1,127 @@
+// Long journeys (sailing north, a caravan crossing the continent): the sky's latitude drifts
+// as the days pass, so days lengthen or shorten on the way, with a message when the sky
+// enters or leaves the midnight sun or the polar night.
+
+use bevy::{platform::collections::HashMap, prelude::*};
+
+use crate::{
+    DEGREES_TO_RADIANS, SkyCenter, SkyTimestamp, SunMoveSet, calculate_day_fraction,
+    calculate_declination,
+};
+
+pub struct LatitudeDriftPlugin;
+
+impl Plugin for LatitudeDriftPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<LatitudeDrift>()
+            .register_type::<DaylightBand>();
+        app.add_message::<DaylightBandChanged>();
+        // The sky clock is advanced in Update, so read it afterwards. The new latitude
+        // moves the sun from the next frame on.
+        app.add_systems(PostUpdate, drift_latitude.after(SunMoveSet));
+    }
+}
+
+/// Moves the latitude of the `SkyCenter` by `degrees_per_day` each cycle, continuously
+/// (positive goes north). The latitude stops at the poles.
+#[derive(Component, Reflect, Debug, Default, Clone, Copy)]
+#[reflect(Component, Default, Debug)]
+#[require(SkyCenter)]
+pub struct LatitudeDrift {
+    pub degrees_per_day: f32,
+}
+
+/// Whether the sun rises and sets at the sky's latitude and season.
+#[derive(Reflect, Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DaylightBand {
+    #[default]
+    DayAndNight,
+    /// The sun never sets (midnight sun).
+    PolarDay,
+    /// The sun never rises.
+    PolarNight,
+}
+
+impl DaylightBand {
+    /// Band of `sky_center` today, from the length of its day.
+    pub fn of(sky_center: &SkyCenter) -> Self {
+        let day_fraction = calculate_day_fraction(
+            sky_center.latitude_degrees * DEGREES_TO_RADIANS,
+            calculate_declination(
+                sky_center.planet_tilt_degrees * DEGREES_TO_RADIANS,
+                sky_center.season_year_fraction(),
+            ),
+        );
+        if day_fraction >= 1.0 {
+            DaylightBand::PolarDay
+        } else if day_fraction <= 0.0 {
+            DaylightBand::PolarNight
+        } else {
+            DaylightBand::DayAndNight
+        }
+    }
+}
+
+/// Sent when a sky enters or leaves perpetual day or night, by drifting across a polar
+/// circle or as the season changes.
+#[derive(Message, Debug, Clone, Copy)]
+pub struct DaylightBandChanged {
+    pub sky: Entity,
+    pub from: DaylightBand,
+    pub to: DaylightBand,
+    pub latitude_degrees: f32,
+    pub at: SkyTimestamp,
+}
+
+fn drift_latitude(
+    time: Res<Time>,
+    mut band_changes: MessageWriter<DaylightBandChanged>,
+    mut last_positions: Local<HashMap<Entity, (u64, f32, DaylightBand)>>,
+    mut q_sky_center: Query<(Entity, &mut SkyCenter, Option<&LatitudeDrift>)>,
+) {
+    for (entity, mut sky_center, drift) in q_sky_center.iter_mut() {
+        if !(sky_center.cycle_duration_secs > 0.0) {
+            continue;
+        }
+        let hour_fraction = sky_center.current_cycle_time / sky_center.cycle_duration_secs;
+        let Some(&(last_day_index, last_hour_fraction, last_band)) = last_positions.get(&entity)
+        else {
+            last_positions.insert(
+                entity,
+                (
+                    sky_center.day_index,
+                    hour_fraction,
+                    DaylightBand::of(&sky_center),
+                ),
+            );
+            continue;
+        };
+
+        // Drift with the running clock only, not when the time of day is set by hand
+        let cycles = sky_center.day_index.wrapping_sub(last_day_index) as f32 + hour_fraction
+            - last_hour_fraction;
+        if let Some(drift) = drift
+            && cycles > 0.0
+            && drift.degrees_per_day != 0.0
+        {
+            let latitude_degrees =
+                (sky_center.latitude_degrees + drift.degrees_per_day * cycles).clamp(-90.0, 90.0);
+            if latitude_degrees != sky_center.latitude_degrees {
+                sky_center.latitude_degrees = latitude_degrees;
+            }
+        }
+
+        let band = DaylightBand::of(&sky_center);
+        if band != last_band {
+            band_changes.write(DaylightBandChanged {
+                sky: entity,
+                from: last_band,
+                to: band,
+                latitude_degrees: sky_center.latitude_degrees,
+                at: sky_center.timestamp(time.elapsed_secs()),
+            });
+        }
+        last_positions.insert(entity, (sky_center.day_index, hour_fraction, band));
+    }
+    last_positions.retain(|entity, _| q_sky_center.contains(*entity));
+}
@@ -1,26 +1,216 @@
+pub mod ambience;
+#[cfg(feature = "bevy_atmosphere")]
+pub mod atmosphere_sync;
+pub mod calendar;
+pub mod celestial_sphere;
+pub mod comet;
+pub mod config_sweep;
+pub mod cycle_length;
+pub mod debug_controls;
+pub mod environment;
+pub mod ephemeris;
+pub mod floating_origin;
+pub mod game_clock;
+pub mod ground_shadow;
+pub mod hdri_match;
+pub mod heliodon;
+pub mod horizon_profile;
+pub mod latitude_drift;
+pub mod moon;
+#[cfg(feature = "serde")]
+pub mod persistence;
+pub mod phase_modifiers;
+pub mod primary_sky;
 pub mod random_stars;
-
-use bevy::prelude::*;
+#[cfg(feature = "chrono")]
+pub mod real_world;
+pub mod scene;
+pub mod sky_ambient;
+pub mod sky_anomaly;
+pub mod sky_command;
+#[cfg(feature = "serde")]
+pub mod sky_config_asset;
+pub mod sky_cubemap;
+pub mod sky_events;
+pub mod sky_labels;
+pub mod sky_layers;
+pub mod sky_observer;
+pub mod sky_override;
+pub mod sky_picking;
+pub mod sky_spawn;
+pub mod sky_state_uniform;
+pub mod sky_stats;
+pub mod sky_triggers;
+pub mod special_night;
+pub mod sun_cache;
+pub mod sun_fit;
+#[cfg(feature = "editor")]
+pub mod sun_gizmo;
+pub mod sun_light;
+pub mod sunset_flourish;
+pub mod surface_conditions;
+pub mod tide;
+pub mod time_of_day;
+pub mod ui_lighting;
+pub mod wall_clock;
+pub mod year_planner;
+
+use bevy::{
+    ecs::schedule::{InternedScheduleLabel, ScheduleLabel},
+    platform::collections::HashMap,
+    prelude::*,
+};
+use smallvec::{SmallVec, smallvec};
 use std::f32::consts::PI;
 
+use crate::{
+    floating_origin::SkyOrigin,
+    horizon_profile::HorizonProfile,
+    moon::MoonLight,
+    primary_sky::PrimarySky,
+    random_stars::{Star, StarBrightness, StarSpawner},
+    scene::{
+        AutoSunSettings, SkySun, SunLinkBroken, SunRef, auto_spawn_sun, detect_broken_sun_links,
+        resolve_scene_sun, resolve_sun_ref,
+    },
+    sky_command::{SkyCommand, apply_sky_commands},
+    sun_cache::{CachedSunPosition, SunPositionCache},
+    time_of_day::{
+        PhaseTimeScales, TimeOfDayPhase, TimeOfDayPhaseChanged, TimeOfDayThresholds,
+        classify_time_of_day, update_time_of_day_phase,
+    },
+};
+
 // Helper constants
 pub const DEGREES_TO_RADIANS: f32 = PI / 180.0;
 pub const RADIANS_TO_DEGREES: f32 = 180.0 / PI;
 
-pub struct SunMovePlugin;
+/// Moves the sun of every `SkyCenter`, in `Update` by default.
+///
+/// The skies follow the virtual clock: they stop while `Time<Virtual>` is paused and run
+/// at its relative speed (through `Time<Fixed>` in `FixedUpdate`). Use
+/// [`SunMovePlugin::with_real_time`] to keep them moving regardless.
+pub struct SunMovePlugin {
+    schedule: InternedScheduleLabel,
+    real_time: bool,
+}
+
+impl Default for SunMovePlugin {
+    fn default() -> Self {
+        Self::in_schedule(Update)
+    }
+}
+
+impl SunMovePlugin {
+    /// Runs the sky systems in `schedule` (e.g. `FixedUpdate` for deterministic simulations
+    /// or `PostUpdate`), see [`SunMoveSet`].
+    pub fn in_schedule(schedule: impl ScheduleLabel) -> Self {
+        Self {
+            schedule: schedule.intern(),
+            real_time: false,
+        }
+    }
+
+    /// Drives the skies with `Time<Real>`, ignoring the pause and relative speed of
+    /// `Time<Virtual>` (e.g. for a menu background that keeps moving while the game is
+    /// paused). `SkyCenter::paused` and `time_scale` still apply.
+    pub fn with_real_time(mut self) -> Self {
+        self.real_time = true;
+        self
+    }
+}
 
 impl Plugin for SunMovePlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, update_sky_center::<Time>);
+        if self.real_time {
+            build_sun_move::<Time<Real>>(app, self.schedule);
+        } else {
+            // `Time` is the virtual clock, or the fixed one in `FixedUpdate`
+            build_sun_move::<Time>(app, self.schedule);
+        }
     }
 }
 
-pub trait ISunTime {
+/// The systems advancing the sky clocks, moving the suns and updating `SunState`, the moon
+/// and the time of day phases. Order your systems `.before(SunMoveSet)` to change a sky
+/// before it moves, or `.after(SunMoveSet)` to read the sun of this frame.
+///
+/// The plugins of this crate reading the sun run in `PostUpdate`, after this set.
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SunMoveSet;
+
+fn build_sun_move<T: SunClock + Resource>(app: &mut App, schedule: InternedScheduleLabel) {
+    // Needed to save and spawn sky rigs as scenes
+    app.register_type::<SkyCenter>()
+        .register_type::<TimedSkyConfig>()
+        .register_type::<HemispherePreference>()
+        .register_type::<NoonSunSide>()
+        .register_type::<SeasonHalf>()
+        .register_type::<StarSpawner>()
+        .register_type::<Star>()
+        .register_type::<StarBrightness>()
+        .register_type::<SunState>()
+        .register_type::<PreciseCycleTime>()
+        .register_type::<SkyTick>()
+        .register_type::<SunUpdatePolicy>()
+        .register_type::<TimeOfDayPhase>()
+        .register_type::<PhaseTimeScales>()
+        .register_type::<MoonCenter>()
+        .register_type::<MoonPhase>()
+        .register_type::<MoonLight>()
+        .register_type::<SkyOrigin>()
+        .register_type::<HorizonProfile>()
+        .register_type::<SunRef>()
+        .register_type::<SkySun>()
+        .register_type::<PrimarySky>();
+    app.add_message::<SkyCommand>()
+        .add_message::<SunriseEvent>()
+        .add_message::<SunsetEvent>()
+        .add_message::<SkyCatchUp>()
+        .add_message::<NewCycleEvent>()
+        .add_message::<NewYearEvent>()
+        .add_message::<SunLinkBroken>()
+        .add_message::<TimeOfDayPhaseChanged>();
+    app.init_resource::<SkyCatchUpSettings>()
+        .init_resource::<AutoSunSettings>()
+        .init_resource::<TimeOfDayThresholds>()
+        .init_resource::<SunPositionCache>();
+    app.add_observer(auto_spawn_sun);
+    // The sun transform is written here, and propagated to the light afterwards
+    app.configure_sets(schedule, SunMoveSet.before(TransformSystems::Propagate));
+    app.add_systems(
+        schedule,
+        (
+            resolve_sun_ref,
+            resolve_scene_sun,
+            detect_broken_sun_links::<T>,
+            apply_sky_commands,
+            sync_timed_sky_config,
+            // Sub-apps or secondary worlds may not run a clock
+            update_sky_center::<T>.run_if(resource_exists::<T>),
+            update_sun_state,
+            (update_moon_center, update_time_of_day_phase::<T>),
+        )
+            .chain()
+            .in_set(SunMoveSet),
+    );
+}
+
+/// Time source of the sky clocks, see [`TypedSunMovePlugin`]. Implemented for every
+/// `Time<T>` (`Time<Virtual>`, `Time<Fixed>`, `Time<Real>` or a custom context), or
+/// implement it for your own resource.
+pub trait SunClock {
+    /// Seconds passed since the last update. May be negative for clocks that rewind: the
+    /// sky then moves back without sending sunrise or sunset events.
     fn delta_secs(&self) -> f32;
+    /// Seconds since the clock started, used to timestamp sky events.
     fn elapsed_secs(&self) -> f32;
 }
 
-impl<T: Default + Send + Sync + 'static> ISunTime for Time<T> {
+/// Former name of [`SunClock`].
+pub use SunClock as ISunTime;
+
+impl<T: Default + Send + Sync + 'static> SunClock for Time<T> {
     fn delta_secs(&self) -> f32 {
         self.delta_secs()
     }
@@ -30,26 +220,38 @@ impl<T: Default + Send + Sync + 'static> ISunTime for Time<T> {
     }
 }
 
-pub struct TypedSunMovePlugin<T: ISunTime + Resource> {
+/// [`SunMovePlugin`] driven by the clock resource `T` instead of `Time`.
+pub struct TypedSunMovePlugin<T: SunClock + Resource> {
+    schedule: InternedScheduleLabel,
     _marker: std::marker::PhantomData<T>,
 }
 
-impl<T: ISunTime + Resource> Default for TypedSunMovePlugin<T> {
+impl<T: SunClock + Resource> Default for TypedSunMovePlugin<T> {
     fn default() -> Self {
+        Self::in_schedule(Update)
+    }
+}
+
+impl<T: SunClock + Resource> TypedSunMovePlugin<T> {
+    /// Runs the sky systems in `schedule`, see [`SunMovePlugin::in_schedule`].
+    pub fn in_schedule(schedule: impl ScheduleLabel) -> Self {
         Self {
+            schedule: schedule.intern(),
             _marker: std::marker::PhantomData,
         }
     }
 }
 
-impl<T: ISunTime + Resource> Plugin for TypedSunMovePlugin<T> {
+impl<T: SunClock + Resource> Plugin for TypedSunMovePlugin<T> {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, update_sky_center::<T>);
+        build_sun_move::<T>(app, self.schedule);
     }
 }
 
-// Determine latitude and year fraction from day and night fractions of full cycle
-#[derive(Component, Debug, Clone)]
+// Determine latitude and year fraction from day and night fractions of full cycle.
+// The SkyCenter of the entity is inserted and re-solved automatically when it changes.
+#[derive(Component, Reflect, Debug, Clone)]
+#[reflect(Component, Default, Debug)]
 pub struct TimedSkyConfig {
     pub planet_tilt_degrees: f32,
     /// Desired duration of daylight in seconds.
@@ -59,7 +261,14 @@ pub struct TimedSkyConfig {
     /// Desired maximum sun height (altitude) in degrees during the day.
     pub max_sun_height_deg: f32,
     /// The entity representing the sun (usually a DirectionalLight).
+    #[entities]
     pub sun_entity: Entity,
+    /// Hemisphere of the calculated latitude.
+    pub hemisphere: HemispherePreference,
+    /// Side of the zenith the noon sun is on, which tells tropical latitudes apart.
+    pub noon_sun: NoonSunSide,
+    /// Half of the year the season is picked in.
+    pub season_half: SeasonHalf,
 }
 
 impl Default for TimedSkyConfig {
@@ -70,10 +279,261 @@ impl Default for TimedSkyConfig {
             day_duration_secs: 15.0,   // Example: 15s day
             night_duration_secs: 15.0, // Example: 15s night (total cycle 30s)
             max_sun_height_deg: 45.0,
+            hemisphere: HemispherePreference::default(),
+            noon_sun: NoonSunSide::default(),
+            season_half: SeasonHalf::default(),
+        }
+    }
+}
+
+/// Which hemisphere the timed solvers place the observer in.
+///
+/// Every timed config has a mirrored solution (negated latitude and declination),
+/// this picks between them.
+#[derive(Reflect, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[reflect(Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum HemispherePreference {
+    North,
+    /// Southern latitudes, where the sun culminates to the north.
+    South,
+    /// Northern latitudes for positive tilt, southern for negative tilt.
+    MatchTiltSign,
+    /// First valid candidate, preferring the sign combination typical for the
+    /// requested day length. Polar cases follow the tilt sign.
+    #[default]
+    Any,
+}
+
+impl HemispherePreference {
+    pub fn accepts(self, latitude_degrees: f32, planet_tilt_degrees: f32) -> bool {
+        match self {
+            HemispherePreference::North => latitude_degrees >= 0.0,
+            HemispherePreference::South => latitude_degrees <= 0.0,
+            HemispherePreference::MatchTiltSign => {
+                latitude_degrees == 0.0 || latitude_degrees.signum() == planet_tilt_degrees.signum()
+            }
+            HemispherePreference::Any => true,
+        }
+    }
+
+    /// Sign of the latitude used for perpetual day/night solutions.
+    fn polar_sign(self, planet_tilt_degrees: f32) -> f32 {
+        match self {
+            HemispherePreference::North => 1.0,
+            HemispherePreference::South => -1.0,
+            HemispherePreference::MatchTiltSign | HemispherePreference::Any => {
+                if planet_tilt_degrees < 0.0 {
+                    -1.0
+                } else {
+                    1.0
+                }
+            }
+        }
+    }
+}
+
+/// Which side of the zenith the noon sun is on, seen from the latitude picked by the
+/// timed solvers.
+///
+/// Between the tropics, a latitude and declination can swap places and give the same day
+/// length and noon height (latitude 10° with declination 23° and latitude 23° with
+/// declination 10°), this picks between them.
+#[derive(Reflect, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[reflect(Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum NoonSunSide {
+    /// The sun culminates towards the equator, the only option outside the tropics.
+    Equatorward,
+    /// The sun culminates towards the pole, between the equator and the declination.
+    Poleward,
+    /// First valid candidate.
+    #[default]
+    Any,
+}
+
+impl NoonSunSide {
+    pub fn accepts(
+        self,
+        latitude_degrees: f32,
+        declination_degrees: f32,
+        tolerance_deg: f32,
+    ) -> bool {
+        // Distance from the zenith to the noon sun, towards the equator
+        let equatorward_deg = (latitude_degrees - declination_degrees) * latitude_degrees.signum();
+        match self {
+            NoonSunSide::Any => true,
+            // No pole to face at the equator
+            _ if latitude_degrees.abs() <= tolerance_deg => true,
+            NoonSunSide::Equatorward => equatorward_deg >= -tolerance_deg,
+            NoonSunSide::Poleward => equatorward_deg <= tolerance_deg,
+        }
+    }
+}
+
+/// Half of the year the timed solvers pick the season in: every declination is reached
+/// twice a year, once on each side of a solstice.
+#[derive(Reflect, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[reflect(Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SeasonHalf {
+    /// Year fractions from 0.75 to 0.25, through the Vernal Equinox.
+    FromWinterSolstice,
+    /// Year fractions from 0.25 to 0.75, through the Autumnal Equinox.
+    FromSummerSolstice,
+    /// Whichever the solver picks first.
+    #[default]
+    Any,
+}
+
+impl SeasonHalf {
+    /// Year fraction in the middle of this half, `None` for `Any`.
+    fn middle_year_fraction(self) -> Option<f32> {
+        match self {
+            SeasonHalf::FromWinterSolstice => Some(0.0),
+            SeasonHalf::FromSummerSolstice => Some(0.5),
+            SeasonHalf::Any => None,
+        }
+    }
+}
+
+/// Tolerances used by the solvers instead of raw `f32::EPSILON` comparisons.
+///
+/// Durations are compared relative to the full cycle, so results don't depend on
+/// whether the cycle is 30 seconds or several hours long.
+#[derive(Debug, Clone, Copy)]
+pub struct SolverTolerances {
+    /// A day or night shorter than this fraction of the cycle counts as zero (perpetual night/day).
+    pub duration_relative: f32,
+    /// Absolute tolerance for angles (heights, declinations, tilt) in degrees.
+    pub angle_deg: f32,
+    /// Slack allowed on sine/cosine values before they count as out of [-1, 1].
+    pub trig: f32,
+    /// Iteration cap for solvers that refine their result.
+    pub max_iterations: u32,
+    /// Relative error (of the day fraction, and of the max height over 90°) accepted
+    /// by solvers that verify their result by forward simulation.
+    pub match_relative: f32,
+}
+
+impl Default for SolverTolerances {
+    fn default() -> Self {
+        Self {
+            duration_relative: 1e-6,
+            angle_deg: 1e-4,
+            trig: 1e-6,
+            max_iterations: 32,
+            match_relative: 1e-4,
+        }
+    }
+}
+
+/// Why a timed sky config can't be reached.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SkyConfigError {
+    InvalidDurations {
+        day_duration_secs: f32,
+        night_duration_secs: f32,
+    },
+    MaxHeightOutOfRange {
+        max_sun_height_deg: f32,
+    },
+    PerpetualNightRequiresZeroMaxHeight {
+        max_sun_height_deg: f32,
+    },
+    PerpetualDayRequiresPositiveMaxHeight {
+        max_sun_height_deg: f32,
+    },
+    /// Without tilt the declination is always 0, so day and night are always equal.
+    ZeroTiltRequiresEqualDayNight,
+    DeclinationExceedsTilt {
+        required_declination_deg: f32,
+        planet_tilt_degrees: f32,
+    },
+    /// The day is too short to let the sun climb this high.
+    MaxHeightTooHighForDayFraction {
+        max_sun_height_deg: f32,
+        day_fraction: f32,
+    },
+    /// A day longer or shorter than the night needs the sun above the horizon at noon.
+    MaxHeightTooLowForDayFraction {
+        max_sun_height_deg: f32,
+        day_fraction: f32,
+    },
+    /// Numerically degenerate input close to perpetual night.
+    Indeterminate,
+    /// No latitude/declination pair within the tilt (and hemisphere preference) matches.
+    NoValidSolution,
+}
+
+impl std::fmt::Display for SkyConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SkyConfigError::InvalidDurations {
+                day_duration_secs,
+                night_duration_secs,
+            } => write!(
+                f,
+                "Invalid timed durations: day={}s, night={}s. Cannot calculate.",
+                day_duration_secs, night_duration_secs
+            ),
+            SkyConfigError::MaxHeightOutOfRange { max_sun_height_deg } => write!(
+                f,
+                "Max sun height {:.2}° is outside valid range [0°, 90°]. Cannot calculate.",
+                max_sun_height_deg
+            ),
+            SkyConfigError::PerpetualNightRequiresZeroMaxHeight { max_sun_height_deg } => write!(
+                f,
+                "Perpetual night requested but max sun height is {:.2}°. Impossible.",
+                max_sun_height_deg
+            ),
+            SkyConfigError::PerpetualDayRequiresPositiveMaxHeight { max_sun_height_deg } => write!(
+                f,
+                "Perpetual day requested but max sun height is {:.2}°. Impossible (must be > 0 unless 12/12).",
+                max_sun_height_deg
+            ),
+            SkyConfigError::ZeroTiltRequiresEqualDayNight => write!(
+                f,
+                "Planet tilt is 0°, only equal day and night (12/12 cycle) is possible."
+            ),
+            SkyConfigError::DeclinationExceedsTilt {
+                required_declination_deg,
+                planet_tilt_degrees,
+            } => write!(
+                f,
+                "Required declination {:.2}° exceeds planet tilt {:.2}°. Impossible.",
+                required_declination_deg, planet_tilt_degrees
+            ),
+            SkyConfigError::MaxHeightTooHighForDayFraction {
+                max_sun_height_deg,
+                day_fraction,
+            } => write!(
+                f,
+                "Impossible combination: Max height {:.2}° is too high for day fraction {:.2}.",
+                max_sun_height_deg, day_fraction
+            ),
+            SkyConfigError::MaxHeightTooLowForDayFraction {
+                max_sun_height_deg,
+                day_fraction,
+            } => write!(
+                f,
+                "Impossible combination: Max height {:.2}° is too low for day fraction {:.2}.",
+                max_sun_height_deg, day_fraction
+            ),
+            SkyConfigError::Indeterminate => write!(
+                f,
+                "Reached indeterminate case for cos(lat+dec) near day_fraction 0."
+            ),
+            SkyConfigError::NoValidSolution => write!(
+                f,
+                "No valid latitude/declination found for the given constraints."
+            ),
         }
     }
 }
 
+impl std::error::Error for SkyConfigError {}
+
 /// Calculates required latitude and year fraction to achieve a specific day/night
 /// duration ratio and maximum sun height (noon altitude) for a given planet tilt.
 ///
@@ -85,111 +545,158 @@ impl Default for TimedSkyConfig {
 /// - day_duration_secs: The target duration of daylight in seconds.
 /// - night_duration_secs: The target duration of nighttime in seconds.
 /// - max_sun_height_deg: The target maximum altitude of the sun in degrees.
+/// - hemisphere: Which hemisphere the returned latitude should be in.
 ///
 /// Returns:
-/// An `Option<(latitude_degrees, year_fraction, calculated_declination_degrees)>`.
-/// Returns `None` if the requested parameters are impossible for the given tilt
-/// (e.g., max height too high/low for the day length, or required declination
+/// `(latitude_degrees, year_fraction, calculated_declination_degrees)`, or a
+/// [`SkyConfigError`] telling why the requested parameters are impossible for the given
+/// tilt (e.g., max height too high/low for the day length, or required declination
 /// exceeds the planet tilt).
-#[allow(non_snake_case)]
 pub fn calculate_latitude_yearfraction(
     planet_tilt_degrees: f32,
     day_duration_secs: f32,
     night_duration_secs: f32,
     max_sun_height_deg: f32,
-) -> Option<(f32, f32, f32)> {
+    hemisphere: HemispherePreference,
+) -> Result<(f32, f32, f32), SkyConfigError> {
+    calculate_latitude_yearfraction_with_tolerances(
+        planet_tilt_degrees,
+        day_duration_secs,
+        night_duration_secs,
+        max_sun_height_deg,
+        hemisphere,
+        &SolverTolerances::default(),
+    )
+}
+
+/// Same as [`calculate_latitude_yearfraction`] with explicit tolerances.
+pub fn calculate_latitude_yearfraction_with_tolerances(
+    planet_tilt_degrees: f32,
+    day_duration_secs: f32,
+    night_duration_secs: f32,
+    max_sun_height_deg: f32,
+    hemisphere: HemispherePreference,
+    tolerances: &SolverTolerances,
+) -> Result<(f32, f32, f32), SkyConfigError> {
+    closed_form_latitude_yearfraction(
+        planet_tilt_degrees,
+        day_duration_secs,
+        night_duration_secs,
+        max_sun_height_deg,
+        hemisphere,
+        NoonSunSide::Any,
+        tolerances,
+    )
+}
+
+#[deprecated(note = "use `calculate_latitude_yearfraction_with_tolerances`")]
+pub fn try_calculate_latitude_yearfraction(
+    planet_tilt_degrees: f32,
+    day_duration_secs: f32,
+    night_duration_secs: f32,
+    max_sun_height_deg: f32,
+    hemisphere: HemispherePreference,
+    tolerances: &SolverTolerances,
+) -> Result<(f32, f32, f32), SkyConfigError> {
+    calculate_latitude_yearfraction_with_tolerances(
+        planet_tilt_degrees,
+        day_duration_secs,
+        night_duration_secs,
+        max_sun_height_deg,
+        hemisphere,
+        tolerances,
+    )
+}
+
+/// Closed-form solve behind [`calculate_latitude_yearfraction_with_tolerances`].
+fn closed_form_latitude_yearfraction(
+    planet_tilt_degrees: f32,
+    day_duration_secs: f32,
+    night_duration_secs: f32,
+    max_sun_height_deg: f32,
+    hemisphere: HemispherePreference,
+    noon_sun: NoonSunSide,
+    tolerances: &SolverTolerances,
+) -> Result<(f32, f32, f32), SkyConfigError> {
     let total_duration_secs = day_duration_secs + night_duration_secs;
     let tilt_rad = planet_tilt_degrees.abs() * DEGREES_TO_RADIANS;
+    let angle_tolerance_rad = tolerances.angle_deg * DEGREES_TO_RADIANS;
 
-    if total_duration_secs <= f32::EPSILON || day_duration_secs < 0.0 || night_duration_secs < 0.0 {
-        warn!(
-            "Invalid timed durations: day={}s, night={}s. Cannot calculate.",
-            day_duration_secs, night_duration_secs
-        );
-        return None;
+    if total_duration_secs <= 0.0 || day_duration_secs < 0.0 || night_duration_secs < 0.0 {
+        return Err(SkyConfigError::InvalidDurations {
+            day_duration_secs,
+            night_duration_secs,
+        });
     }
 
     if max_sun_height_deg < -0.1 || max_sun_height_deg > 90.0 + 0.1 {
         // Allow slight floating point deviations
-        warn!(
-            "Max sun height {:.2}° is outside valid range [0°, 90°]. Cannot calculate.",
-            max_sun_height_deg
-        );
-        return None;
+        return Err(SkyConfigError::MaxHeightOutOfRange { max_sun_height_deg });
     }
 
+    // Durations are compared relative to the whole cycle, so the solver behaves the same
+    // for a 30 second cycle and for a 10 hour one.
+    let day_fraction = day_duration_secs / total_duration_secs;
+    let night_fraction = night_duration_secs / total_duration_secs;
+
     // Handle edge cases: Perpetual Day/Night or 12/12 cycle
-    if day_duration_secs < f32::EPSILON && night_duration_secs > f32::EPSILON {
+    if day_fraction < tolerances.duration_relative {
         // Perpetual Night (day_fraction = 0)
         // Requires sun never rises, i.e. max altitude <= 0.
-        if max_sun_height_deg > f32::EPSILON {
-            warn!(
-                "Perpetual night requested but max sun height is {:.2}°. Impossible.",
-                max_sun_height_deg
-            );
-            return None;
+        if max_sun_height_deg > tolerances.angle_deg {
+            return Err(SkyConfigError::PerpetualNightRequiresZeroMaxHeight { max_sun_height_deg });
         }
         // Max height is 0. This happens at latitudes where sun circles the horizon.
         // This occurs at latitude = 90 - |dec|. For perpetual night at a pole-like lat,
         // we need dec to be -tilt (NH winter) or +tilt (SH winter).
         // Latitude is 90 - tilt. Year fraction is 0.75 (NH) or 0.25 (SH).
-        if tilt_rad < f32::EPSILON {
-            warn!("Perpetual night with 0 tilt is impossible unless at equator (12/12 cycle).");
-            return None; // 0 tilt implies 12/12 cycle everywhere.
+        if tilt_rad < angle_tolerance_rad {
+            return Err(SkyConfigError::ZeroTiltRequiresEqualDayNight); // 0 tilt implies 12/12 cycle everywhere.
         }
-        let calculated_latitude_degrees =
-            (90.0 - planet_tilt_degrees.abs()).copysign(-planet_tilt_degrees); // Choose pole opposite tilt
-        let calculated_declination_degrees = -planet_tilt_degrees.copysign(planet_tilt_degrees); // Winter solstice dec
-        let calculated_year_fraction = if planet_tilt_degrees > 0.0 {
-            0.75
-        } else {
-            0.25
-        }; // NH Winter or SH Winter
+        let hemisphere_sign = hemisphere.polar_sign(planet_tilt_degrees);
+        let calculated_latitude_degrees = (90.0 - planet_tilt_degrees.abs()) * hemisphere_sign;
+        let calculated_declination_degrees = -planet_tilt_degrees.abs() * hemisphere_sign; // Winter solstice dec
+        let calculated_year_fraction = if hemisphere_sign > 0.0 { 0.75 } else { 0.25 }; // NH Winter or SH Winter
         // info!("Perpetual night calculation: Lat {:.2}°, Dec {:.2}°, YF {:.2}", calculated_latitude_degrees, calculated_declination_degrees, calculated_year_fraction);
-        return Some((
+        return Ok((
             calculated_latitude_degrees,
             calculated_year_fraction,
             calculated_declination_degrees,
         ));
     }
 
-    if night_duration_secs < f32::EPSILON && day_duration_secs > f32::EPSILON {
+    if night_fraction < tolerances.duration_relative {
         // Perpetual Day (day_fraction = 1)
         // Requires sun never sets, i.e. min altitude >= 0.
         // Max height must be > 0 (unless at pole/equinox/tilt=0 which implies 12/12 max height 0).
-        if max_sun_height_deg < f32::EPSILON {
-            warn!(
-                "Perpetual day requested but max sun height is {:.2}°. Impossible (must be > 0 unless 12/12).",
-                max_sun_height_deg
-            );
-            return None; // Perpetual day usually has max height > 0. Max height 0 is the 12/12 case.
+        if max_sun_height_deg < tolerances.angle_deg {
+            // Perpetual day usually has max height > 0. Max height 0 is the 12/12 case.
+            return Err(SkyConfigError::PerpetualDayRequiresPositiveMaxHeight {
+                max_sun_height_deg,
+            });
         }
         // Max height > 0. Perpetual day happens at latitudes polewards of 90 - tilt during summer solstice.
         // Max height = 90 - |lat - dec|. Min height = 90 - |lat + dec|.
         // At lat = 90 - tilt, summer solstice (dec=tilt), max height = 90 - (90-tilt - tilt) = 2*tilt. Min height = 90 - (90-tilt + tilt) = 0.
         // For max height H > 0 and perpetual day, required dec = H/2, required lat = 90 - H/2.
-        if tilt_rad < f32::EPSILON {
-            warn!("Perpetual day with 0 tilt is impossible unless at equator (12/12 cycle).");
-            return None; // 0 tilt implies 12/12 cycle everywhere.
+        if tilt_rad < angle_tolerance_rad {
+            return Err(SkyConfigError::ZeroTiltRequiresEqualDayNight); // 0 tilt implies 12/12 cycle everywhere.
         }
         let max_height_rad = max_sun_height_deg * DEGREES_TO_RADIANS;
         let required_dec_rad = max_height_rad / 2.0;
-        if required_dec_rad.abs() > tilt_rad + f32::EPSILON {
-            warn!(
-                "Required declination {:.2}° for perpetual day with max height {:.2}° exceeds planet tilt {:.2}°. Impossible.",
-                required_dec_rad * RADIANS_TO_DEGREES,
-                max_sun_height_deg,
-                planet_tilt_degrees
-            );
-            return None;
+        if required_dec_rad.abs() > tilt_rad + angle_tolerance_rad {
+            return Err(SkyConfigError::DeclinationExceedsTilt {
+                required_declination_deg: required_dec_rad * RADIANS_TO_DEGREES,
+                planet_tilt_degrees,
+            });
         }
         let calculated_latitude_degrees =
             (90.0 * DEGREES_TO_RADIANS - required_dec_rad) * RADIANS_TO_DEGREES;
         let calculated_declination_degrees = required_dec_rad * RADIANS_TO_DEGREES;
         // Summer solstice requires dec > 0 if lat > 0, or dec < 0 if lat < 0.
-        // We aim for positive latitude hemisphere:
-        let final_lat_deg = calculated_latitude_degrees.copysign(planet_tilt_degrees); // Use tilt sign to pick hemisphere
-        let final_dec_deg = calculated_declination_degrees.copysign(planet_tilt_degrees); // Dec must match hemi for summer
+        let hemisphere_sign = hemisphere.polar_sign(planet_tilt_degrees);
+        let final_lat_deg = calculated_latitude_degrees * hemisphere_sign;
+        let final_dec_deg = calculated_declination_degrees * hemisphere_sign; // Dec must match hemi for summer
         let sin_yf_angle = final_dec_deg * DEGREES_TO_RADIANS / tilt_rad;
         let phi = sin_yf_angle.clamp(-1.0, 1.0).asin();
         let calculated_year_fraction = if final_dec_deg >= 0.0 {
@@ -199,15 +706,19 @@ pub fn calculate_latitude_yearfraction(
         };
 
         // info!("Perpetual day calculation: Lat {:.2}°, Dec {:.2}°, YF {:.2}", final_lat_deg, final_dec_deg, calculated_year_fraction);
-        return Some((final_lat_deg, calculated_year_fraction, final_dec_deg));
+        return Ok((final_lat_deg, calculated_year_fraction, final_dec_deg));
     }
 
-    if total_duration_secs <= f32::EPSILON {
-        warn!("Total duration is zero.");
-        return None;
+    // Any day other than 12/12 needs the sun to climb above the horizon at noon
+    if max_sun_height_deg < tolerances.angle_deg
+        && (day_fraction - 0.5).abs() > tolerances.duration_relative
+    {
+        return Err(SkyConfigError::MaxHeightTooLowForDayFraction {
+            max_sun_height_deg,
+            day_fraction,
+        });
     }
 
-    let day_fraction = day_duration_secs / total_duration_secs;
     let max_height_rad = max_sun_height_deg * DEGREES_TO_RADIANS;
 
     let C = (PI * day_fraction).cos();
@@ -217,32 +728,30 @@ pub fn calculate_latitude_yearfraction(
     // cos(lat_rad - dec_rad) = sin(max_height_rad)
     // cos(lat_rad + dec_rad) = sin(max_height_rad) * (1 + cos(PI * day_fraction)) / (1 - cos(PI * day_fraction))
 
-    let term_for_cos_sum = if (1.0 - C).abs() < f32::EPSILON {
+    let term_for_cos_sum = if (1.0 - C).abs() < tolerances.trig {
         // Handle day_fraction near 0 (C near 1)
-        if S_h > f32::EPSILON {
+        if S_h > tolerances.trig {
             // Max height > 0 with day fraction near 0 (perpetual night)
-            warn!(
-                "Impossible combination: Max height {:.2}° requires sun rise, but day fraction {:.2} requests near perpetual night.",
-                max_sun_height_deg, day_fraction
-            );
-            return None;
+            return Err(SkyConfigError::MaxHeightTooHighForDayFraction {
+                max_sun_height_deg,
+                day_fraction,
+            });
         } else {
             // Max height near 0 with day fraction near 0 (perpetual night on horizon)
             // This case should be handled by the perpetual night block above.
-            // If we reach here, something is slightly off. Return None or default.
-            warn!("Reached indeterminate case for cos(lat+dec) near day_fraction 0.");
-            return None;
+            // If we reach here, something is slightly off.
+            return Err(SkyConfigError::Indeterminate);
         }
     } else {
         S_h * (1.0 + C) / (1.0 - C)
     };
 
-    if term_for_cos_sum.abs() > 1.0 + f32::EPSILON {
-        warn!(
-            "Impossible combination: Max height {:.2}° and day fraction {:.2} requires cos(lat+dec) value {:.2} outside [-1, 1].",
-            max_sun_height_deg, day_fraction, term_for_cos_sum
-        );
-        return None;
+    if term_for_cos_sum.abs() > 1.0 + tolerances.trig {
+        // cos(lat+dec) outside [-1, 1]
+        return Err(SkyConfigError::MaxHeightTooHighForDayFraction {
+            max_sun_height_deg,
+            day_fraction,
+        });
     }
 
     let beta = term_for_cos_sum.clamp(-1.0, 1.0).acos(); // angle for lat + dec
@@ -269,15 +778,29 @@ pub fn calculate_latitude_yearfraction(
 
     let mut found_lat_rad = None;
     let mut found_dec_rad = None;
+    // Smallest declination of the candidates only rejected for the tilt, for the error
+    let mut closest_out_of_tilt_dec_deg: Option<f32> = None;
 
     for (lat_candidate, dec_candidate) in candidates.iter() {
         let lat_deg = lat_candidate * RADIANS_TO_DEGREES;
         let dec_deg = dec_candidate * RADIANS_TO_DEGREES;
 
+        if dec_deg.abs() > planet_tilt_degrees.abs() + tolerances.angle_deg
+            && lat_deg.abs() <= 90.0 + tolerances.angle_deg
+            && hemisphere.accepts(lat_deg, planet_tilt_degrees)
+            && noon_sun.accepts(lat_deg, dec_deg, tolerances.angle_deg)
+            && closest_out_of_tilt_dec_deg.is_none_or(|closest| dec_deg.abs() < closest.abs())
+        {
+            closest_out_of_tilt_dec_deg = Some(dec_deg);
+        }
+
         // Check if dec is achievable with the planet tilt
-        if dec_deg.abs() <= planet_tilt_degrees.abs() + f32::EPSILON {
-            // Check if latitude is valid
-            if lat_deg.abs() <= 90.0 + f32::EPSILON {
+        if dec_deg.abs() <= planet_tilt_degrees.abs() + tolerances.angle_deg {
+            // Check if latitude is valid and in the requested hemisphere
+            if lat_deg.abs() <= 90.0 + tolerances.angle_deg
+                && hemisphere.accepts(lat_deg, planet_tilt_degrees)
+                && noon_sun.accepts(lat_deg, dec_deg, tolerances.angle_deg)
+            {
                 // Found a valid pair. Check if it matches our preferred sign combo.
                 let current_lat_sign = lat_deg.signum();
                 let current_dec_sign = dec_deg.signum();
@@ -310,17 +833,13 @@ pub fn calculate_latitude_yearfraction(
             let calculated_declination_degrees = dec_rad * RADIANS_TO_DEGREES;
 
             // Now find the year fraction corresponding to this declination and tilt
-            if tilt_rad < f32::EPSILON {
+            if tilt_rad < angle_tolerance_rad {
                 // Handle 0 tilt separately
-                if dec_rad.abs() > f32::EPSILON {
-                    warn!(
-                        "Calculated non-zero declination {:.2}° but tilt is 0°. Impossible.",
-                        calculated_declination_degrees
-                    );
-                    return None;
+                if dec_rad.abs() > angle_tolerance_rad {
+                    return Err(SkyConfigError::ZeroTiltRequiresEqualDayNight);
                 }
                 // If dec is 0 and tilt is 0, any year fraction works, but let's pick equinox.
-                return Some((
+                return Ok((
                     calculated_latitude_degrees,
                     0.0,
                     calculated_declination_degrees,
@@ -368,21 +887,158 @@ pub fn calculate_latitude_yearfraction(
             //  info!("Calculated parameters: Latitude {:.2}°, Declination {:.2}°, Year Fraction {:.4}",
             //        calculated_latitude_degrees, calculated_declination_degrees, final_yf);
 
-            Some((
+            Ok((
                 calculated_latitude_degrees,
                 final_yf,
                 calculated_declination_degrees,
             ))
         }
-        _ => {
-            warn!("No valid latitude/declination found for the given constraints.");
-            None
+        _ => Err(match closest_out_of_tilt_dec_deg {
+            Some(required_declination_deg) => SkyConfigError::DeclinationExceedsTilt {
+                required_declination_deg,
+                planet_tilt_degrees,
+            },
+            None => SkyConfigError::NoValidSolution,
+        }),
+    }
+}
+
+/// Result of [`calculate_latitude_yearfraction_checked`], including the values
+/// the sky actually produces with the returned parameters.
+#[derive(Debug, Clone, Copy)]
+pub struct CheckedSolution {
+    pub latitude_degrees: f32,
+    pub year_fraction: f32,
+    pub declination_degrees: f32,
+    pub achieved_day_duration_secs: f32,
+    pub achieved_night_duration_secs: f32,
+    pub achieved_max_sun_height_deg: f32,
+    /// Newton iterations spent refining (0 if the closed-form answer was already exact).
+    pub iterations: u32,
+}
+
+/// Checked variant of [`calculate_latitude_yearfraction`].
+///
+/// The closed-form result is forward-simulated (day fraction and noon altitude) and,
+/// if it misses the request, the other candidate branches and a coarse grid are used as
+/// starting points for a Newton refinement on (latitude, declination). Returns the first
+/// solution matching the request within `tolerances.match_relative`, or `None`.
+///
+/// Like the other solvers, the year fraction is on a circular orbit. For an eccentric sky,
+/// use [`SkyCenter::update_from_timed_config_checked`], which checks the sky on its orbit.
+pub fn calculate_latitude_yearfraction_checked(
+    planet_tilt_degrees: f32,
+    day_duration_secs: f32,
+    night_duration_secs: f32,
+    max_sun_height_deg: f32,
+    hemisphere: HemispherePreference,
+    tolerances: &SolverTolerances,
+) -> Option<CheckedSolution> {
+    let total_duration_secs = day_duration_secs + night_duration_secs;
+    if total_duration_secs <= 0.0 || day_duration_secs < 0.0 || night_duration_secs < 0.0 {
+        return None;
+    }
+    let target_day_fraction = day_duration_secs / total_duration_secs;
+    let max_tilt = planet_tilt_degrees.abs();
+
+    let residual = |lat_deg: f32, dec_deg: f32| {
+        let (day_fraction, max_height_deg) = simulate_day_fraction_and_height(lat_deg, dec_deg);
+        Vec2::new(
+            day_fraction - target_day_fraction,
+            (max_height_deg - max_sun_height_deg) / 90.0,
+        )
+    };
+    let matches = |r: Vec2| r.abs().max_element() <= tolerances.match_relative;
+
+    // Seeds: closed-form pick first, then its sibling branches, then a coarse grid
+    let closed_form = closed_form_latitude_yearfraction(
+        planet_tilt_degrees,
+        day_duration_secs,
+        night_duration_secs,
+        max_sun_height_deg,
+        hemisphere,
+        NoonSunSide::Any,
+        tolerances,
+    )
+    .ok();
+    let mut seeds = Vec::new();
+    let mut near_year_fraction = 0.25;
+    if let Some((lat, yf, dec)) = closed_form {
+        near_year_fraction = yf;
+        let (a, b) = ((lat - dec).abs(), (lat + dec).abs());
+        seeds.push((lat, dec));
+        for (sa, sb) in [(1.0, 1.0), (1.0, -1.0), (-1.0, 1.0), (-1.0, -1.0)] {
+            seeds.push(((sa * a + sb * b) / 2.0, (sb * b - sa * a) / 2.0));
+        }
+    }
+    for lat in [-75.0, -45.0, -15.0, 15.0, 45.0, 75.0] {
+        for dec in [-max_tilt * 0.5, 0.0, max_tilt * 0.5] {
+            seeds.push((lat, dec));
+        }
+    }
+
+    for (seed_lat, seed_dec) in seeds {
+        let mut lat = seed_lat.clamp(-90.0, 90.0);
+        let mut dec = seed_dec.clamp(-max_tilt, max_tilt);
+        let mut iterations = 0;
+        let mut r = residual(lat, dec);
+
+        while !matches(r) && iterations < tolerances.max_iterations {
+            iterations += 1;
+            // Finite difference Jacobian, degrees in, residual units out
+            let h = 1e-2;
+            let d_lat = (residual(lat + h, dec) - residual(lat - h, dec)) / (2.0 * h);
+            let d_dec = (residual(lat, dec + h) - residual(lat, dec - h)) / (2.0 * h);
+            let jacobian = Mat2::from_cols(d_lat, d_dec);
+            if jacobian.determinant().abs() < 1e-8 {
+                break; // Flat region (polar day/night), Newton can't move
+            }
+            // Limit the step to keep the iteration on the current branch
+            let step = (jacobian.inverse() * r).clamp_length_max(10.0);
+            lat = (lat - step.x).clamp(-90.0, 90.0);
+            dec = (dec - step.y).clamp(-max_tilt, max_tilt);
+            r = residual(lat, dec);
+        }
+
+        if !matches(r) || !hemisphere.accepts(lat, planet_tilt_degrees) {
+            continue;
         }
+
+        let tilt_rad = planet_tilt_degrees * DEGREES_TO_RADIANS;
+        let Some(year_fraction) =
+            year_fraction_from_declination(dec * DEGREES_TO_RADIANS, tilt_rad, near_year_fraction)
+        else {
+            continue;
+        };
+        let (day_fraction, max_height_deg) = simulate_day_fraction_and_height(lat, dec);
+
+        return Some(CheckedSolution {
+            latitude_degrees: lat,
+            year_fraction,
+            declination_degrees: dec,
+            achieved_day_duration_secs: day_fraction * total_duration_secs,
+            achieved_night_duration_secs: (1.0 - day_fraction) * total_duration_secs,
+            achieved_max_sun_height_deg: max_height_deg,
+            iterations,
+        });
     }
+
+    None
+}
+
+/// Forward model for the timed solvers: (day fraction, noon altitude in degrees).
+fn simulate_day_fraction_and_height(latitude_degrees: f32, declination_degrees: f32) -> (f32, f32) {
+    let day_fraction = calculate_day_fraction(
+        latitude_degrees * DEGREES_TO_RADIANS,
+        declination_degrees * DEGREES_TO_RADIANS,
+    );
+    let max_height_deg = 90.0 - (latitude_degrees - declination_degrees).abs();
+    (day_fraction, max_height_deg)
 }
 
-#[derive(Component, Debug, Clone)]
-#[require(Transform, Visibility)]
+#[derive(Component, Reflect, Debug, Clone)]
+#[reflect(Component, Default, Debug)]
+#[require(Transform, Visibility, SunState, TimeOfDayPhase, PreciseCycleTime)]
 pub struct SkyCenter {
     pub latitude_degrees: f32,
     pub planet_tilt_degrees: f32,
@@ -394,11 +1050,50 @@ pub struct SkyCenter {
     pub cycle_duration_secs: f32,
 
     /// The entity representing the sun (usually a DirectionalLight).
+    /// Remapped when spawned from a scene.
+    #[entities]
     pub sun: Entity,
 
     /// Time elapsed within the current cycle (seconds).
-    /// Stored here to allow pausing/setting time easily.
+    /// Stored here to allow pausing/setting time easily. The clock itself runs in double
+    /// precision, see [`PreciseCycleTime`].
     pub current_cycle_time: f32,
+
+    /// Stops advancing `current_cycle_time` while keeping the sun in place.
+    pub paused: bool,
+
+    /// Multiplier applied to the clock delta (2.0 runs the cycle twice as fast).
+    pub time_scale: f32,
+
+    /// Extra multipliers applied during each `TimeOfDayPhase`, e.g. to play the night
+    /// three times faster than the day. The sun path itself is unchanged, only the time
+    /// spent on each part of it, so events and hour fractions stay consistent.
+    pub phase_time_scales: PhaseTimeScales,
+
+    /// Eccentricity of the planet's orbit (0.0 circular, Earth 0.0167). The planet moves
+    /// faster near perihelion, so the seasons stop being a plain sine over the year.
+    pub orbital_eccentricity: f32,
+
+    /// Year fraction of the closest approach to the sun (Earth: early January, ~0.79).
+    pub perihelion_year_fraction: f32,
+
+    /// Applies the equation of time: the sun runs up to ~16 minutes ahead or behind the
+    /// clock over the year, so solar noon drifts like on Earth (the analemma).
+    pub equation_of_time: bool,
+
+    /// Midnights passed by the running clock since the sky started (catch-ups included),
+    /// minus the ones crossed back by a rewinding `SunClock`. Setting `current_cycle_time`
+    /// directly doesn't change it.
+    pub day_index: u64,
+
+    /// Cycles per year. When set, `year_fraction` advances by `1 / days_per_year` each
+    /// completed cycle so the seasons change over time. `None` keeps the season frozen.
+    pub days_per_year: Option<f32>,
+
+    /// Apparent diameter of the sun disk in degrees (0.53 on Earth). Sunrise and sunset
+    /// happen when its upper limb crosses the horizon, and the time of day phases and
+    /// `SunIlluminance` follow the upper limb too. 0.0 uses the center of the sun.
+    pub sun_angular_diameter_deg: f32,
 }
 
 impl Default for SkyCenter {
@@ -410,148 +1105,2424 @@ impl Default for SkyCenter {
             cycle_duration_secs: 600.0, // 10 minutes by default
             sun: Entity::PLACEHOLDER,
             current_cycle_time: 0.0,
+            paused: false,
+            time_scale: 1.0,
+            phase_time_scales: PhaseTimeScales::default(),
+            orbital_eccentricity: 0.0,
+            perihelion_year_fraction: 0.79,
+            equation_of_time: false,
+            day_index: 0,
+            days_per_year: None,
+            sun_angular_diameter_deg: 0.53,
         }
     }
 }
 
-impl SkyCenter {
-    pub fn from_timed_config(timed_config: &TimedSkyConfig) -> Option<Self> {
-        let calc = calculate_latitude_yearfraction(
-            timed_config.planet_tilt_degrees,
-            timed_config.day_duration_secs,
-            timed_config.night_duration_secs,
-            timed_config.max_sun_height_deg,
-        );
+/// Double precision clock behind `SkyCenter::current_cycle_time`. Long cycles (a real-time
+/// day is 86400 s) leave an `f32` too coarse to add frame deltas to, so the sun would move
+/// in visible steps. Added automatically with the `SkyCenter`; setting `current_cycle_time`
+/// directly still works, the clock picks it up on the next update.
+#[derive(Component, Reflect, Debug, Default, Clone, Copy)]
+#[reflect(Component, Default, Debug)]
+pub struct PreciseCycleTime {
+    secs: f64,
+    /// `current_cycle_time` as last written, to notice changes from outside.
+    written: f32,
+    /// Sky seconds added to the next update, see [`PreciseCycleTime::skip`].
+    skipped_secs: f64,
+}
 
-        if let Some((latitude, year_fraction, _)) = calc {
-            Some(Self {
-                latitude_degrees: latitude,
-                planet_tilt_degrees: timed_config.planet_tilt_degrees,
-                year_fraction,
-                cycle_duration_secs: timed_config.day_duration_secs
-                    + timed_config.night_duration_secs,
-                sun: timed_config.sun_entity,
-                current_cycle_time: 0.0,
-            })
-        } else {
-            warn!("Failed to calculate latitude/year_fraction/declination for timed sky config.");
-            None
+impl PreciseCycleTime {
+    /// Time elapsed within the current cycle, in seconds.
+    pub fn secs(&self) -> f64 {
+        self.secs
+    }
+
+    pub fn duration(&self) -> std::time::Duration {
+        std::time::Duration::from_secs_f64(self.secs.max(0.0))
+    }
+
+    /// Adds `secs` of sky time to the next update, on top of the frame delta, as if they
+    /// passed during that frame: the day index, seasons and events (or a `SkyCatchUp`)
+    /// follow. `paused`, `time_scale` and the time warp are not applied, and a `SkyTick`
+    /// ignores it.
+    pub fn skip(&mut self, secs: f64) {
+        self.skipped_secs += secs;
+    }
+
+    /// Advances the clock of `sky_center` by `delta_secs` (wrapping at the cycle end), and
+    /// returns the hour fractions before and after.
+    fn advance(&mut self, sky_center: &mut SkyCenter, delta_secs: f64) -> (f32, f32) {
+        let previous_hour_fraction = self.hour_fraction(sky_center);
+        (
+            previous_hour_fraction,
+            self.write(sky_center, self.secs + delta_secs),
+        )
+    }
+
+    /// Sets the clock of `sky_center` to `cycle_secs` into the cycle, and returns the hour
+    /// fractions before and after.
+    fn set(&mut self, sky_center: &mut SkyCenter, cycle_secs: f64) -> (f32, f32) {
+        let previous_hour_fraction = self.hour_fraction(sky_center);
+        (previous_hour_fraction, self.write(sky_center, cycle_secs))
+    }
+
+    /// Current hour fraction, picking up `current_cycle_time` if it was changed from outside.
+    fn hour_fraction(&mut self, sky_center: &SkyCenter) -> f32 {
+        if self.written != sky_center.current_cycle_time {
+            self.secs = sky_center.current_cycle_time as f64;
         }
+        (self.secs / sky_center.cycle_duration_secs as f64) as f32
     }
 
-    #[allow(dead_code)]
-    fn update_from_timed_config(&mut self, timed_config: &TimedSkyConfig) {
-        let calc = calculate_latitude_yearfraction(
-            timed_config.planet_tilt_degrees,
-            timed_config.day_duration_secs,
-            timed_config.night_duration_secs,
-            timed_config.max_sun_height_deg,
-        );
+    /// Writes `secs` (wrapped at the cycle end) to the clock and returns its hour fraction.
+    fn write(&mut self, sky_center: &mut SkyCenter, secs: f64) -> f32 {
+        let cycle_duration = sky_center.cycle_duration_secs as f64;
+        self.secs = secs.rem_euclid(cycle_duration);
 
-        if let Some((latitude, year_fraction, _)) = calc {
-            self.latitude_degrees = latitude;
-            self.year_fraction = year_fraction;
-            self.cycle_duration_secs =
-                timed_config.day_duration_secs + timed_config.night_duration_secs;
-            self.sun = timed_config.sun_entity;
-        } else {
-            warn!("Failed to calculate latitude/year_fraction/declination for timed sky config.");
+        // Rounding may land on the cycle end, which is the next midnight
+        let mut cycle_time = self.secs as f32;
+        if cycle_time >= sky_center.cycle_duration_secs {
+            cycle_time = 0.0;
         }
+        sky_center.current_cycle_time = cycle_time;
+        self.written = cycle_time;
+        (self.secs / cycle_duration) as f32
     }
 }
 
-/// Calculates the sun's direction vector in the observer's local coordinate frame (Y up, X east, Z north).
-/// This vector points *from* the observer *towards* the sun.
-///
-/// Based on standard astronomical formulas converting equatorial coordinates (declination, hour angle)
-/// to horizontal coordinates (altitude, azimuth).
-///
-/// Args:
-/// - hour_fraction: Fraction of the day (0.0 to 1.0), where 0.0 is midnight, 0.5 is noon.
-/// - latitude_rad: Observer's latitude in radians (-PI/2 to PI/2).
-/// - axial_tilt_rad: Planet's axial tilt in radians (e.g., 23.5 degrees for Earth).
-/// - year_fraction: Fraction of the year (0.0 to 1.0), where 0.0 is Vernal Equinox.
+/// Stateless clock for rollback netcode (GGRS, backroll): add next to a `SkyCenter` and the
+/// sky is evaluated from `tick` alone every update, instead of accumulating frame deltas.
+/// Rolling back and resimulating frames lands on the same sky, never advancing it twice.
 ///
-/// Returns:
-/// A `Vec3` representing the sun's direction relative to the observer.
-/// The vector length is arbitrary, usually normalized.
-pub fn calculate_sun_direction(
-    hour_fraction: f32,
-    latitude_rad: f32,
-    axial_tilt_rad: f32,
-    year_fraction: f32,
-) -> Vec3 {
-    // Calculate sun's declination based on axial tilt and time of year.
-    // Assuming year_fraction 0.0 is Vernal Equinox (dec=0), 0.25 is Summer Solstice (dec=tilt), etc.
-    let year_angle_rad = year_fraction * 2.0 * PI;
-    let dec_rad = axial_tilt_rad * year_angle_rad.sin();
-
-    // Calculate Local Hour Angle (LHA). This is angle from local meridian (South/North line).
-    // hour_fraction 0.0 is midnight, 0.5 is noon. LHA is 0 at noon, PI 12 hours later.
-    // hour_angle_rad from midnight = hour_fraction * 2.0 * PI.
-    // Local Hour Angle (HA) is angle west of meridian. HA=0 at noon.
-    let hour_angle_rad_from_midnight = hour_fraction * 2.0 * PI;
-    let local_hour_angle_rad = hour_angle_rad_from_midnight - PI; // Angle from noon meridian, positive West
-
-    // Calculate sun's altitude (elevation above horizon) and components in local frame.
-    // Standard formulas for converting equatorial (Dec, HA) to horizontal (Alt, Azi):
-    // sin(alt) = sin(lat)sin(dec) + cos(lat)cos(dec)cos(HA)
-    // cos(alt)sin(azi) = cos(dec)sin(HA)              (X component in East-Up-North)
-    // cos(alt)cos(azi) = cos(lat)sin(dec) - sin(lat)cos(dec)cos(HA) (Z component in East-Up-North)
+/// `paused`, `time_scale` and the phase time warp are ignored, the tick owner drives the
+/// clock. `current_cycle_time`, `day_index` and (with `days_per_year`) `year_fraction`
+/// are overwritten from the tick. Events are sent for the change since the previous update,
+/// none for rewinds.
+#[derive(Component, Reflect, Debug, Clone, Copy, PartialEq)]
+#[reflect(Component, Debug)]
+pub struct SkyTick {
+    /// Current simulation tick, owned by your game.
+    pub tick: u64,
+    /// Sky seconds per tick, e.g. `1.0 / 60.0` for a 60 Hz simulation at real speed.
+    pub secs_per_tick: f64,
+    /// Sky seconds since midnight of day 0 at tick 0.
+    pub start_secs: f64,
+    /// `year_fraction` at day 0, used with `SkyCenter::days_per_year`.
+    pub start_year_fraction: f32,
+}
 
-    // Y (up) component = sin(altitude)
-    let sin_alt = latitude_rad.sin() * dec_rad.sin()
-        + latitude_rad.cos() * dec_rad.cos() * local_hour_angle_rad.cos();
+impl SkyTick {
+    pub fn new(secs_per_tick: f64) -> Self {
+        Self {
+            tick: 0,
+            secs_per_tick,
+            start_secs: 0.0,
+            start_year_fraction: 0.0,
+        }
+    }
 
-    // X (east) component = cos(altitude) * sin(azimuth from North towards East)
-    // Z (north) component = cos(altitude) * cos(azimuth from North towards East)
-    // We can get these components directly without calculating azimuth explicitly:
-    let x_east = dec_rad.cos() * local_hour_angle_rad.sin();
-    let z_north = latitude_rad.cos() * dec_rad.sin()
-        - latitude_rad.sin() * dec_rad.cos() * local_hour_angle_rad.cos();
+    /// Sky seconds since midnight of day 0 at the current tick.
+    pub fn sky_secs(&self) -> f64 {
+        self.start_secs + self.tick as f64 * self.secs_per_tick
+    }
 
-    // Construct the direction vector in the observer's local Bevy frame (X east, Y up, Z north)
-    let sun_direction_local = Vec3::new(
-        x_east,  // X: East
-        sin_alt, // Y: Up (sin_alt is already calculated)
-        z_north, // Z: North
-    );
+    /// Day index and seconds into the cycle at the current tick, for cycles of
+    /// `cycle_duration_secs`. Times before day 0 stay on day 0.
+    pub fn day_and_cycle_secs(&self, cycle_duration_secs: f32) -> (u64, f64) {
+        let cycle_duration = cycle_duration_secs as f64;
+        let sky_secs = self.sky_secs().max(0.0);
+        (
+            (sky_secs / cycle_duration).floor() as u64,
+            sky_secs.rem_euclid(cycle_duration),
+        )
+    }
+}
 
-    // Normalize the vector
-    sun_direction_local.normalize()
+/// How often `update_sky_center` writes the `Transform`s of a sky and its sun, on the
+/// `SkyCenter` entity. Every write triggers `Changed<Transform>` (and shadow map updates),
+/// which is wasted on very long cycles where the sun barely moves between frames.
+/// `SunState`, events and the clock are still updated every frame.
+#[derive(Component, Reflect, Debug, Default, Clone, Copy, PartialEq)]
+#[reflect(Component, Default, Debug)]
+pub enum SunUpdatePolicy {
+    #[default]
+    EveryFrame,
+    /// At most once per interval of the sky clock's elapsed time.
+    Interval(std::time::Duration),
+    /// Only once the rotation moved by at least this many degrees.
+    AngleThreshold(f32),
+    /// Moves the sun light along its daily path in steps of `step_deg` of hour angle, so
+    /// cascaded shadow maps stay still in between instead of shimmering as the sun creeps.
+    /// The sky sphere and `SunState` keep moving smoothly.
+    Quantized { step_deg: f32 },
 }
 
-fn update_sky_center<T: ISunTime + Resource>(
-    mut q_sky_center: Query<(&mut Transform, &mut SkyCenter)>,
-    mut q_sun: Query<&mut Transform, Without<SkyCenter>>,
-    time: Res<T>,
-) {
-    for (mut sky_transforms, mut sky_center) in q_sky_center.iter_mut() {
-        // Update time
-        sky_center.current_cycle_time = time.elapsed_secs();
-        sky_center.current_cycle_time %= sky_center.cycle_duration_secs; // Cycle time loops
+impl SunUpdatePolicy {
+    /// [`SunUpdatePolicy::Quantized`] with steps moving the shadow of a caster
+    /// `caster_height` tall by about one texel of a `map_size` shadow map covering
+    /// `cascade_extent` world units.
+    pub fn shadow_stable(map_size: u32, cascade_extent: f32, caster_height: f32) -> Self {
+        let texel_size = cascade_extent / map_size.max(1) as f32;
+        SunUpdatePolicy::Quantized {
+            step_deg: (texel_size / caster_height.max(f32::EPSILON)).atan() * RADIANS_TO_DEGREES,
+        }
+    }
 
-        let hour_fraction = sky_center.current_cycle_time / sky_center.cycle_duration_secs;
+    /// Solar hour fraction the sun light is placed at.
+    fn sun_hour_fraction(&self, solar_hour_fraction: f32) -> f32 {
+        match self {
+            SunUpdatePolicy::Quantized { step_deg } if *step_deg > 0.0 => {
+                let step = step_deg / 360.0;
+                (solar_hour_fraction / step).round() * step
+            }
+            _ => solar_hour_fraction,
+        }
+    }
+
+    /// Whether a transform at rotation `current` should be moved to `target`.
+    fn should_write(&self, current: Quat, target: Quat) -> bool {
+        match self {
+            SunUpdatePolicy::AngleThreshold(threshold_deg) => {
+                current.angle_between(target) * RADIANS_TO_DEGREES >= *threshold_deg
+            }
+            // Between steps the target doesn't change, leave the transform unchanged
+            SunUpdatePolicy::Quantized { .. } => current != target,
+            _ => true,
+        }
+    }
+}
+
+/// When a sky event happened, carried by every event of the crate so consumers can
+/// order them exactly, even when several happen in one frame.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct SkyTimestamp {
+    /// Cycle time of the event (seconds), which usually lies inside the frame.
+    pub cycle_time_secs: f32,
+    /// Same as `cycle_time_secs`, as a cycle fraction (0.0 midnight, 0.5 noon).
+    pub hour_fraction: f32,
+    /// `SkyCenter::day_index` at the event.
+    pub day_index: u64,
+    /// Elapsed time of the app clock when the event was sent (seconds).
+    pub elapsed_secs: f32,
+}
+
+/// Sent by `update_sky_center` when the sun of `sky` rises above the horizon.
+#[derive(Message, Debug, Clone, Copy)]
+pub struct SunriseEvent {
+    pub sky: Entity,
+    /// Exact time of the crossing.
+    pub at: SkyTimestamp,
+}
+
+/// Sent by `update_sky_center` when the sun of `sky` sets below the horizon.
+#[derive(Message, Debug, Clone, Copy)]
+pub struct SunsetEvent {
+    pub sky: Entity,
+    /// Exact time of the crossing.
+    pub at: SkyTimestamp,
+}
+
+/// Sent by `update_sky_center` at each midnight passed by the running clock, once per
+/// completed cycle. Catch-ups send a `SkyCatchUp` instead.
+#[derive(Message, Debug, Clone, Copy)]
+pub struct NewCycleEvent {
+    pub sky: Entity,
+    /// `day_index` of the cycle starting, the number of cycles completed so far.
+    pub cycle_index: u64,
+    pub at: SkyTimestamp,
+}
+
+/// Sent by `update_sky_center` when `year_fraction` wraps around to a new year,
+/// only for skies with `days_per_year` set.
+#[derive(Message, Debug, Clone, Copy)]
+pub struct NewYearEvent {
+    pub sky: Entity,
+    pub at: SkyTimestamp,
+}
+
+/// Sent instead of individual `SunriseEvent`/`SunsetEvent`s when the sky advances by at
+/// least `SkyCatchUpSettings::threshold_cycles` at once, e.g. when the app resumes after
+/// hours of simulated time.
+#[derive(Message, Debug, Clone, Copy)]
+pub struct SkyCatchUp {
+    pub sky: Entity,
+    /// Cycles (days) skipped, fractional.
+    pub days_passed: f32,
+    /// Horizon crossings (sunrises and sunsets) skipped.
+    pub phases_crossed: u32,
+    /// Where the sky landed.
+    pub at: SkyTimestamp,
+}
+
+#[derive(Resource, Debug, Clone, Copy)]
+pub struct SkyCatchUpSettings {
+    /// Advances of at least this many cycles in one frame are reported as a `SkyCatchUp`.
+    pub threshold_cycles: f32,
+}
+
+impl Default for SkyCatchUpSettings {
+    fn default() -> Self {
+        Self {
+            threshold_cycles: 1.0,
+        }
+    }
+}
+
+/// Sun position of a `SkyCenter`, updated every frame after the sun moved.
+/// Added automatically with the `SkyCenter`.
+#[derive(Component, Reflect, Debug, Default, Clone, Copy)]
+#[reflect(Component, Default, Debug)]
+pub struct SunState {
+    /// Direction towards the sun (X east, Y up, Z north).
+    pub direction: Vec3,
+    /// Angle above the horizon in degrees (negative at night).
+    pub altitude_deg: f32,
+    /// Altitude of the top of the sun disk, `altitude_deg` plus half of
+    /// `SkyCenter::sun_angular_diameter_deg`. Positive while any part of the sun is up.
+    pub upper_limb_altitude_deg: f32,
+    /// Azimuth in degrees from North towards East, in `[0, 360)`.
+    pub azimuth_deg: f32,
+    /// Azimuth accumulated over time without wrapping at due North, so it keeps
+    /// growing (or shrinking) continuously past 360. Use for filtering and animation.
+    pub unwrapped_azimuth_deg: f32,
+    /// Azimuth of today's culmination: 180 (South) or 0 (North, e.g. at southern latitudes).
+    pub noon_azimuth_deg: f32,
+    /// Angular velocity of the sky sphere this frame (rad/s in the sky's parent frame,
+    /// world space for a root `SkyCenter`), see [`SkyCenter::celestial_angular_velocity`].
+    pub sky_angular_velocity: Vec3,
+    /// Skyline elevation in degrees at the sun's azimuth, from the sky's
+    /// [`HorizonProfile`] (0.0 without one).
+    pub horizon_elevation_deg: f32,
+    #[reflect(ignore)]
+    initialized: bool,
+}
+
+impl SunState {
+    /// Altitude of the upper limb above the skyline, positive while the sun is visible
+    /// over the terrain.
+    pub fn altitude_above_skyline_deg(&self) -> f32 {
+        self.upper_limb_altitude_deg - self.horizon_elevation_deg
+    }
+
+    /// `next`, with its unwrapped azimuth continued from this state.
+    pub(crate) fn followed_by(&self, next: SunState) -> SunState {
+        let unwrapped_azimuth_deg = if self.initialized {
+            // Shortest signed step from the previous azimuth
+            let step = (next.azimuth_deg - self.azimuth_deg + 180.0).rem_euclid(360.0) - 180.0;
+            self.unwrapped_azimuth_deg + step
+        } else {
+            next.azimuth_deg
+        };
+        SunState {
+            unwrapped_azimuth_deg,
+            initialized: true,
+            ..next
+        }
+    }
+}
+
+/// Drives a second light (the moon) along a lunar orbit. Add to the `SkyCenter` entity,
+/// the moon then shares its cycle clock and celestial frame.
+#[derive(Component, Reflect, Debug, Clone)]
+#[reflect(Component, Default, Debug)]
+#[require(MoonPhase)]
+pub struct MoonCenter {
+    /// The entity representing the moon (usually a DirectionalLight).
+    #[entities]
+    pub moon: Entity,
+
+    /// Length of a lunar month (new moon to new moon) in day/night cycles.
+    pub orbital_period_cycles: f32,
+
+    /// Inclination of the lunar orbit to the ecliptic in degrees.
+    pub inclination_degrees: f32,
+
+    /// Added to `orbit_fraction`, e.g. 0.5 to start at full moon.
+    pub phase_offset: f32,
+
+    /// Progress along the orbit (0.0 to 1.0, 0.0 is new moon), derived every frame from the
+    /// sky day index and time of day. Shift it with `phase_offset`.
+    pub orbit_fraction: f32,
+
+    /// Moon light illuminance at full moon with the moon well above the horizon.
+    /// Scaled down by the phase and as the moon sets. `None` leaves the light untouched.
+    pub full_moon_illuminance: Option<f32>,
+}
+
+impl Default for MoonCenter {
+    fn default() -> Self {
+        Self {
+            moon: Entity::PLACEHOLDER,
+            orbital_period_cycles: 29.5,
+            inclination_degrees: 5.14,
+            phase_offset: 0.0,
+            orbit_fraction: 0.0,
+            full_moon_illuminance: Some(moon::MOON_FULL_ILLUMINANCE),
+        }
+    }
+}
+
+#[derive(Reflect, Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LunarPhase {
+    #[default]
+    New,
+    Waxing,
+    Full,
+    Waning,
+}
+
+/// Phase of a `MoonCenter`, updated every frame. Added automatically with the `MoonCenter`.
+#[derive(Component, Reflect, Debug, Default, Clone, Copy)]
+#[reflect(Component, Default, Debug)]
+pub struct MoonPhase {
+    /// Angle between the sun and the moon seen from the observer, in degrees
+    /// (0 at new moon, 180 at full moon).
+    pub phase_angle_deg: f32,
+    /// Lit fraction of the moon disk (0.0 to 1.0).
+    pub illuminated_fraction: f32,
+    /// `New` and `Full` within 1/16 of a lunar month of the exact phase.
+    pub phase: LunarPhase,
+}
+
+impl SkyCenter {
+    /// Solves `timed_config` into a `SkyCenter`, see [`calculate_latitude_yearfraction`].
+    pub fn from_timed_config(timed_config: &TimedSkyConfig) -> Result<Self, SkyConfigError> {
+        let mut sky_center = Self::default();
+        sky_center.update_from_timed_config(timed_config)?;
+        Ok(sky_center)
+    }
+
+    /// Re-solves `timed_config` into this `SkyCenter`, keeping the clock and the other
+    /// settings. Left untouched on error.
+    pub(crate) fn update_from_timed_config(
+        &mut self,
+        timed_config: &TimedSkyConfig,
+    ) -> Result<(), SkyConfigError> {
+        let (latitude, mut year_fraction, declination) = closed_form_latitude_yearfraction(
+            timed_config.planet_tilt_degrees,
+            timed_config.day_duration_secs,
+            timed_config.night_duration_secs,
+            timed_config.max_sun_height_deg,
+            timed_config.hemisphere,
+            timed_config.noon_sun,
+            &SolverTolerances::default(),
+        )?;
+        if let Some(season_middle) = timed_config.season_half.middle_year_fraction()
+            && let Some(season) = year_fraction_from_declination(
+                declination * DEGREES_TO_RADIANS,
+                timed_config.planet_tilt_degrees * DEGREES_TO_RADIANS,
+                season_middle,
+            )
+        {
+            year_fraction = season;
+        }
+        self.latitude_degrees = latitude;
+        self.planet_tilt_degrees = timed_config.planet_tilt_degrees;
+        // The solver's season is on a circular orbit
+        self.year_fraction = self.year_fraction_for_season(year_fraction);
+        self.cycle_duration_secs =
+            timed_config.day_duration_secs + timed_config.night_duration_secs;
+        self.sun = timed_config.sun_entity;
+        Ok(())
+    }
+
+    /// Checked variant of re-solving `timed_config` into this `SkyCenter`, see
+    /// [`calculate_latitude_yearfraction_checked`]. The solved season is placed on this
+    /// sky's orbit, and the sky that results (eccentricity included) is simulated again:
+    /// the achieved values of the returned solution are the ones it produces, and its
+    /// `year_fraction` is the one written to the sky. `None`, leaving the sky untouched, if
+    /// no solution matches the request within `tolerances.match_relative`.
+    pub fn update_from_timed_config_checked(
+        &mut self,
+        timed_config: &TimedSkyConfig,
+        tolerances: &SolverTolerances,
+    ) -> Option<CheckedSolution> {
+        let solution = calculate_latitude_yearfraction_checked(
+            timed_config.planet_tilt_degrees,
+            timed_config.day_duration_secs,
+            timed_config.night_duration_secs,
+            timed_config.max_sun_height_deg,
+            timed_config.hemisphere,
+            tolerances,
+        )?;
+        let mut solved = self.clone();
+        solved.latitude_degrees = solution.latitude_degrees;
+        solved.planet_tilt_degrees = timed_config.planet_tilt_degrees;
+        solved.year_fraction = solved.year_fraction_for_season(solution.year_fraction);
+        solved.cycle_duration_secs =
+            timed_config.day_duration_secs + timed_config.night_duration_secs;
+        solved.sun = timed_config.sun_entity;
+
+        // Forward check through the orbit, which maps the year fraction back to the season
+        let declination_degrees = calculate_declination(
+            solved.planet_tilt_degrees * DEGREES_TO_RADIANS,
+            solved.season_year_fraction(),
+        ) * RADIANS_TO_DEGREES;
+        let (day_fraction, max_height_deg) =
+            simulate_day_fraction_and_height(solved.latitude_degrees, declination_degrees);
+        let target_day_fraction = timed_config.day_duration_secs / solved.cycle_duration_secs;
+        if (day_fraction - target_day_fraction).abs() > tolerances.match_relative
+            || (max_height_deg - timed_config.max_sun_height_deg).abs() / 90.0
+                > tolerances.match_relative
+        {
+            return None;
+        }
+
+        *self = solved;
+        Some(CheckedSolution {
+            year_fraction: self.year_fraction,
+            declination_degrees,
+            achieved_day_duration_secs: day_fraction * self.cycle_duration_secs,
+            achieved_night_duration_secs: (1.0 - day_fraction) * self.cycle_duration_secs,
+            achieved_max_sun_height_deg: max_height_deg,
+            ..solution
+        })
+    }
+
+    /// Celestial pole direction of this sky, see [`celestial_pole_direction`].
+    pub fn celestial_pole_direction(&self) -> Vec3 {
+        celestial_pole_direction(self.latitude_degrees * DEGREES_TO_RADIANS)
+    }
 
-        let latitude_rad = sky_center.latitude_degrees * DEGREES_TO_RADIANS;
-        let tilt_rad = sky_center.planet_tilt_degrees * DEGREES_TO_RADIANS;
-        let year_fraction = sky_center.year_fraction;
+    /// Current sky sphere rotation, see [`celestial_rotation`].
+    pub fn celestial_rotation(&self) -> Quat {
+        celestial_rotation(
+            self.solar_hour_fraction(self.current_cycle_time / self.cycle_duration_secs),
+            self.latitude_degrees * DEGREES_TO_RADIANS,
+        )
+    }
+
+    /// Year fraction with the same season on a circular orbit, the one to pass to the
+    /// declination and sun direction functions. Equals `year_fraction` without eccentricity.
+    pub fn season_year_fraction(&self) -> f32 {
+        orbital_year_fraction(
+            self.year_fraction,
+            self.orbital_eccentricity,
+            self.perihelion_year_fraction,
+        )
+    }
+
+    /// Inverse of [`SkyCenter::season_year_fraction`]: the `year_fraction` of this orbit
+    /// with the season of the circular `season_year_fraction`, e.g. from the timed solvers.
+    pub fn year_fraction_for_season(&self, season_year_fraction: f32) -> f32 {
+        year_fraction_from_orbital(
+            season_year_fraction,
+            self.orbital_eccentricity,
+            self.perihelion_year_fraction,
+        )
+        .rem_euclid(1.0)
+    }
+
+    /// Timestamp of the current cycle time, `elapsed_secs` being the app clock.
+    pub fn timestamp(&self, elapsed_secs: f32) -> SkyTimestamp {
+        let hour_fraction = self.current_cycle_time / self.cycle_duration_secs;
+        SkyTimestamp {
+            cycle_time_secs: self.current_cycle_time,
+            hour_fraction,
+            day_index: self.day_index,
+            elapsed_secs,
+        }
+    }
 
-        sky_transforms.translation = Vec3::ZERO;
-        // Sky sphere rotation axis. Useful for attach stars and celestial bodies to the sky sphere.
-        let celestial_pole_axis_local = Vec3::new(0.0, latitude_rad.sin(), latitude_rad.cos());
+    /// Sun position at clock time `hour_fraction` (0.0 midnight, 0.5 noon) of the current
+    /// day, without touching the clock. `unwrapped_azimuth_deg` equals `azimuth_deg` and
+    /// `sky_angular_velocity` leaves out `phase_time_scales`.
+    pub fn sun_state_at(&self, hour_fraction: f32) -> SunState {
+        self.sun_state_from_position(CachedSunPosition::compute(
+            self.solar_hour_fraction(hour_fraction),
+            self.latitude_degrees * DEGREES_TO_RADIANS,
+            self.planet_tilt_degrees * DEGREES_TO_RADIANS,
+            self.season_year_fraction(),
+        ))
+    }
+
+    /// Same as [`SkyCenter::sun_state_at`], sharing the sun position with the other skies
+    /// looked up in `cache` with the same latitude, tilt, season and time of day.
+    pub fn sun_state_at_cached(
+        &self,
+        hour_fraction: f32,
+        cache: &mut SunPositionCache,
+    ) -> SunState {
+        self.sun_state_from_position(cache.sun_position(
+            self.solar_hour_fraction(hour_fraction),
+            self.latitude_degrees * DEGREES_TO_RADIANS,
+            self.planet_tilt_degrees * DEGREES_TO_RADIANS,
+            self.season_year_fraction(),
+        ))
+    }
+
+    fn sun_state_from_position(&self, position: CachedSunPosition) -> SunState {
+        SunState {
+            direction: position.direction,
+            altitude_deg: position.altitude_deg,
+            upper_limb_altitude_deg: position.altitude_deg + self.sun_radius_deg(),
+            azimuth_deg: position.azimuth_deg,
+            unwrapped_azimuth_deg: position.azimuth_deg,
+            noon_azimuth_deg: position.noon_azimuth_deg,
+            sky_angular_velocity: self.celestial_angular_velocity(),
+            horizon_elevation_deg: 0.0,
+            initialized: false,
+        }
+    }
 
-        // Sky sphere rotation
-        let rotation_angle_rad = PI - hour_fraction * 2.0 * PI;
-        sky_transforms.rotation =
-            Quat::from_axis_angle(celestial_pole_axis_local, rotation_angle_rad);
+    /// Current sun altitude above the horizon in degrees, see [`SkyCenter::sun_state_at`].
+    pub fn sun_altitude_deg(&self) -> f32 {
+        self.sun_state_at(self.current_cycle_time / self.cycle_duration_secs)
+            .altitude_deg
+    }
 
-        let sun_direction_local =
-            calculate_sun_direction(hour_fraction, latitude_rad, tilt_rad, year_fraction);
+    /// Current sun azimuth in degrees from North towards East, in `[0, 360)`.
+    pub fn sun_azimuth_deg(&self) -> f32 {
+        self.sun_state_at(self.current_cycle_time / self.cycle_duration_secs)
+            .azimuth_deg
+    }
+
+    /// How far the sun runs ahead of the clock, as a cycle fraction: the
+    /// [`equation_of_time`] when enabled, 0.0 otherwise.
+    pub fn equation_of_time_offset(&self) -> f32 {
+        if self.equation_of_time {
+            equation_of_time(self.year_fraction) / MINUTES_PER_DAY
+        } else {
+            0.0
+        }
+    }
+
+    /// Apparent solar hour fraction (the one that places the sun) at the clock's
+    /// `hour_fraction`.
+    pub fn solar_hour_fraction(&self, hour_fraction: f32) -> f32 {
+        (hour_fraction + self.equation_of_time_offset()).rem_euclid(1.0)
+    }
+
+    /// Angular velocity of the sky sphere (rad/s, axis times speed) at the current
+    /// `time_scale`, zero while paused. Useful for motion blur or for spinning large sky
+    /// meshes through physics instead of writing their transform. The phase time warp isn't
+    /// applied, `SunState::sky_angular_velocity` includes it.
+    pub fn celestial_angular_velocity(&self) -> Vec3 {
+        if self.paused || self.cycle_duration_secs <= 0.0 {
+            return Vec3::ZERO;
+        }
+        // The rotation angle decreases by a full turn per cycle, see `celestial_rotation`
+        -self.celestial_pole_direction() * (2.0 * PI / self.cycle_duration_secs) * self.time_scale
+    }
+
+    /// Time of day phases over the cycle as `(start_hour_fraction, phase)`, sorted by start.
+    /// Each phase lasts until the start of the next one (wrapping around).
+    pub fn phase_segments(
+        &self,
+        thresholds: &TimeOfDayThresholds,
+    ) -> SmallVec<[(f32, TimeOfDayPhase); 6]> {
+        // Every phase change happens at a threshold crossing or at noon (dawn to dusk)
+        let mut boundaries: SmallVec<[f32; 6]> = smallvec![0.0, 0.5];
+        // Phases follow the upper limb, which is above the center by the sun radius
+        let sun_radius_deg = self.sun_radius_deg();
+        boundaries.extend(self.times_at_altitude(thresholds.night_below_deg - sun_radius_deg));
+        boundaries.extend(self.times_at_altitude(thresholds.day_above_deg - sun_radius_deg));
+        boundaries.sort_by(f32::total_cmp);
+        boundaries.dedup();
+
+        let latitude_rad = self.latitude_degrees * DEGREES_TO_RADIANS;
+        let tilt_rad = self.planet_tilt_degrees * DEGREES_TO_RADIANS;
+        let mut segments: SmallVec<[(f32, TimeOfDayPhase); 6]> = SmallVec::new();
+        for (index, start) in boundaries.iter().enumerate() {
+            let end = boundaries.get(index + 1).copied().unwrap_or(1.0);
+            let middle = (start + end) * 0.5;
+            let direction = calculate_sun_direction(
+                self.solar_hour_fraction(middle),
+                latitude_rad,
+                tilt_rad,
+                self.season_year_fraction(),
+            );
+            let phase = classify_time_of_day(
+                direction.y.clamp(-1.0, 1.0).asin() * RADIANS_TO_DEGREES + sun_radius_deg,
+                middle,
+                thresholds,
+            );
+            if segments
+                .last()
+                .is_none_or(|(_, last_phase)| *last_phase != phase)
+            {
+                segments.push((*start, phase));
+            }
+        }
+        // Night before midnight continues after it
+        if segments.len() > 1 && segments[0].1 == segments[segments.len() - 1].1 {
+            segments.remove(0);
+        }
+        segments
+    }
+
+    /// Pieces of the cycle played at a different speed, as `(start_hour_fraction, speed)`
+    /// sorted by start, following `phase_time_scales`. Each piece lasts until the start of
+    /// the next one (wrapping around).
+    pub fn time_warp_segments(
+        &self,
+        thresholds: &TimeOfDayThresholds,
+    ) -> SmallVec<[(f32, f32); 6]> {
+        self.phase_segments(thresholds)
+            .iter()
+            .map(|(start, phase)| (*start, self.phase_time_scales.for_phase(*phase)))
+            .collect()
+    }
+
+    /// Time warp speed at `hour_fraction` (1.0 for unwarped parts of the cycle).
+    pub fn warp_speed_at(&self, hour_fraction: f32, thresholds: &TimeOfDayThresholds) -> f32 {
+        let segments = self.time_warp_segments(thresholds);
+        segments[warp_segment_index(&segments, hour_fraction.rem_euclid(1.0))].1
+    }
+
+    /// Sky time (seconds) that passes from the current cycle time during `delta_secs` of
+    /// clock time, see [`warp_cycle_delta`]. `time_scale` is not applied.
+    pub fn warped_cycle_delta(&self, delta_secs: f32, thresholds: &TimeOfDayThresholds) -> f32 {
+        if self.phase_time_scales.is_uniform() {
+            return delta_secs * self.phase_time_scales.day;
+        }
+        warp_cycle_delta(
+            self.current_cycle_time / self.cycle_duration_secs,
+            delta_secs,
+            self.cycle_duration_secs,
+            &self.time_warp_segments(thresholds),
+        )
+    }
+
+    /// Number of horizon crossings (sunrises plus sunsets) when advancing by `cycles` from
+    /// `start_hour_fraction`, with the current latitude and season.
+    pub fn horizon_crossings(&self, start_hour_fraction: f32, cycles: f32) -> u32 {
+        self.sunrise_and_sunset()
+            .iter()
+            .map(|&crossing| times_passed(crossing, start_hour_fraction, cycles))
+            .sum()
+    }
+
+    /// Sets the clock to `hours` of a 24 hour day (12.0 is noon), keeping the day index.
+    pub fn set_hour_of_day(&mut self, hours: f32) {
+        self.set_hour_fraction(hours / 24.0);
+    }
+
+    /// Sets the clock to `hour_fraction` of the cycle (0.0 midnight, 0.5 noon), keeping
+    /// the day index.
+    pub fn set_hour_fraction(&mut self, hour_fraction: f32) {
+        self.current_cycle_time = hour_fraction.rem_euclid(1.0) * self.cycle_duration_secs;
+    }
+
+    /// Moves the clock forward to the next sunrise with the current latitude and season,
+    /// starting a new day if it is tomorrow's (e.g. after sleeping through the night).
+    /// Returns the sky seconds skipped, or `None` if the sun does not rise this season.
+    pub fn advance_to_next_sunrise(&mut self) -> Option<f32> {
+        let &[sunrise, _] = self.sunrise_and_sunset().as_slice() else {
+            return None;
+        };
+        let hour_fraction = self.current_cycle_time / self.cycle_duration_secs;
+        let mut skipped = (sunrise - hour_fraction).rem_euclid(1.0);
+        if skipped <= f32::EPSILON {
+            skipped = 1.0; // Already at sunrise: go to the next one
+        }
+        if hour_fraction + skipped >= 1.0 {
+            self.day_index += 1;
+        }
+        self.set_hour_fraction(sunrise);
+        Some(skipped * self.cycle_duration_secs)
+    }
+
+    /// Half of `sun_angular_diameter_deg`: how far the upper limb is above the sun center.
+    pub fn sun_radius_deg(&self) -> f32 {
+        self.sun_angular_diameter_deg.max(0.0) * 0.5
+    }
+
+    /// Hour fractions of sunrise and sunset, when the upper limb of the sun crosses the
+    /// horizon. Empty during polar day or night, see [`SkyCenter::times_at_altitude`].
+    pub fn sunrise_and_sunset(&self) -> SmallVec<[f32; 2]> {
+        self.times_at_altitude(-self.sun_radius_deg())
+    }
+
+    /// Hour fractions at which the sun crosses `altitude_deg` (e.g. -6° for civil twilight),
+    /// see [`calculate_times_at_altitude`]. Clock times, shifted by the equation of time
+    /// when enabled.
+    pub fn times_at_altitude(&self, altitude_deg: f32) -> SmallVec<[f32; 2]> {
+        let latitude_rad = self.latitude_degrees * DEGREES_TO_RADIANS;
+        let tilt_rad = self.planet_tilt_degrees * DEGREES_TO_RADIANS;
+        let mut times = calculate_times_at_altitude(
+            altitude_deg * DEGREES_TO_RADIANS,
+            latitude_rad,
+            calculate_declination(tilt_rad, self.season_year_fraction()),
+        );
+        let offset = self.equation_of_time_offset();
+        if offset != 0.0 {
+            for time in times.iter_mut() {
+                *time = (*time - offset).rem_euclid(1.0);
+            }
+        }
+        times
+    }
+
+    /// Clock hours (0.0 to 24.0) at which the sun's center reaches `altitude_deg`, rising
+    /// then setting, e.g. `hours_at_altitude(6.0)` for the end of the morning golden hour.
+    /// Same as [`SkyCenter::times_at_altitude`] on a 24 hour clock.
+    pub fn hours_at_altitude(&self, altitude_deg: f32) -> SmallVec<[f32; 2]> {
+        self.times_at_altitude(altitude_deg)
+            .into_iter()
+            .map(|hour_fraction| hour_fraction * 24.0)
+            .collect()
+    }
+
+    /// Recovers the timed parameters (day/night durations, max sun height) produced by
+    /// the current latitude, tilt and year fraction, with the hemisphere, noon sun side and
+    /// season half that lead [`SkyCenter::from_timed_config`] back to them.
+    ///
+    /// Only the position, season and cycle duration come back: the orbit and the clock
+    /// settings aren't part of a `TimedSkyConfig`. Polar days and nights don't round trip,
+    /// the solver picks the latitude where they start.
+    ///
+    /// If the sun never rises, `max_sun_height_deg` is reported as 0° (the sun touches the
+    /// horizon at best), which is the value the solver expects for perpetual night.
+    pub fn to_timed_config(&self) -> TimedSkyConfig {
+        let latitude_rad = self.latitude_degrees * DEGREES_TO_RADIANS;
+        let tilt_rad = self.planet_tilt_degrees * DEGREES_TO_RADIANS;
+        let season = self.season_year_fraction().rem_euclid(1.0);
+        let dec_rad = calculate_declination(tilt_rad, season);
+
+        let day_fraction = calculate_day_fraction(latitude_rad, dec_rad);
+        let max_sun_height_deg =
+            (90.0 - (self.latitude_degrees - dec_rad * RADIANS_TO_DEGREES).abs()).max(0.0);
+
+        TimedSkyConfig {
+            planet_tilt_degrees: self.planet_tilt_degrees,
+            day_duration_secs: self.cycle_duration_secs * day_fraction,
+            night_duration_secs: self.cycle_duration_secs * (1.0 - day_fraction),
+            max_sun_height_deg,
+            sun_entity: self.sun,
+            hemisphere: if self.latitude_degrees < 0.0 {
+                HemispherePreference::South
+            } else {
+                HemispherePreference::North
+            },
+            noon_sun: if (self.latitude_degrees - dec_rad * RADIANS_TO_DEGREES)
+                * self.latitude_degrees
+                < 0.0
+            {
+                NoonSunSide::Poleward
+            } else {
+                NoonSunSide::Equatorward
+            },
+            season_half: if (0.25..0.75).contains(&season) {
+                SeasonHalf::FromSummerSolstice
+            } else {
+                SeasonHalf::FromWinterSolstice
+            },
+        }
+    }
+}
+
+/// Solves Kepler's equation `E - e sin(E) = M` for the eccentric anomaly `E` (radians)
+/// with Newton's method, for eccentricities in `[0, 1)`.
+pub fn solve_kepler(mean_anomaly_rad: f32, eccentricity: f32) -> f32 {
+    let e = eccentricity.clamp(0.0, 0.99);
+    let mean_anomaly = mean_anomaly_rad.rem_euclid(2.0 * PI);
+    // M is a good first guess for low eccentricities, PI is safe for high ones
+    let mut eccentric_anomaly = if e > 0.8 { PI } else { mean_anomaly };
+    for _ in 0..16 {
+        let step = (eccentric_anomaly - e * eccentric_anomaly.sin() - mean_anomaly)
+            / (1.0 - e * eccentric_anomaly.cos());
+        eccentric_anomaly -= step;
+        if step.abs() < 1e-6 {
+            break;
+        }
+    }
+    eccentric_anomaly
+}
+
+/// Converts a year fraction (time since the Vernal Equinox) on an eccentric orbit into the
+/// year fraction of a circular orbit with the same sun longitude, so declination and sun
+/// direction can keep using the circular formulas.
+pub fn orbital_year_fraction(
+    year_fraction: f32,
+    eccentricity: f32,
+    perihelion_year_fraction: f32,
+) -> f32 {
+    if eccentricity <= 0.0 {
+        return year_fraction;
+    }
+    let e = eccentricity.min(0.99);
+    let mean_anomaly = (year_fraction - perihelion_year_fraction) * 2.0 * PI;
+    let eccentric_anomaly = solve_kepler(mean_anomaly, e);
+    let true_anomaly = 2.0
+        * ((1.0 + e).sqrt() * (eccentric_anomaly * 0.5).sin())
+            .atan2((1.0 - e).sqrt() * (eccentric_anomaly * 0.5).cos());
+    // Unwrap around the mean anomaly, so the result stays close to the input
+    let offset = (true_anomaly - mean_anomaly + PI).rem_euclid(2.0 * PI) - PI;
+    year_fraction + offset / (2.0 * PI)
+}
+
+/// Inverse of [`orbital_year_fraction`]: the year fraction at which an eccentric orbit
+/// reaches the sun longitude of the circular `season_year_fraction`.
+pub fn year_fraction_from_orbital(
+    season_year_fraction: f32,
+    eccentricity: f32,
+    perihelion_year_fraction: f32,
+) -> f32 {
+    if eccentricity <= 0.0 {
+        return season_year_fraction;
+    }
+    let e = eccentricity.min(0.99);
+    let true_anomaly = (season_year_fraction - perihelion_year_fraction) * 2.0 * PI;
+    let eccentric_anomaly = 2.0
+        * ((1.0 - e).sqrt() * (true_anomaly * 0.5).sin())
+            .atan2((1.0 + e).sqrt() * (true_anomaly * 0.5).cos());
+    let mean_anomaly = eccentric_anomaly - e * eccentric_anomaly.sin();
+    let offset = (mean_anomaly - true_anomaly + PI).rem_euclid(2.0 * PI) - PI;
+    season_year_fraction + offset / (2.0 * PI)
+}
+
+const MINUTES_PER_DAY: f32 = 24.0 * 60.0;
+
+/// Equation of time in minutes (apparent minus mean solar time) for a year fraction,
+/// where 0.0 is Vernal Equinox. Earth's curve: between about -14 and +16 minutes,
+/// combining the orbit eccentricity and the axial tilt.
+pub fn equation_of_time(year_fraction: f32) -> f32 {
+    // Usually written with B = 2PI (day_of_year - 81) / 365, day 81 being the equinox
+    let b = year_fraction * 2.0 * PI;
+    9.87 * (2.0 * b).sin() - 7.53 * b.cos() - 1.5 * b.sin()
+}
+
+/// Calculates the sun's declination in radians for a given axial tilt (radians)
+/// and year fraction, where 0.0 is Vernal Equinox and 0.25 is Summer Solstice.
+pub fn calculate_declination(axial_tilt_rad: f32, year_fraction: f32) -> f32 {
+    let year_angle_rad = year_fraction * 2.0 * PI;
+    axial_tilt_rad * year_angle_rad.sin()
+}
+
+/// Calculates the fraction of the cycle the sun spends above the horizon.
+///
+/// Uses the sunrise hour angle relation `cos(H0) = -tan(lat) * tan(dec)`.
+/// Returns 0.0 for perpetual night and 1.0 for perpetual day.
+pub fn calculate_day_fraction(latitude_rad: f32, declination_rad: f32) -> f32 {
+    let cos_h0 = -latitude_rad.tan() * declination_rad.tan();
+    if cos_h0 >= 1.0 {
+        0.0 // Sun never rises
+    } else if cos_h0 <= -1.0 {
+        1.0 // Sun never sets
+    } else {
+        cos_h0.acos() / PI
+    }
+}
+
+/// Calculates the hour fractions (0.0 midnight, 0.5 noon) at which the sun crosses
+/// `altitude_rad`, in increasing order: the rising crossing first, then the setting one.
+///
+/// Returns no values if the sun stays above or below that altitude all cycle,
+/// and a single value if it only touches it (at noon or midnight).
+pub fn calculate_times_at_altitude(
+    altitude_rad: f32,
+    latitude_rad: f32,
+    declination_rad: f32,
+) -> SmallVec<[f32; 2]> {
+    // sin(alt) = sin(lat)sin(dec) + cos(lat)cos(dec)cos(HA)
+    let denominator = latitude_rad.cos() * declination_rad.cos();
+    if denominator.abs() < f32::EPSILON {
+        return SmallVec::new(); // Pole: altitude never changes during the cycle
+    }
+    let cos_ha = (altitude_rad.sin() - latitude_rad.sin() * declination_rad.sin()) / denominator;
+    if cos_ha.abs() > 1.0 {
+        return SmallVec::new();
+    }
+
+    let hour_angle_fraction = cos_ha.acos() / (2.0 * PI);
+    let rising = 0.5 - hour_angle_fraction;
+    let setting = 0.5 + hour_angle_fraction;
+    if (setting - rising).abs() < f32::EPSILON || (1.0 - (setting - rising)).abs() < f32::EPSILON {
+        smallvec![rising.rem_euclid(1.0)]
+    } else {
+        smallvec![rising, setting]
+    }
+}
+
+/// Calculates the sun's direction vector in the observer's local coordinate frame (Y up, X east, Z north).
+/// This vector points *from* the observer *towards* the sun.
+///
+/// Based on standard astronomical formulas converting equatorial coordinates (declination, hour angle)
+/// to horizontal coordinates (altitude, azimuth).
+///
+/// Args:
+/// - hour_fraction: Fraction of the day (0.0 to 1.0), where 0.0 is midnight, 0.5 is noon.
+/// - latitude_rad: Observer's latitude in radians (-PI/2 to PI/2).
+/// - axial_tilt_rad: Planet's axial tilt in radians (e.g., 23.5 degrees for Earth).
+/// - year_fraction: Fraction of the year (0.0 to 1.0), where 0.0 is Vernal Equinox.
+///
+/// Returns:
+/// A `Vec3` representing the sun's direction relative to the observer.
+/// The vector length is arbitrary, usually normalized.
+pub fn calculate_sun_direction(
+    hour_fraction: f32,
+    latitude_rad: f32,
+    axial_tilt_rad: f32,
+    year_fraction: f32,
+) -> Vec3 {
+    // Calculate sun's declination based on axial tilt and time of year.
+    // Assuming year_fraction 0.0 is Vernal Equinox (dec=0), 0.25 is Summer Solstice (dec=tilt), etc.
+    let dec_rad = calculate_declination(axial_tilt_rad, year_fraction);
+
+    // Calculate Local Hour Angle (LHA). This is angle from local meridian (South/North line).
+    // hour_fraction 0.0 is midnight, 0.5 is noon. LHA is 0 at noon, PI 12 hours later.
+    // hour_angle_rad from midnight = hour_fraction * 2.0 * PI.
+    // Local Hour Angle (HA) is angle west of meridian. HA=0 at noon.
+    let hour_angle_rad_from_midnight = hour_fraction * 2.0 * PI;
+    let local_hour_angle_rad = hour_angle_rad_from_midnight - PI; // Angle from noon meridian, positive West
+
+    calculate_direction_from_hour_angle(local_hour_angle_rad, latitude_rad, dec_rad)
+}
+
+/// Direction (X east, Y up, Z north) of a body on the celestial sphere with the given
+/// local hour angle (0 at the noon meridian, positive West) and declination.
+pub fn calculate_direction_from_hour_angle(
+    local_hour_angle_rad: f32,
+    latitude_rad: f32,
+    dec_rad: f32,
+) -> Vec3 {
+    // Calculate altitude (elevation above horizon) and components in local frame.
+    // Standard formulas for converting equatorial (Dec, HA) to horizontal (Alt, Azi):
+    // sin(alt) = sin(lat)sin(dec) + cos(lat)cos(dec)cos(HA)
+    // cos(alt)sin(azi) = cos(dec)sin(HA)              (X component in East-Up-North)
+    // cos(alt)cos(azi) = cos(lat)sin(dec) - sin(lat)cos(dec)cos(HA) (Z component in East-Up-North)
+
+    // Y (up) component = sin(altitude)
+    let sin_alt = latitude_rad.sin() * dec_rad.sin()
+        + latitude_rad.cos() * dec_rad.cos() * local_hour_angle_rad.cos();
+
+    // X (east) component = cos(altitude) * sin(azimuth from North towards East)
+    // Z (north) component = cos(altitude) * cos(azimuth from North towards East)
+    // We can get these components directly without calculating azimuth explicitly:
+    let x_east = dec_rad.cos() * local_hour_angle_rad.sin();
+    let z_north = latitude_rad.cos() * dec_rad.sin()
+        - latitude_rad.sin() * dec_rad.cos() * local_hour_angle_rad.cos();
+
+    // Construct the direction vector in the observer's local Bevy frame (X east, Y up, Z north)
+    let direction_local = Vec3::new(
+        x_east,  // X: East
+        sin_alt, // Y: Up (sin_alt is already calculated)
+        z_north, // Z: North
+    );
+
+    // Normalize the vector
+    direction_local.normalize()
+}
+
+/// Direction of the moon in the observer's local frame (X east, Y up, Z north).
+///
+/// Uses the same simplified model as [`calculate_sun_direction`]: the moon is ahead of the
+/// sun by the elongation `orbit_fraction * 2PI` along the ecliptic (0.0 new moon, 0.5 full
+/// moon), which delays its hour angle by the same amount, and its orbit is inclined to the
+/// ecliptic by `inclination_rad`.
+pub fn calculate_moon_direction(
+    hour_fraction: f32,
+    latitude_rad: f32,
+    axial_tilt_rad: f32,
+    year_fraction: f32,
+    orbit_fraction: f32,
+    inclination_rad: f32,
+) -> Vec3 {
+    let elongation_rad = orbit_fraction * 2.0 * PI;
+    let ecliptic_longitude_rad = year_fraction * 2.0 * PI + elongation_rad;
+    // Declination from the ecliptic position, plus the orbit's own inclination.
+    // The ascending node is fixed at the vernal equinox (no 18.6 year nodal precession).
+    let dec_rad = axial_tilt_rad * ecliptic_longitude_rad.sin()
+        + inclination_rad * ecliptic_longitude_rad.sin();
+
+    let local_hour_angle_rad = hour_fraction * 2.0 * PI - PI - elongation_rad;
+    calculate_direction_from_hour_angle(local_hour_angle_rad, latitude_rad, dec_rad)
+}
+
+/// Azimuth (degrees from North towards East) at which the sun culminates: 180 (due South)
+/// when it passes south of the zenith, 0 (due North) when it passes north of it, as it
+/// does at southern latitudes (and in the tropics for part of the year).
+pub fn calculate_noon_azimuth_deg(latitude_rad: f32, declination_rad: f32) -> f32 {
+    let culminates_south = if latitude_rad == declination_rad {
+        latitude_rad >= 0.0 // Zenith, pick the usual side of the hemisphere
+    } else {
+        latitude_rad > declination_rad
+    };
+    if culminates_south { 180.0 } else { 0.0 }
+}
+
+/// Azimuth of `direction` in degrees from North towards East, in the 360 degree range
+/// centered on `center_azimuth_deg`. With the noon azimuth as center the sun's daytime
+/// arc never wraps, in either hemisphere.
+pub fn azimuth_around_deg(direction: Vec3, center_azimuth_deg: f32) -> f32 {
+    let azimuth_deg = direction.x.atan2(direction.z) * RADIANS_TO_DEGREES;
+    center_azimuth_deg + (azimuth_deg - center_azimuth_deg + 180.0).rem_euclid(360.0) - 180.0
+}
+
+/// Inverse of [`calculate_sun_direction`] for the time of day: returns the hour fraction
+/// (0.0 midnight, 0.5 noon) whose local hour angle matches `direction`.
+///
+/// The hour angle does not depend on declination, so this is also the point of the
+/// sun's daily path closest to `direction` for any year fraction.
+pub fn hour_fraction_from_direction(direction: Vec3, latitude_rad: f32) -> f32 {
+    // x = cos(dec)sin(HA), y*cos(lat) - z*sin(lat) = cos(dec)cos(HA)
+    let sin_part = direction.x;
+    let cos_part = direction.y * latitude_rad.cos() - direction.z * latitude_rad.sin();
+    let local_hour_angle_rad = sin_part.atan2(cos_part);
+    ((local_hour_angle_rad + PI) / (2.0 * PI)).rem_euclid(1.0)
+}
+
+/// Direction of the north celestial pole (the sky sphere rotation axis) in the observer's
+/// local frame (X east, Y up, Z north): `latitude_rad` above the northern horizon.
+pub fn celestial_pole_direction(latitude_rad: f32) -> Vec3 {
+    Vec3::new(0.0, latitude_rad.sin(), latitude_rad.cos())
+}
+
+/// Rotation of the sky sphere at `hour_fraction` (0.0 midnight, 0.5 noon) around
+/// [`celestial_pole_direction`], as applied to the `SkyCenter` transform. Use it to align
+/// external sky domes, planetarium textures or telescope views with the plugin's sky.
+pub fn celestial_rotation(hour_fraction: f32, latitude_rad: f32) -> Quat {
+    let rotation_angle_rad = PI - hour_fraction * 2.0 * PI;
+    Quat::from_axis_angle(celestial_pole_direction(latitude_rad), rotation_angle_rad)
+}
+
+/// Declination (radians) of a direction in the observer's local frame,
+/// i.e. its angle above the celestial equator.
+pub fn declination_from_direction(direction: Vec3, latitude_rad: f32) -> f32 {
+    celestial_pole_direction(latitude_rad)
+        .dot(direction.normalize_or_zero())
+        .clamp(-1.0, 1.0)
+        .asin()
+}
+
+/// Inverse of [`calculate_declination`]. Every declination inside `[-tilt, tilt]` is reached
+/// twice a year, the returned year fraction is the one closest to `near_year_fraction`.
+///
+/// Returns `None` if the declination is out of reach for the tilt.
+pub fn year_fraction_from_declination(
+    declination_rad: f32,
+    axial_tilt_rad: f32,
+    near_year_fraction: f32,
+) -> Option<f32> {
+    if axial_tilt_rad.abs() < f32::EPSILON {
+        return (declination_rad.abs() < f32::EPSILON).then_some(near_year_fraction);
+    }
+    let sin_yf_angle = declination_rad / axial_tilt_rad;
+    if sin_yf_angle.abs() > 1.0 + f32::EPSILON {
+        return None;
+    }
+
+    let phi = sin_yf_angle.clamp(-1.0, 1.0).asin();
+    let yf1 = (phi / (2.0 * PI)).rem_euclid(1.0);
+    let yf2 = (0.5 - phi / (2.0 * PI)).rem_euclid(1.0);
+
+    // Distance on the yearly circle
+    let dist = |yf: f32| {
+        let d = (yf - near_year_fraction).rem_euclid(1.0);
+        d.min(1.0 - d)
+    };
+    Some(if dist(yf1) <= dist(yf2) { yf1 } else { yf2 })
+}
+
+/// How much daylight a sun direction gives, from 0.0 (night) to 1.0 (day), with a smooth
+/// twilight while the sun is within ~6 degrees of the horizon.
+pub fn daylight_factor(sun_direction: Vec3) -> f32 {
+    let t = ((sun_direction.normalize_or_zero().y + 0.1) / 0.2).clamp(0.0, 1.0);
+    t * t * (3.0 - 2.0 * t)
+}
+
+/// Rotation of a light shining from `direction` (pointing towards the light) to the origin.
+///
+/// `look_at` with a fixed `Vec3::Y` up degenerates when the light passes the zenith and
+/// flips the light's roll, which makes shadow maps snap at noon near the equator. This
+/// instead rotates a straight down light along the shortest arc, which is continuous
+/// everywhere except at the nadir, where a light is never visible.
+pub fn light_rotation_from_direction(direction: Vec3) -> Quat {
+    let straight_down = Quat::from_rotation_x(-PI / 2.0); // Forward (-Z) turned to -Y
+    match (-direction).try_normalize() {
+        Some(forward) => Quat::from_rotation_arc(Vec3::NEG_Y, forward) * straight_down,
+        None => straight_down,
+    }
+}
+
+/// Index of the warp segment containing `hour_fraction` (in `[0, 1)`).
+fn warp_segment_index(segments: &[(f32, f32)], hour_fraction: f32) -> usize {
+    segments
+        .iter()
+        .rposition(|(start, _)| *start <= hour_fraction)
+        // Before the first start, still in the last segment of the previous cycle
+        .unwrap_or(segments.len() - 1)
+}
+
+/// Piecewise time warp: converts `delta_secs` of clock time, starting at `hour_fraction`,
+/// into sky time (seconds) when the cycle is split into `segments` of
+/// `(start_hour_fraction, speed)` sorted by start. Negative deltas (rewinds) walk the
+/// segments backwards, so a rewind undoes the same clock time played forward.
+pub fn warp_cycle_delta(
+    hour_fraction: f32,
+    delta_secs: f32,
+    cycle_duration_secs: f32,
+    segments: &[(f32, f32)],
+) -> f32 {
+    if delta_secs == 0.0 || cycle_duration_secs <= 0.0 || segments.is_empty() {
+        return delta_secs;
+    }
+    if delta_secs < 0.0 {
+        // Backwards is forwards through the mirrored cycle, where each segment starts at
+        // the mirror of its end
+        let mut mirrored: SmallVec<[(f32, f32); 6]> = segments
+            .iter()
+            .enumerate()
+            .map(|(index, (_, speed))| {
+                let end = segments[(index + 1) % segments.len()].0;
+                ((1.0 - end).rem_euclid(1.0), *speed)
+            })
+            .collect();
+        mirrored.sort_by(|a, b| a.0.total_cmp(&b.0));
+        return -warp_cycle_delta(
+            1.0 - hour_fraction.rem_euclid(1.0),
+            -delta_secs,
+            cycle_duration_secs,
+            &mirrored,
+        );
+    }
+    let speed = |index: usize| segments[index].1.max(1e-3);
+    if segments.len() == 1 {
+        return delta_secs * speed(0);
+    }
+    let segment_length = |index: usize| {
+        (segments[(index + 1) % segments.len()].0 - segments[index].0).rem_euclid(1.0)
+    };
+
+    // Whole cycles first, so big catch-up jumps don't walk every segment
+    let real_cycle_secs: f32 = (0..segments.len())
+        .map(|index| segment_length(index) * cycle_duration_secs / speed(index))
+        .sum();
+    let full_cycles = (delta_secs / real_cycle_secs).floor();
+    let mut remaining_secs = delta_secs - full_cycles * real_cycle_secs;
+    let mut sky_delta_secs = full_cycles * cycle_duration_secs;
+
+    let mut hour_fraction = hour_fraction.rem_euclid(1.0);
+    let mut index = warp_segment_index(segments, hour_fraction);
+    // Less than a cycle is left, so every segment is entered at most once more
+    for _ in 0..=segments.len() {
+        let segment_end = segments[(index + 1) % segments.len()].0;
+        let left_fraction = (segment_end - hour_fraction).rem_euclid(1.0);
+        let needed_secs = left_fraction * cycle_duration_secs / speed(index);
+        if remaining_secs < needed_secs {
+            sky_delta_secs += remaining_secs * speed(index);
+            break;
+        }
+        sky_delta_secs += left_fraction * cycle_duration_secs;
+        remaining_secs -= needed_secs;
+        hour_fraction = segment_end;
+        index = (index + 1) % segments.len();
+    }
+    sky_delta_secs
+}
+
+/// How many times the cycle position `fraction` is passed when moving forward from
+/// `start` by `delta` (all in cycle fractions, `delta` may span several cycles).
+pub(crate) fn times_passed(fraction: f32, start: f32, delta: f32) -> u32 {
+    if delta <= 0.0 {
+        return 0;
+    }
+    ((start + delta - fraction).floor() - (start - fraction).floor()).max(0.0) as u32
+}
+
+/// Sky sphere rotation and local sun direction at clock time `hour_fraction` (0.0 midnight,
+/// 0.5 noon), exactly as written by the plugin: the rotation goes to the `SkyCenter`
+/// transform, the sun light is placed at the direction (relative to the sky center) and
+/// turned with [`light_rotation_from_direction`]. For skies posed from your own schedules,
+/// e.g. rollback netcode re-simulating past frames.
+pub fn compute_sky_pose(sky_center: &SkyCenter, hour_fraction: f32) -> (Quat, Vec3) {
+    sky_pose(
+        sky_center,
+        hour_fraction,
+        SunUpdatePolicy::EveryFrame,
+        |(hour_fraction, latitude_rad, tilt_rad, year_fraction)| {
+            CachedSunPosition::compute(hour_fraction, latitude_rad, tilt_rad, year_fraction)
+        },
+    )
+}
+
+/// [`compute_sky_pose`] with the sun quantized by `policy`, looking up the sun position
+/// (solar hour fraction, latitude, tilt, season) with `sun_position`.
+fn sky_pose(
+    sky_center: &SkyCenter,
+    hour_fraction: f32,
+    policy: SunUpdatePolicy,
+    sun_position: impl FnOnce((f32, f32, f32, f32)) -> CachedSunPosition,
+) -> (Quat, Vec3) {
+    let latitude_rad = sky_center.latitude_degrees * DEGREES_TO_RADIANS;
+    let solar_hour_fraction = sky_center.solar_hour_fraction(hour_fraction);
+    let sun_direction = sun_position((
+        policy.sun_hour_fraction(solar_hour_fraction),
+        latitude_rad,
+        sky_center.planet_tilt_degrees * DEGREES_TO_RADIANS,
+        sky_center.season_year_fraction(),
+    ))
+    .direction;
+    (
+        celestial_rotation(solar_hour_fraction, latitude_rad),
+        sun_direction,
+    )
+}
+
+fn update_sky_center<T: SunClock + Resource>(
+    mut q_sky_center: Query<(
+        Entity,
+        &mut Transform,
+        &mut SkyCenter,
+        &mut PreciseCycleTime,
+        Option<&SkyOrigin>,
+        Option<&HorizonProfile>,
+        Option<&SunUpdatePolicy>,
+        Option<&SkyTick>,
+    )>,
+    mut q_sun: Query<&mut Transform, Without<SkyCenter>>,
+    mut sunrise_events: MessageWriter<SunriseEvent>,
+    mut sunset_events: MessageWriter<SunsetEvent>,
+    mut catch_up_events: MessageWriter<SkyCatchUp>,
+    mut new_cycle_events: MessageWriter<NewCycleEvent>,
+    mut new_year_events: MessageWriter<NewYearEvent>,
+    catch_up_settings: Res<SkyCatchUpSettings>,
+    thresholds: Res<TimeOfDayThresholds>,
+    mut sun_position_cache: ResMut<SunPositionCache>,
+    mut last_transform_writes: Local<HashMap<Entity, f32>>,
+    time: Res<T>,
+) {
+    // Keyed by the inputs, so never stale, but only this frame's positions are reused
+    sun_position_cache.clear();
+    for (
+        entity,
+        mut sky_transforms,
+        mut sky_center,
+        mut precise_cycle_time,
+        sky_origin,
+        horizon_profile,
+        update_policy,
+        sky_tick,
+    ) in q_sky_center.iter_mut()
+    {
+        // Everything below divides by the cycle duration, use `paused` to stop the clock
+        if !(sky_center.cycle_duration_secs > 0.0 && sky_center.cycle_duration_secs.is_finite()) {
+            warn_once!(
+                "SkyCenter {} has an invalid cycle_duration_secs ({}), set `paused` to stop the sky instead.",
+                entity,
+                sky_center.cycle_duration_secs
+            );
+            continue;
+        }
+        let previous_day_index = sky_center.day_index;
+        let skipped_secs = std::mem::take(&mut precise_cycle_time.skipped_secs);
+        let (previous_hour_fraction, hour_fraction, delta_fraction, day_index) =
+            if let Some(sky_tick) = sky_tick {
+                // A pure function of the tick, so resimulated frames land on the same sky
+                let (day_index, cycle_secs) =
+                    sky_tick.day_and_cycle_secs(sky_center.cycle_duration_secs);
+                let (previous_hour_fraction, hour_fraction) =
+                    precise_cycle_time.set(&mut sky_center, cycle_secs);
+                let delta_fraction = (day_index as f64 - previous_day_index as f64) as f32
+                    + (hour_fraction - previous_hour_fraction);
+                (
+                    previous_hour_fraction,
+                    hour_fraction,
+                    delta_fraction,
+                    day_index,
+                )
+            } else {
+                // Advance time. Accumulated instead of derived from elapsed time,
+                // so current_cycle_time can be set from outside (sliders, editor gizmo, etc.)
+                let clock_delta = if sky_center.paused {
+                    0.0
+                } else {
+                    sky_center
+                        .warped_cycle_delta(time.delta_secs() * sky_center.time_scale, &thresholds)
+                };
+                let delta = clock_delta as f64 + skipped_secs;
+                // Cycle time loops
+                let (previous_hour_fraction, hour_fraction) =
+                    precise_cycle_time.advance(&mut sky_center, delta);
+                let delta_fraction = (delta / sky_center.cycle_duration_secs as f64) as f32;
+                let day_index = if delta_fraction >= 0.0 {
+                    let completed_cycles =
+                        times_passed(0.0, previous_hour_fraction, delta_fraction);
+                    previous_day_index + completed_cycles as u64
+                } else {
+                    // A rewinding clock takes back the midnights it crosses
+                    let rewound_cycles = times_passed(
+                        0.0,
+                        previous_hour_fraction + delta_fraction,
+                        -delta_fraction,
+                    );
+                    previous_day_index.saturating_sub(rewound_cycles as u64)
+                };
+                (
+                    previous_hour_fraction,
+                    hour_fraction,
+                    delta_fraction,
+                    day_index,
+                )
+            };
+        let completed_cycles = day_index.saturating_sub(previous_day_index);
+        sky_center.day_index = day_index;
+        let elapsed_secs = time.elapsed_secs();
+
+        // Seasons move on with every completed cycle
+        if let Some(days_per_year) = sky_center.days_per_year.filter(|days| *days > 0.0) {
+            let season = match sky_tick {
+                Some(sky_tick) => {
+                    let year_position = |day_index: u64| {
+                        sky_tick.start_year_fraction as f64
+                            + day_index as f64 / days_per_year as f64
+                    };
+                    Some((
+                        year_position(day_index).rem_euclid(1.0) as f32,
+                        (year_position(day_index).floor()
+                            - year_position(previous_day_index).floor())
+                        .max(0.0) as u32,
+                    ))
+                }
+                None if completed_cycles > 0 => {
+                    let year_fraction =
+                        sky_center.year_fraction + completed_cycles as f32 / days_per_year;
+                    Some((
+                        year_fraction.rem_euclid(1.0),
+                        year_fraction.floor().max(0.0) as u32,
+                    ))
+                }
+                None => None,
+            };
+            if let Some((year_fraction, new_years)) = season {
+                for _ in 0..new_years {
+                    new_year_events.write(NewYearEvent {
+                        sky: entity,
+                        at: sky_center.timestamp(elapsed_secs),
+                    });
+                }
+                sky_center.year_fraction = year_fraction;
+            }
+        }
+
+        // Horizon (or skyline) crossings passed during this frame. Rewinding (negative delta)
+        // sends none.
+        let (sunrise, sunset, sunrises, sunsets) = match sky_center
+            .sunrise_and_sunset_over(horizon_profile)
+            .as_slice()
+        {
+            [sunrise, sunset] => (
+                *sunrise,
+                *sunset,
+                times_passed(*sunrise, previous_hour_fraction, delta_fraction),
+                times_passed(*sunset, previous_hour_fraction, delta_fraction),
+            ),
+            _ => (0.0, 0.0, 0, 0),
+        };
+
+        if delta_fraction >= catch_up_settings.threshold_cycles {
+            // A big jump (app resumed, time skip): one summary instead of a flood of events
+            catch_up_events.write(SkyCatchUp {
+                sky: entity,
+                days_passed: delta_fraction,
+                phases_crossed: sunrises + sunsets,
+                at: sky_center.timestamp(elapsed_secs),
+            });
+        } else {
+            for cycle_index in previous_day_index + 1..=sky_center.day_index {
+                new_cycle_events.write(NewCycleEvent {
+                    sky: entity,
+                    cycle_index,
+                    at: SkyTimestamp {
+                        cycle_time_secs: 0.0,
+                        hour_fraction: 0.0,
+                        day_index: cycle_index,
+                        elapsed_secs,
+                    },
+                });
+            }
+
+            // In the order they happened: cycles after the frame start, then the kind
+            let mut crossings: SmallVec<[(f32, bool); 4]> = SmallVec::new();
+            for (crossing, count, is_sunrise) in
+                [(sunrise, sunrises, true), (sunset, sunsets, false)]
+            {
+                let first = (crossing - previous_hour_fraction).rem_euclid(1.0);
+                crossings.extend((0..count).map(|k| (first + k as f32, is_sunrise)));
+            }
+            crossings.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+            for (cycles_after_start, is_sunrise) in crossings {
+                let position = previous_hour_fraction + cycles_after_start;
+                let hour_fraction = position.rem_euclid(1.0);
+                let at = SkyTimestamp {
+                    cycle_time_secs: hour_fraction * sky_center.cycle_duration_secs,
+                    hour_fraction,
+                    day_index: previous_day_index + position.floor().max(0.0) as u64,
+                    elapsed_secs,
+                };
+                if is_sunrise {
+                    sunrise_events.write(SunriseEvent { sky: entity, at });
+                } else {
+                    sunset_events.write(SunsetEvent { sky: entity, at });
+                }
+            }
+        }
+
+        // Origin shifts are applied right away, whatever the policy
+        let translation = sky_origin.map_or(Vec3::ZERO, |origin| origin.0);
+        if sky_transforms.translation != translation {
+            sky_transforms.translation = translation;
+        }
+
+        let policy = update_policy.copied().unwrap_or_default();
+        let interval_passed = match policy {
+            SunUpdatePolicy::Interval(interval) => {
+                let due = last_transform_writes
+                    .get(&entity)
+                    .is_none_or(|last| (elapsed_secs - last).abs() >= interval.as_secs_f32());
+                if due {
+                    last_transform_writes.insert(entity, elapsed_secs);
+                }
+                due
+            }
+            _ => true,
+        };
+        if !interval_passed {
+            continue;
+        }
+
+        let (sky_rotation, sun_direction_local) = sky_pose(
+            &sky_center,
+            hour_fraction,
+            policy,
+            |(hour_fraction, latitude_rad, tilt_rad, year_fraction)| {
+                sun_position_cache.sun_position(
+                    hour_fraction,
+                    latitude_rad,
+                    tilt_rad,
+                    year_fraction,
+                )
+            },
+        );
+        // Sky sphere rotation. Useful for attach stars and celestial bodies to the sky sphere.
+        if policy.should_write(sky_transforms.rotation, sky_rotation) {
+            sky_transforms.rotation = sky_rotation;
+        }
 
         if let Ok(mut sun_transform) = q_sun.get_mut(sky_center.sun) {
-            sun_transform.translation = sun_direction_local;
-            sun_transform.look_at(Vec3::ZERO, Vec3::Y); // Ensure the light points towards the origin
+            // Ensure the light points towards the origin
+            let sun_rotation = light_rotation_from_direction(sun_direction_local);
+            if policy.should_write(sun_transform.rotation, sun_rotation) {
+                sun_transform.translation = sun_direction_local;
+                sun_transform.rotation = sun_rotation;
+            }
+        }
+    }
+    last_transform_writes.retain(|entity, _| q_sky_center.contains(*entity));
+}
+
+/// Keeps the `SkyCenter` of `TimedSkyConfig` entities solved from their config: inserted
+/// when missing, re-solved (keeping the clock) whenever the config changes.
+fn sync_timed_sky_config(
+    mut commands: Commands,
+    mut q_timed_config: Query<
+        (Entity, &TimedSkyConfig, Option<&mut SkyCenter>),
+        Changed<TimedSkyConfig>,
+    >,
+) {
+    for (entity, timed_config, sky_center) in q_timed_config.iter_mut() {
+        let result = match sky_center {
+            Some(mut sky_center) => sky_center.update_from_timed_config(timed_config),
+            None => SkyCenter::from_timed_config(timed_config).map(|sky_center| {
+                commands.entity(entity).insert(sky_center);
+            }),
+        };
+        if let Err(err) = result {
+            warn!(
+                "TimedSkyConfig of {} is impossible, keeping the previous sky: {}",
+                entity, err
+            );
+        }
+    }
+}
+
+fn update_sun_state(
+    thresholds: Res<TimeOfDayThresholds>,
+    mut sun_position_cache: ResMut<SunPositionCache>,
+    mut q_sky_center: Query<(&SkyCenter, &mut SunState, Option<&HorizonProfile>)>,
+) {
+    for (sky_center, mut sun_state, horizon_profile) in q_sky_center.iter_mut() {
+        if !(sky_center.cycle_duration_secs > 0.0 && sky_center.cycle_duration_secs.is_finite()) {
+            continue;
+        }
+        let hour_fraction = sky_center.current_cycle_time / sky_center.cycle_duration_secs;
+        let state = sky_center.sun_state_at_cached(hour_fraction, &mut sun_position_cache);
+
+        *sun_state = sun_state.followed_by(SunState {
+            sky_angular_velocity: state.sky_angular_velocity
+                * sky_center.warp_speed_at(hour_fraction, &thresholds),
+            horizon_elevation_deg: horizon_profile
+                .map_or(0.0, |profile| profile.elevation_at(state.azimuth_deg)),
+            ..state
+        });
+    }
+}
+
+fn update_moon_center(
+    mut q_moon_center: Query<(&SkyCenter, &SunState, &mut MoonCenter, &mut MoonPhase)>,
+    mut q_moon: Query<
+        (
+            &mut Transform,
+            Option<&mut DirectionalLight>,
+            Option<&MoonLight>,
+        ),
+        Without<SkyCenter>,
+    >,
+) {
+    for (sky_center, sun_state, mut moon_center, mut moon_phase) in q_moon_center.iter_mut() {
+        // Stopped like the sky itself, see `update_sky_center`
+        let cycle_duration = sky_center.cycle_duration_secs;
+        if !(cycle_duration > 0.0 && cycle_duration.is_finite()) {
+            continue;
+        }
+        // From the absolute sky time, so jumps and rewinds of any length land on the same moon
+        let sky_cycles = sky_center.day_index as f64
+            + sky_center.current_cycle_time as f64 / cycle_duration as f64;
+        let orbit = (sky_cycles / moon_center.orbital_period_cycles as f64).rem_euclid(1.0);
+        if orbit.is_finite() && moon_center.orbit_fraction != orbit as f32 {
+            moon_center.orbit_fraction = orbit as f32;
+        }
+
+        let orbit_fraction =
+            (moon_center.orbit_fraction + moon_center.phase_offset).rem_euclid(1.0);
+        let moon_direction_local = calculate_moon_direction(
+            sky_center.solar_hour_fraction(sky_center.current_cycle_time / cycle_duration),
+            sky_center.latitude_degrees * DEGREES_TO_RADIANS,
+            sky_center.planet_tilt_degrees * DEGREES_TO_RADIANS,
+            sky_center.season_year_fraction(),
+            orbit_fraction,
+            moon_center.inclination_degrees * DEGREES_TO_RADIANS,
+        );
+
+        // Sun-moon angle as seen from the observer, the moon is lit from the sun side
+        let phase_angle_rad = sun_state
+            .direction
+            .dot(moon_direction_local)
+            .clamp(-1.0, 1.0)
+            .acos();
+        let illuminated_fraction = (1.0 - phase_angle_rad.cos()) * 0.5;
+        let phase = match orbit_fraction {
+            f if !(1.0 / 16.0..15.0 / 16.0).contains(&f) => LunarPhase::New,
+            f if (7.0 / 16.0..9.0 / 16.0).contains(&f) => LunarPhase::Full,
+            f if f < 0.5 => LunarPhase::Waxing,
+            _ => LunarPhase::Waning,
+        };
+        *moon_phase = MoonPhase {
+            phase_angle_deg: phase_angle_rad * RADIANS_TO_DEGREES,
+            illuminated_fraction,
+            phase,
+        };
+
+        if let Ok((mut moon_transform, mut moon_light, moon_light_settings)) =
+            q_moon.get_mut(moon_center.moon)
+        {
+            moon_transform.translation = moon_direction_local;
+            // Ensure the light points towards the origin
+            moon_transform.rotation = light_rotation_from_direction(moon_direction_local);
+
+            if let (Some(settings), Some(moon_light)) = (moon_light_settings, moon_light.as_mut()) {
+                // No shadows from below the ground
+                let shadows_enabled = settings.cast_shadows && moon_direction_local.y > 0.0;
+                if moon_light.shadows_enabled != shadows_enabled {
+                    moon_light.shadows_enabled = shadows_enabled;
+                }
+            }
+
+            if let (Some(full_moon_illuminance), Some(mut moon_light)) =
+                (moon_center.full_moon_illuminance, moon_light)
+            {
+                // daylight_factor doubles as a smooth "above the horizon" factor
+                moon_light.illuminance = full_moon_illuminance
+                    * illuminated_fraction
+                    * daylight_factor(moon_direction_local);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy::time::TimeUpdateStrategy;
+
+    use super::*;
+    use crate::random_stars::{RandomStarsPlugin, StarSpawnerCache};
+
+    /// Headless app without sky plugins, the clocks advancing by `step` every update.
+    fn headless_app(step: Duration) -> App {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(TimeUpdateStrategy::ManualDuration(step));
+        app
+    }
+
+    /// Headless app moving the skies and their stars with the virtual clock.
+    fn sky_app(step: Duration) -> App {
+        let mut app = headless_app(step);
+        app.add_plugins((SunMovePlugin::default(), RandomStarsPlugin));
+        // Stars are only spawned in worlds with mesh and material assets
+        app.init_resource::<Assets<Mesh>>()
+            .init_resource::<Assets<StandardMaterial>>();
+        app
+    }
+
+    /// Spawns `sky_center` with its own sun and star field, returns (sky, sun).
+    fn spawn_sky(app: &mut App, sky_center: SkyCenter) -> (Entity, Entity) {
+        let sun = app.world_mut().spawn(Transform::default()).id();
+        let sky = app
+            .world_mut()
+            .spawn((
+                SkyCenter { sun, ..sky_center },
+                StarSpawner {
+                    star_count: 10,
+                    ..default()
+                },
+            ))
+            .id();
+        (sky, sun)
+    }
+
+    /// Asserts that the sun transform and `SunState` of `sky` match its own clock and config.
+    fn assert_sun_follows_sky(app: &App, sky: Entity, sun: Entity) {
+        let world = app.world();
+        let sky_center = world.get::<SkyCenter>(sky).unwrap();
+        let expected =
+            sky_center.sun_state_at(sky_center.current_cycle_time / sky_center.cycle_duration_secs);
+        let sun_state = world.get::<SunState>(sky).unwrap();
+        let sun_transform = world.get::<Transform>(sun).unwrap();
+        assert!(
+            sun_transform
+                .translation
+                .abs_diff_eq(expected.direction, 1e-4),
+            "sun of {sky} at {}, expected {}",
+            sun_transform.translation,
+            expected.direction
+        );
+        assert!(sun_state.direction.abs_diff_eq(expected.direction, 1e-4));
+        assert!((sun_state.altitude_deg - expected.altitude_deg).abs() < 1e-2);
+    }
+
+    fn star_illuminance(app: &App, sky: Entity) -> f32 {
+        let world = app.world();
+        let material = world
+            .resource::<StarSpawnerCache>()
+            .material(sky)
+            .expect("the stars of the sky were spawned");
+        world
+            .resource::<Assets<StandardMaterial>>()
+            .get(material.id())
+            .unwrap()
+            .emissive
+            .red
+    }
+
+    #[test]
+    fn skies_follow_their_own_config() {
+        let mut app = sky_app(Duration::from_millis(100));
+        let (north, north_sun) = spawn_sky(
+            &mut app,
+            SkyCenter {
+                latitude_degrees: 50.0,
+                cycle_duration_secs: 600.0,
+                current_cycle_time: 300.0,
+                ..default()
+            },
+        );
+        let (south, south_sun) = spawn_sky(
+            &mut app,
+            SkyCenter {
+                latitude_degrees: -30.0,
+                planet_tilt_degrees: 10.0,
+                year_fraction: 0.25,
+                cycle_duration_secs: 120.0,
+                time_scale: 2.0,
+                ..default()
+            },
+        );
+
+        for _ in 0..10 {
+            app.update();
+            assert_sun_follows_sky(&app, north, north_sun);
+            assert_sun_follows_sky(&app, south, south_sun);
+        }
+
+        let world = app.world();
+        let north_elapsed = world.get::<SkyCenter>(north).unwrap().current_cycle_time - 300.0;
+        let south_elapsed = world.get::<SkyCenter>(south).unwrap().current_cycle_time;
+        assert!(north_elapsed > 0.0);
+        assert!((south_elapsed / north_elapsed - 2.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn stars_follow_their_own_sun() {
+        let mut app = sky_app(Duration::from_millis(100));
+        let (noon, _) = spawn_sky(
+            &mut app,
+            SkyCenter {
+                current_cycle_time: 300.0,
+                paused: true,
+                ..default()
+            },
+        );
+        let (midnight, _) = spawn_sky(
+            &mut app,
+            SkyCenter {
+                latitude_degrees: -30.0,
+                current_cycle_time: 0.0,
+                paused: true,
+                ..default()
+            },
+        );
+
+        // The stars spawn on the first update and read the sun placed by it on the next
+        app.update();
+        app.update();
+
+        assert_eq!(star_illuminance(&app, noon), 0.0);
+        assert_eq!(star_illuminance(&app, midnight), 1.0);
+    }
+
+    #[test]
+    fn despawning_a_sky_leaves_the_other_running() {
+        let mut app = sky_app(Duration::from_millis(100));
+        let (removed, removed_sun) = spawn_sky(&mut app, SkyCenter::default());
+        let (kept, kept_sun) = spawn_sky(
+            &mut app,
+            SkyCenter {
+                latitude_degrees: 40.0,
+                cycle_duration_secs: 60.0,
+                ..default()
+            },
+        );
+        app.update();
+        app.update();
+
+        app.world_mut().despawn(removed);
+        app.world_mut().despawn(removed_sun);
+        let before = app
+            .world()
+            .get::<SkyCenter>(kept)
+            .unwrap()
+            .current_cycle_time;
+        for _ in 0..5 {
+            app.update();
+            assert_sun_follows_sky(&app, kept, kept_sun);
+        }
+
+        let world = app.world();
+        assert!(world.get::<SkyCenter>(kept).unwrap().current_cycle_time > before);
+        let cache = world.resource::<StarSpawnerCache>();
+        assert!(cache.material(removed).is_none());
+        assert!(cache.material(kept).is_some());
+    }
+
+    #[test]
+    fn moon_stays_put_without_a_cycle_duration() {
+        let mut app = sky_app(Duration::from_millis(100));
+        let moon = app.world_mut().spawn(Transform::default()).id();
+        let (sky, _) = spawn_sky(
+            &mut app,
+            SkyCenter {
+                cycle_duration_secs: 0.0,
+                ..default()
+            },
+        );
+        app.world_mut()
+            .entity_mut(sky)
+            .insert(MoonCenter { moon, ..default() });
+        for _ in 0..3 {
+            app.update();
+        }
+
+        let world = app.world();
+        assert_eq!(world.get::<MoonCenter>(sky).unwrap().orbit_fraction, 0.0);
+        assert!(world.get::<Transform>(moon).unwrap().is_finite());
+    }
+
+    /// Cycle events read after the sky moved.
+    #[derive(Resource, Default)]
+    struct SkyEventLog {
+        new_cycles: Vec<u64>,
+        sunrises: usize,
+        sunsets: usize,
+    }
+
+    fn log_sky_events(
+        mut log: ResMut<SkyEventLog>,
+        mut new_cycles: MessageReader<NewCycleEvent>,
+        mut sunrises: MessageReader<SunriseEvent>,
+        mut sunsets: MessageReader<SunsetEvent>,
+    ) {
+        log.new_cycles
+            .extend(new_cycles.read().map(|event| event.cycle_index));
+        log.sunrises += sunrises.read().count();
+        log.sunsets += sunsets.read().count();
+    }
+
+    /// Spawns a sky at the equator with a 2 s cycle, returns it.
+    fn spawn_clock_sky(app: &mut App) -> Entity {
+        let sun = app.world_mut().spawn(Transform::default()).id();
+        app.world_mut()
+            .spawn(SkyCenter {
+                sun,
+                cycle_duration_secs: 2.0,
+                ..default()
+            })
+            .id()
+    }
+
+    /// Asserts that `sky` spent `elapsed_secs` on its clock since midnight of day 0.
+    fn assert_sky_clock(app: &App, sky: Entity, elapsed_secs: f32) {
+        let sky_center = app.world().get::<SkyCenter>(sky).unwrap();
+        let day_index = (elapsed_secs / sky_center.cycle_duration_secs).floor() as u64;
+        let cycle_time = elapsed_secs.rem_euclid(sky_center.cycle_duration_secs);
+        assert_eq!(sky_center.day_index, day_index);
+        assert!(
+            (sky_center.current_cycle_time - cycle_time).abs() < 1e-3,
+            "cycle time {}, expected {cycle_time}",
+            sky_center.current_cycle_time
+        );
+    }
+
+    #[test]
+    fn virtual_time_drives_the_sky() {
+        let mut app = headless_app(Duration::from_millis(100));
+        app.add_plugins(SunMovePlugin::default());
+        let sky = spawn_clock_sky(&mut app);
+
+        for _ in 0..25 {
+            app.update();
+        }
+        let elapsed = app.world().resource::<Time<Virtual>>().elapsed_secs();
+        assert!(elapsed > 2.0);
+        assert_sky_clock(&app, sky, elapsed);
+
+        // Pausing the virtual clock stops the sky
+        app.world_mut().resource_mut::<Time<Virtual>>().pause();
+        for _ in 0..5 {
+            app.update();
+        }
+        assert_sky_clock(&app, sky, elapsed);
+    }
+
+    #[test]
+    fn fixed_time_drives_the_sky() {
+        let mut app = headless_app(Duration::from_millis(100));
+        app.add_plugins(SunMovePlugin::in_schedule(FixedUpdate));
+        let sky = spawn_clock_sky(&mut app);
+
+        for _ in 0..25 {
+            app.update();
+        }
+        // The sky only moves by whole fixed timesteps, behind the virtual clock
+        let world = app.world();
+        let fixed_elapsed = world.resource::<Time<Fixed>>().elapsed_secs();
+        assert!(fixed_elapsed < world.resource::<Time<Virtual>>().elapsed_secs());
+        assert_sky_clock(&app, sky, fixed_elapsed);
+    }
+
+    /// A clock stepped by hand, rewinding with a negative delta.
+    #[derive(Resource, Default)]
+    struct RewindingClock {
+        delta_secs: f32,
+        elapsed_secs: f32,
+    }
+
+    impl SunClock for RewindingClock {
+        fn delta_secs(&self) -> f32 {
+            self.delta_secs
+        }
+
+        fn elapsed_secs(&self) -> f32 {
+            self.elapsed_secs
+        }
+    }
+
+    fn step_clock(app: &mut App, delta_secs: f32) {
+        let mut clock = app.world_mut().resource_mut::<RewindingClock>();
+        clock.delta_secs = delta_secs;
+        clock.elapsed_secs += delta_secs;
+        app.update();
+    }
+
+    #[test]
+    fn rewinding_clock_drives_the_sky() {
+        let mut app = headless_app(Duration::from_millis(100));
+        app.add_plugins(TypedSunMovePlugin::<RewindingClock>::default())
+            .init_resource::<RewindingClock>()
+            .init_resource::<SkyEventLog>()
+            .add_systems(Update, log_sky_events.after(SunMoveSet));
+        let sky = spawn_clock_sky(&mut app);
+
+        // Nothing moves without a delta
+        step_clock(&mut app, 0.0);
+        assert_sky_clock(&app, sky, 0.0);
+
+        // Sunrise near 0.5 s, sunset near 1.5 s, then midnight and the next sunrise
+        step_clock(&mut app, 1.0);
+        step_clock(&mut app, 1.8);
+        assert_sky_clock(&app, sky, 2.8);
+        let log = app.world().resource::<SkyEventLog>();
+        assert_eq!(log.new_cycles, [1]);
+        assert_eq!((log.sunrises, log.sunsets), (2, 1));
+
+        // Back over the sunrise and midnight: the day is taken back, without events
+        step_clock(&mut app, -1.0);
+        assert_sky_clock(&app, sky, 1.8);
+        let log = app.world().resource::<SkyEventLog>();
+        assert_eq!(log.new_cycles, [1]);
+        assert_eq!((log.sunrises, log.sunsets), (2, 1));
+
+        // Forward again: the same midnight and sunrise are passed again
+        step_clock(&mut app, 1.0);
+        assert_sky_clock(&app, sky, 2.8);
+        let log = app.world().resource::<SkyEventLog>();
+        assert_eq!(log.new_cycles, [1, 1]);
+        assert_eq!((log.sunrises, log.sunsets), (3, 1));
+    }
+
+    #[test]
+    fn sky_tick_rollback_is_deterministic() {
+        let mut app = headless_app(Duration::from_millis(100));
+        app.add_plugins(SunMovePlugin::default())
+            .init_resource::<SkyEventLog>()
+            .add_systems(Update, log_sky_events.after(SunMoveSet));
+        let sky = spawn_clock_sky(&mut app);
+        app.world_mut().entity_mut(sky).insert(SkyTick {
+            start_year_fraction: 0.3,
+            ..SkyTick::new(0.2)
+        });
+        app.world_mut()
+            .get_mut::<SkyCenter>(sky)
+            .unwrap()
+            .days_per_year = Some(4.0);
+        let run_tick = |app: &mut App, tick: u64| {
+            app.world_mut().get_mut::<SkyTick>(sky).unwrap().tick = tick;
+            app.update();
+            let sky_center = app.world().get::<SkyCenter>(sky).unwrap();
+            (
+                sky_center.current_cycle_time,
+                sky_center.day_index,
+                sky_center.year_fraction,
+            )
+        };
+
+        // Tick 6 is at 1.2 s, past the sunrise of day 0
+        let at_tick = run_tick(&mut app, 6);
+        assert_eq!(at_tick.1, 0);
+        *app.world_mut().resource_mut::<SkyEventLog>() = default();
+
+        // Over the sunset and midnight to 0.2 s into day 1
+        let ahead = run_tick(&mut app, 11);
+        assert_eq!(ahead.1, 1);
+        assert!((ahead.2 - 0.55).abs() < 1e-6);
+        let log = app.world().resource::<SkyEventLog>();
+        assert_eq!(log.new_cycles, [1]);
+        assert_eq!((log.sunrises, log.sunsets), (0, 1));
+
+        // Rolled back: the same sky as before, and nothing sent twice
+        assert_eq!(run_tick(&mut app, 6), at_tick);
+        assert_eq!(run_tick(&mut app, 6), at_tick);
+        let log = app.world().resource::<SkyEventLog>();
+        assert_eq!(log.new_cycles, [1]);
+        assert_eq!((log.sunrises, log.sunsets), (0, 1));
+    }
+
+    #[test]
+    fn sun_culminates_north_at_southern_latitudes() {
+        for latitude_degrees in [-30.0, -45.0, -60.0] {
+            for year_fraction in [0.0, 0.25, 0.5, 0.75] {
+                let sky_center = SkyCenter {
+                    latitude_degrees,
+                    year_fraction,
+                    ..default()
+                };
+                let declination_rad = calculate_declination(
+                    sky_center.planet_tilt_degrees * DEGREES_TO_RADIANS,
+                    sky_center.season_year_fraction(),
+                );
+                let noon_azimuth_deg = calculate_noon_azimuth_deg(
+                    latitude_degrees * DEGREES_TO_RADIANS,
+                    declination_rad,
+                );
+                assert_eq!(
+                    noon_azimuth_deg, 0.0,
+                    "at {latitude_degrees} deg, {year_fraction}"
+                );
+
+                let noon = sky_center.sun_state_at(0.5);
+                assert_eq!(noon.noon_azimuth_deg, noon_azimuth_deg);
+                assert!(
+                    azimuth_around_deg(noon.direction, noon_azimuth_deg).abs() < 0.1,
+                    "noon sun at {} deg azimuth",
+                    noon.azimuth_deg
+                );
+
+                // East in the morning, west in the evening, without wrapping over north
+                let morning = sky_center.sun_state_at(0.4);
+                let evening = sky_center.sun_state_at(0.6);
+                let morning_azimuth = azimuth_around_deg(morning.direction, noon_azimuth_deg);
+                let evening_azimuth = azimuth_around_deg(evening.direction, noon_azimuth_deg);
+                assert!(morning_azimuth > 0.0 && morning_azimuth < 180.0);
+                assert!(evening_azimuth < 0.0 && evening_azimuth > -180.0);
+                assert!(
+                    (azimuth_around_deg(morning.direction, 180.0) - morning.azimuth_deg).abs()
+                        < 1e-2
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn southern_sun_state_matches_the_heading_helpers() {
+        let mut app = headless_app(Duration::from_millis(100));
+        app.add_plugins(SunMovePlugin::default());
+        let sun = app.world_mut().spawn(Transform::default()).id();
+        let sky = app
+            .world_mut()
+            .spawn(SkyCenter {
+                sun,
+                latitude_degrees: -35.0,
+                year_fraction: 0.25,
+                current_cycle_time: 300.0,
+                paused: true,
+                ..default()
+            })
+            .id();
+        app.update();
+
+        let world = app.world();
+        let sky_center = world.get::<SkyCenter>(sky).unwrap();
+        let sun_state = world.get::<SunState>(sky).unwrap();
+        assert_eq!(sun_state.noon_azimuth_deg, 0.0);
+        assert!(sun_state.azimuth_deg < 0.1 || sun_state.azimuth_deg > 359.9);
+        assert!(azimuth_around_deg(sun_state.direction, sun_state.noon_azimuth_deg).abs() < 0.1);
+        let expected = sky_center.sun_state_at(0.5);
+        assert!(sun_state.direction.abs_diff_eq(expected.direction, 1e-4));
+        assert!((sun_state.altitude_deg - sky_center.sun_altitude_deg()).abs() < 1e-2);
+        // Winter noon at 35 deg south: 90 - 35 - 23.5
+        assert!((sun_state.altitude_deg - 31.5).abs() < 0.1);
+    }
+
+    #[test]
+    fn timed_config_round_trips() {
+        // Tropical on both sides of the zenith, southern, and a temperate control
+        for (latitude_degrees, year_fraction) in
+            [(10.0, 0.3), (-15.0, 0.8), (-35.0, 0.6), (50.0, 0.1)]
+        {
+            let sky_center = SkyCenter {
+                latitude_degrees,
+                year_fraction,
+                cycle_duration_secs: 600.0,
+                ..default()
+            };
+            let timed_config = sky_center.to_timed_config();
+            let solved = SkyCenter::from_timed_config(&timed_config).unwrap();
+            assert!(
+                (solved.latitude_degrees - latitude_degrees).abs() < 0.01,
+                "latitude {latitude_degrees} came back as {}",
+                solved.latitude_degrees
+            );
+            assert!(
+                (solved.year_fraction - year_fraction).abs() < 1e-3,
+                "year fraction {year_fraction} came back as {}",
+                solved.year_fraction
+            );
+            assert!((solved.cycle_duration_secs - 600.0).abs() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn circular_orbit_keeps_the_year_fraction() {
+        for step in 0..=20 {
+            let year_fraction = step as f32 / 20.0;
+            assert_eq!(
+                orbital_year_fraction(year_fraction, 0.0, 0.79),
+                year_fraction
+            );
+            assert_eq!(
+                year_fraction_from_orbital(year_fraction, 0.0, 0.79),
+                year_fraction
+            );
+            // And the eccentric path tends to it
+            let nearly_circular = orbital_year_fraction(year_fraction, 1e-6, 0.79);
+            assert!((nearly_circular - year_fraction).abs() < 1e-5);
+        }
+        let sky_center = SkyCenter {
+            latitude_degrees: 40.0,
+            year_fraction: 0.3,
+            ..default()
+        };
+        assert_eq!(sky_center.season_year_fraction(), 0.3);
+        let expected = calculate_sun_direction(
+            0.4,
+            40.0 * DEGREES_TO_RADIANS,
+            23.5 * DEGREES_TO_RADIANS,
+            0.3,
+        );
+        assert!(
+            sky_center
+                .sun_state_at(0.4)
+                .direction
+                .abs_diff_eq(expected, 1e-6)
+        );
+    }
+
+    #[test]
+    fn eccentric_seasons_advance_monotonically() {
+        for eccentricity in [0.0167, 0.3, 0.6, 0.9] {
+            let mut previous = f32::NEG_INFINITY;
+            for step in 0..=1000 {
+                let year_fraction = step as f32 / 1000.0;
+                let season = orbital_year_fraction(year_fraction, eccentricity, 0.79);
+                assert!(
+                    season > previous,
+                    "season went back at year fraction {year_fraction}, e = {eccentricity}"
+                );
+                previous = season;
+                let back = year_fraction_from_orbital(season, eccentricity, 0.79);
+                assert!(
+                    (back - year_fraction).abs() < 5e-4,
+                    "{year_fraction} came back as {back}, e = {eccentricity}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn kepler_solver_converges_at_high_eccentricity() {
+        for eccentricity in [0.9, 0.95, 0.99, 1.5] {
+            for step in 0..=360 {
+                let mean_anomaly = (step as f32 - 180.0) * DEGREES_TO_RADIANS * 2.0;
+                let eccentric_anomaly = solve_kepler(mean_anomaly, eccentricity);
+                assert!(eccentric_anomaly.is_finite());
+                // Eccentricities of 1 and more are clamped
+                let e = eccentricity.min(0.99);
+                let residual = eccentric_anomaly
+                    - e * eccentric_anomaly.sin()
+                    - mean_anomaly.rem_euclid(2.0 * PI);
+                assert!(
+                    residual.abs() < 1e-4,
+                    "residual {residual}, e = {eccentricity}"
+                );
+            }
+            assert!(orbital_year_fraction(0.42, eccentricity, 0.79).is_finite());
+        }
+    }
+
+    #[test]
+    fn solvers_stay_finite_near_the_limits() {
+        let tolerances = SolverTolerances::default();
+        let tilt = 23.5;
+        for day_fraction in [
+            0.0,
+            1e-7,
+            1e-4,
+            0.01,
+            0.5,
+            0.99,
+            1.0 - 1e-4,
+            1.0 - 1e-7,
+            1.0,
+        ] {
+            for max_height in [
+                0.0,
+                1e-3,
+                2.0 * tilt - 1e-3,
+                2.0 * tilt,
+                2.0 * tilt + 1e-3,
+                89.999,
+                90.0,
+            ] {
+                for hemisphere in [HemispherePreference::North, HemispherePreference::South] {
+                    let (day, night) = (600.0 * day_fraction, 600.0 * (1.0 - day_fraction));
+                    match calculate_latitude_yearfraction_with_tolerances(
+                        tilt,
+                        day,
+                        night,
+                        max_height,
+                        hemisphere,
+                        &tolerances,
+                    ) {
+                        Ok((latitude, year_fraction, declination)) => {
+                            assert!(latitude.abs() <= 90.0 + tolerances.angle_deg);
+                            assert!(declination.abs() <= tilt + tolerances.angle_deg);
+                            assert!((0.0..=1.0).contains(&year_fraction));
+                        }
+                        Err(SkyConfigError::DeclinationExceedsTilt {
+                            required_declination_deg,
+                            ..
+                        }) => assert!(required_declination_deg.is_finite()),
+                        Err(_) => {}
+                    }
+
+                    if let Some(solution) = calculate_latitude_yearfraction_checked(
+                        tilt,
+                        day,
+                        night,
+                        max_height,
+                        hemisphere,
+                        &tolerances,
+                    ) {
+                        assert!(solution.latitude_degrees.is_finite());
+                        assert!(solution.year_fraction.is_finite());
+                        let achieved = solution.achieved_day_duration_secs / 600.0;
+                        assert!(
+                            (achieved - day_fraction).abs() <= tolerances.match_relative + 1e-6
+                        );
+                        let height_error = solution.achieved_max_sun_height_deg - max_height;
+                        assert!(height_error.abs() / 90.0 <= tolerances.match_relative + 1e-6);
+                    }
+                }
+            }
+        }
+
+        // A polar day can't climb higher than twice the tilt
+        assert!(matches!(
+            calculate_latitude_yearfraction(
+                tilt,
+                600.0,
+                0.0,
+                2.0 * tilt + 1.0,
+                HemispherePreference::North
+            ),
+            Err(SkyConfigError::DeclinationExceedsTilt { .. })
+        ));
+        let (latitude, _, _) = calculate_latitude_yearfraction(
+            tilt,
+            600.0,
+            0.0,
+            2.0 * tilt - 1.0,
+            HemispherePreference::North,
+        )
+        .unwrap();
+        assert!((latitude - (90.0 - tilt + 0.5)).abs() < 1e-3);
+    }
+
+    #[test]
+    fn checked_solve_follows_the_orbit() {
+        let mut sky_center = SkyCenter {
+            orbital_eccentricity: 0.3,
+            ..default()
+        };
+        let timed_config = TimedSkyConfig {
+            day_duration_secs: 400.0,
+            night_duration_secs: 200.0,
+            max_sun_height_deg: 60.0,
+            hemisphere: HemispherePreference::North,
+            ..default()
+        };
+        let solution = sky_center
+            .update_from_timed_config_checked(&timed_config, &SolverTolerances::default())
+            .unwrap();
+        assert_eq!(solution.year_fraction, sky_center.year_fraction);
+        // The orbit moves the year fraction away from the circular season
+        assert!((sky_center.season_year_fraction() - sky_center.year_fraction).abs() > 1e-3);
+
+        let achieved = sky_center.to_timed_config();
+        assert!((achieved.day_duration_secs - 400.0).abs() < 0.1);
+        assert!((achieved.max_sun_height_deg - 60.0).abs() < 0.02);
+    }
+
+    #[test]
+    fn rewinds_undo_the_warp() {
+        // Day played 3x faster, between a quarter and three quarters of the cycle
+        let segments = [(0.0, 1.0), (0.25, 3.0), (0.75, 1.0)];
+        for hour_fraction in [0.0, 0.1, 0.25, 0.5, 0.9] {
+            for delta_secs in [5.0, 30.0, 80.0, 250.0] {
+                let forward = warp_cycle_delta(hour_fraction, delta_secs, 100.0, &segments);
+                let back = warp_cycle_delta(
+                    hour_fraction + forward / 100.0,
+                    -delta_secs,
+                    100.0,
+                    &segments,
+                );
+                assert!(
+                    (forward + back).abs() < 1e-2,
+                    "{delta_secs}s from {hour_fraction}: {forward} forward, {back} back"
+                );
+            }
+        }
+        // 25 sky seconds through the day in 8.3 clock seconds, then 1.7 unwarped ones
+        let rewound = warp_cycle_delta(0.5, -10.0, 100.0, &segments);
+        assert!((rewound + 80.0 / 3.0).abs() < 1e-3, "{rewound}");
+    }
+
+    #[test]
+    fn sun_reaches_the_altitude_at_its_crossings() {
+        let skies = [
+            SkyCenter::default(),
+            SkyCenter {
+                latitude_degrees: 45.0,
+                year_fraction: 0.25,
+                ..default()
+            },
+            // Polar day, the sun dips to -6.5° at midnight
+            SkyCenter {
+                latitude_degrees: 60.0,
+                year_fraction: 0.25,
+                ..default()
+            },
+            SkyCenter {
+                latitude_degrees: -35.0,
+                year_fraction: 0.6,
+                orbital_eccentricity: 0.1,
+                equation_of_time: true,
+                ..default()
+            },
+        ];
+        for sky_center in &skies {
+            for altitude_deg in [-6.0, 0.0, 20.0] {
+                let times = sky_center.times_at_altitude(altitude_deg);
+                assert_eq!(times.len(), 2, "{altitude_deg}° at {sky_center:?}");
+                for (time, rising) in times.into_iter().zip([true, false]) {
+                    let altitude_at = |hour_fraction: f32| {
+                        sky_center
+                            .sun_state_at(hour_fraction.rem_euclid(1.0))
+                            .altitude_deg
+                    };
+                    assert!(
+                        (altitude_at(time) - altitude_deg).abs() < 0.05,
+                        "{altitude_deg}° at {time}: {}",
+                        altitude_at(time)
+                    );
+                    assert_eq!(altitude_at(time + 0.01) > altitude_at(time - 0.01), rising);
+                }
+            }
+        }
+
+        // Out of reach: above the noon sun, below the midnight sun
+        assert!(skies[1].times_at_altitude(75.0).is_empty());
+        assert!(skies[2].times_at_altitude(-18.0).is_empty());
+    }
+
+    #[test]
+    fn hours_at_altitude_on_a_24_hour_clock() {
+        // At the equator on the equinox, the sun climbs 15° per hour from 6:00
+        let sky_center = SkyCenter::default();
+        for (altitude_deg, expected) in [(0.0, [6.0, 18.0]), (30.0, [8.0, 16.0])] {
+            let hours = sky_center.hours_at_altitude(altitude_deg);
+            assert_eq!(hours.len(), 2);
+            for (hour, expected) in hours.iter().zip(expected) {
+                assert!((hour - expected).abs() < 1e-3, "{altitude_deg}° at {hour}h");
+            }
+        }
+
+        let sky_center = SkyCenter {
+            latitude_degrees: 50.0,
+            year_fraction: 0.3,
+            equation_of_time: true,
+            ..default()
+        };
+        let hours = sky_center.hours_at_altitude(6.0);
+        let times = sky_center.times_at_altitude(6.0);
+        assert_eq!(hours.len(), times.len());
+        for (hour, time) in hours.iter().zip(&times) {
+            assert!((hour - time * 24.0).abs() < 1e-4);
         }
     }
 }
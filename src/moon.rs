@@ -0,0 +1,49 @@
+// Ready-made moon light: a dim, blue-tinted directional light driven by a `MoonCenter`,
+// which only casts shadows while the moon is up.
+
+use bevy::prelude::*;
+
+use crate::MoonCenter;
+
+/// Full moon illuminance of a default `MoonCenter`, as used by [`spawn_moon`] (lux). Real
+/// full moon nights are around 0.1 to 0.3 lux.
+pub const MOON_FULL_ILLUMINANCE: f32 = 0.25;
+
+/// Cool moonlight color, the eye sees dim light as blue-ish.
+pub const MOON_LIGHT_COLOR: Color = Color::srgb(0.62, 0.72, 1.0);
+
+/// Moon light settings, on the moon `DirectionalLight`.
+#[derive(Component, Reflect, Debug, Clone, Copy)]
+#[reflect(Component, Default, Debug)]
+pub struct MoonLight {
+    /// Cast shadows while the moon is above the horizon. Below it, shadows are always off.
+    pub cast_shadows: bool,
+}
+
+impl Default for MoonLight {
+    fn default() -> Self {
+        Self { cast_shadows: true }
+    }
+}
+
+/// Spawns a moon light and attaches it to the lunar orbit of `sky` (a `SkyCenter` entity),
+/// inserting a default `MoonCenter`. Returns the moon light entity.
+pub fn spawn_moon(commands: &mut Commands, sky: Entity) -> Entity {
+    let moon = commands
+        .spawn((
+            Name::new("Moon"),
+            MoonLight::default(),
+            DirectionalLight {
+                color: MOON_LIGHT_COLOR,
+                // Driven by the moon phase and altitude
+                illuminance: 0.0,
+                shadows_enabled: false,
+                ..default()
+            },
+        ))
+        .id();
+    commands
+        .entity(sky)
+        .insert(MoonCenter { moon, ..default() });
+    moon
+}
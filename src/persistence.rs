@@ -0,0 +1,345 @@
+// Versioned serde representations of the sky configs, for games that keep sky state in
+// save files: old saves deserialize into their version and are migrated to the latest.
+//
+// The sun entity isn't saved (entity ids don't survive a save), pass it when restoring.
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    HemispherePreference, NoonSunSide, SeasonHalf, SkyCenter, TimedSkyConfig,
+    time_of_day::PhaseTimeScales,
+};
+
+/// `SkyCenter` as saved by 0.2: position, season and clock only.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SkyCenterV1 {
+    pub latitude_degrees: f32,
+    pub planet_tilt_degrees: f32,
+    pub year_fraction: f32,
+    pub cycle_duration_secs: f32,
+    pub current_cycle_time: f32,
+}
+
+/// `SkyCenter` with the clock controls, time warp, seasons progression and orbit.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SkyCenterV2 {
+    pub latitude_degrees: f32,
+    pub planet_tilt_degrees: f32,
+    pub year_fraction: f32,
+    pub cycle_duration_secs: f32,
+    pub current_cycle_time: f32,
+    pub paused: bool,
+    pub time_scale: f32,
+    pub phase_time_scales: PhaseTimeScales,
+    pub orbital_eccentricity: f32,
+    pub perihelion_year_fraction: f32,
+    pub equation_of_time: bool,
+    #[serde(default)]
+    pub day_index: u64,
+    pub days_per_year: Option<f32>,
+    #[serde(default = "default_sun_angular_diameter_deg")]
+    pub sun_angular_diameter_deg: f32,
+}
+
+fn default_sun_angular_diameter_deg() -> f32 {
+    SkyCenter::default().sun_angular_diameter_deg
+}
+
+/// Any saved version of a `SkyCenter`, tagged with its version.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "version")]
+pub enum VersionedSkyCenter {
+    V1(SkyCenterV1),
+    V2(SkyCenterV2),
+}
+
+impl VersionedSkyCenter {
+    /// Upgrades to the latest version, filling new fields with their defaults.
+    pub fn migrate(self) -> SkyCenterV2 {
+        match self {
+            VersionedSkyCenter::V1(v1) => {
+                let defaults = SkyCenter::default();
+                SkyCenterV2 {
+                    latitude_degrees: v1.latitude_degrees,
+                    planet_tilt_degrees: v1.planet_tilt_degrees,
+                    year_fraction: v1.year_fraction,
+                    cycle_duration_secs: v1.cycle_duration_secs,
+                    current_cycle_time: v1.current_cycle_time,
+                    paused: defaults.paused,
+                    time_scale: defaults.time_scale,
+                    phase_time_scales: defaults.phase_time_scales,
+                    orbital_eccentricity: defaults.orbital_eccentricity,
+                    perihelion_year_fraction: defaults.perihelion_year_fraction,
+                    equation_of_time: defaults.equation_of_time,
+                    day_index: defaults.day_index,
+                    days_per_year: defaults.days_per_year,
+                    sun_angular_diameter_deg: defaults.sun_angular_diameter_deg,
+                }
+            }
+            VersionedSkyCenter::V2(v2) => v2,
+        }
+    }
+
+    /// Migrates and builds a `SkyCenter` driving `sun`.
+    pub fn into_sky_center(self, sun: Entity) -> SkyCenter {
+        let v2 = self.migrate();
+        SkyCenter {
+            latitude_degrees: v2.latitude_degrees,
+            planet_tilt_degrees: v2.planet_tilt_degrees,
+            year_fraction: v2.year_fraction,
+            cycle_duration_secs: v2.cycle_duration_secs,
+            sun,
+            current_cycle_time: v2.current_cycle_time,
+            paused: v2.paused,
+            time_scale: v2.time_scale,
+            phase_time_scales: v2.phase_time_scales,
+            orbital_eccentricity: v2.orbital_eccentricity,
+            perihelion_year_fraction: v2.perihelion_year_fraction,
+            equation_of_time: v2.equation_of_time,
+            day_index: v2.day_index,
+            days_per_year: v2.days_per_year,
+            sun_angular_diameter_deg: v2.sun_angular_diameter_deg,
+        }
+    }
+}
+
+impl From<&SkyCenter> for VersionedSkyCenter {
+    /// Saves with the latest version.
+    fn from(sky_center: &SkyCenter) -> Self {
+        VersionedSkyCenter::V2(SkyCenterV2 {
+            latitude_degrees: sky_center.latitude_degrees,
+            planet_tilt_degrees: sky_center.planet_tilt_degrees,
+            year_fraction: sky_center.year_fraction,
+            cycle_duration_secs: sky_center.cycle_duration_secs,
+            current_cycle_time: sky_center.current_cycle_time,
+            paused: sky_center.paused,
+            time_scale: sky_center.time_scale,
+            phase_time_scales: sky_center.phase_time_scales,
+            orbital_eccentricity: sky_center.orbital_eccentricity,
+            perihelion_year_fraction: sky_center.perihelion_year_fraction,
+            equation_of_time: sky_center.equation_of_time,
+            day_index: sky_center.day_index,
+            days_per_year: sky_center.days_per_year,
+            sun_angular_diameter_deg: sky_center.sun_angular_diameter_deg,
+        })
+    }
+}
+
+/// `TimedSkyConfig` as saved by 0.2, before the hemisphere preference.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct TimedSkyConfigV1 {
+    pub planet_tilt_degrees: f32,
+    pub day_duration_secs: f32,
+    pub night_duration_secs: f32,
+    pub max_sun_height_deg: f32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct TimedSkyConfigV2 {
+    pub planet_tilt_degrees: f32,
+    pub day_duration_secs: f32,
+    pub night_duration_secs: f32,
+    pub max_sun_height_deg: f32,
+    pub hemisphere: HemispherePreference,
+    #[serde(default)]
+    pub noon_sun: NoonSunSide,
+    #[serde(default)]
+    pub season_half: SeasonHalf,
+}
+
+/// Any saved version of a `TimedSkyConfig`, tagged with its version.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "version")]
+pub enum VersionedTimedSkyConfig {
+    V1(TimedSkyConfigV1),
+    V2(TimedSkyConfigV2),
+}
+
+impl VersionedTimedSkyConfig {
+    /// Upgrades to the latest version, filling new fields with their defaults.
+    pub fn migrate(self) -> TimedSkyConfigV2 {
+        match self {
+            VersionedTimedSkyConfig::V1(v1) => TimedSkyConfigV2 {
+                planet_tilt_degrees: v1.planet_tilt_degrees,
+                day_duration_secs: v1.day_duration_secs,
+                night_duration_secs: v1.night_duration_secs,
+                max_sun_height_deg: v1.max_sun_height_deg,
+                hemisphere: HemispherePreference::default(),
+                noon_sun: NoonSunSide::default(),
+                season_half: SeasonHalf::default(),
+            },
+            VersionedTimedSkyConfig::V2(v2) => v2,
+        }
+    }
+
+    /// Migrates and builds a `TimedSkyConfig` driving `sun_entity`.
+    pub fn into_timed_config(self, sun_entity: Entity) -> TimedSkyConfig {
+        let v2 = self.migrate();
+        TimedSkyConfig {
+            planet_tilt_degrees: v2.planet_tilt_degrees,
+            day_duration_secs: v2.day_duration_secs,
+            night_duration_secs: v2.night_duration_secs,
+            max_sun_height_deg: v2.max_sun_height_deg,
+            sun_entity,
+            hemisphere: v2.hemisphere,
+            noon_sun: v2.noon_sun,
+            season_half: v2.season_half,
+        }
+    }
+}
+
+impl From<&TimedSkyConfig> for VersionedTimedSkyConfig {
+    /// Saves with the latest version.
+    fn from(config: &TimedSkyConfig) -> Self {
+        VersionedTimedSkyConfig::V2(TimedSkyConfigV2 {
+            planet_tilt_degrees: config.planet_tilt_degrees,
+            day_duration_secs: config.day_duration_secs,
+            night_duration_secs: config.night_duration_secs,
+            max_sun_height_deg: config.max_sun_height_deg,
+            hemisphere: config.hemisphere,
+            noon_sun: config.noon_sun,
+            season_half: config.season_half,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::scene::ron;
+
+    use super::*;
+
+    #[test]
+    fn v1_sky_center_migrates_with_defaults() {
+        let saved = r#"(
+            version: "V1",
+            latitude_degrees: 40.0,
+            planet_tilt_degrees: 23.5,
+            year_fraction: 0.25,
+            cycle_duration_secs: 600.0,
+            current_cycle_time: 120.0,
+        )"#;
+        let versioned: VersionedSkyCenter = ron::from_str(saved).unwrap();
+        assert!(matches!(versioned, VersionedSkyCenter::V1(_)));
+
+        let sun = World::new().spawn_empty().id();
+        let sky_center = versioned.into_sky_center(sun);
+        let defaults = SkyCenter::default();
+        assert_eq!(sky_center.latitude_degrees, 40.0);
+        assert_eq!(sky_center.year_fraction, 0.25);
+        assert_eq!(sky_center.current_cycle_time, 120.0);
+        assert_eq!(sky_center.sun, sun);
+        assert_eq!(sky_center.time_scale, defaults.time_scale);
+        assert_eq!(sky_center.phase_time_scales, defaults.phase_time_scales);
+        assert_eq!(
+            sky_center.orbital_eccentricity,
+            defaults.orbital_eccentricity
+        );
+        assert_eq!(sky_center.day_index, defaults.day_index);
+        assert_eq!(sky_center.days_per_year, defaults.days_per_year);
+        assert_eq!(
+            sky_center.sun_angular_diameter_deg,
+            defaults.sun_angular_diameter_deg
+        );
+    }
+
+    #[test]
+    fn early_v2_sky_center_fills_later_fields() {
+        // Saved before `day_index` and `sun_angular_diameter_deg` were added
+        let saved = r#"(
+            version: "V2",
+            latitude_degrees: -30.0,
+            planet_tilt_degrees: 23.5,
+            year_fraction: 0.6,
+            cycle_duration_secs: 600.0,
+            current_cycle_time: 0.0,
+            paused: true,
+            time_scale: 2.0,
+            phase_time_scales: (dawn: 1.0, day: 1.0, dusk: 1.0, night: 4.0),
+            orbital_eccentricity: 0.0167,
+            perihelion_year_fraction: 0.79,
+            equation_of_time: false,
+            days_per_year: Some(10.0),
+        )"#;
+        let migrated = ron::from_str::<VersionedSkyCenter>(saved)
+            .unwrap()
+            .migrate();
+        assert_eq!(migrated.day_index, 0);
+        assert_eq!(migrated.days_per_year, Some(10.0));
+        assert_eq!(migrated.phase_time_scales.night, 4.0);
+        assert_eq!(
+            migrated.sun_angular_diameter_deg,
+            SkyCenter::default().sun_angular_diameter_deg
+        );
+    }
+
+    #[test]
+    fn sky_center_round_trips() {
+        let sky_center = SkyCenter {
+            latitude_degrees: 52.5,
+            year_fraction: 0.8,
+            cycle_duration_secs: 900.0,
+            current_cycle_time: 333.0,
+            paused: true,
+            time_scale: 3.0,
+            orbital_eccentricity: 0.2,
+            equation_of_time: true,
+            day_index: 41,
+            days_per_year: Some(24.0),
+            ..default()
+        };
+        let saved = ron::to_string(&VersionedSkyCenter::from(&sky_center)).unwrap();
+        let loaded: VersionedSkyCenter = ron::from_str(&saved).unwrap();
+        assert_eq!(loaded, VersionedSkyCenter::from(&sky_center));
+
+        let restored = loaded.into_sky_center(Entity::PLACEHOLDER);
+        assert_eq!(restored.latitude_degrees, 52.5);
+        assert_eq!(restored.current_cycle_time, 333.0);
+        assert_eq!(restored.orbital_eccentricity, 0.2);
+        assert_eq!(restored.day_index, 41);
+        assert_eq!(restored.days_per_year, Some(24.0));
+        assert!(restored.paused && restored.equation_of_time);
+    }
+
+    #[test]
+    fn v1_timed_config_migrates_with_defaults() {
+        let saved = r#"(
+            version: "V1",
+            planet_tilt_degrees: 23.5,
+            day_duration_secs: 400.0,
+            night_duration_secs: 200.0,
+            max_sun_height_deg: 60.0,
+        )"#;
+        let versioned: VersionedTimedSkyConfig = ron::from_str(saved).unwrap();
+        let timed_config = versioned.into_timed_config(Entity::PLACEHOLDER);
+        let defaults = TimedSkyConfig::default();
+        assert_eq!(timed_config.day_duration_secs, 400.0);
+        assert_eq!(timed_config.max_sun_height_deg, 60.0);
+        assert_eq!(timed_config.hemisphere, defaults.hemisphere);
+        assert_eq!(timed_config.noon_sun, defaults.noon_sun);
+        assert_eq!(timed_config.season_half, defaults.season_half);
+    }
+
+    #[test]
+    fn timed_config_round_trips() {
+        let timed_config = TimedSkyConfig {
+            day_duration_secs: 500.0,
+            night_duration_secs: 100.0,
+            max_sun_height_deg: 75.0,
+            hemisphere: HemispherePreference::South,
+            noon_sun: NoonSunSide::Poleward,
+            season_half: SeasonHalf::FromWinterSolstice,
+            ..default()
+        };
+        let sun = World::new().spawn_empty().id();
+        let restored = VersionedTimedSkyConfig::from(&timed_config).into_timed_config(sun);
+        assert_eq!(restored.day_duration_secs, 500.0);
+        assert_eq!(restored.night_duration_secs, 100.0);
+        assert_eq!(restored.max_sun_height_deg, 75.0);
+        assert_eq!(restored.hemisphere, HemispherePreference::South);
+        assert_eq!(restored.noon_sun, NoonSunSide::Poleward);
+        assert_eq!(restored.season_half, SeasonHalf::FromWinterSolstice);
+        assert_eq!(restored.sun_entity, sun);
+    }
+}
@@ -0,0 +1,93 @@
+// Gameplay data per time of day phase (spawn rates, ambient tracks, AI aggression): a table
+// on the sky picks the entry of the current phase, so games don't each write the same
+// phase-switch system.
+
+use std::marker::PhantomData;
+
+use bevy::{
+    prelude::*,
+    reflect::{FromReflect, Reflectable},
+};
+
+use crate::{SunMoveSet, sky_observer::update_sky_observers, time_of_day::TimeOfDayPhase};
+
+/// Keeps the [`ActiveModifier<T>`] of every [`PhaseModifiers<T>`] entity up to date. Add
+/// one per modifier type, e.g. `PhaseModifiersPlugin::<SpawnRate>::default()`.
+pub struct PhaseModifiersPlugin<T>(PhantomData<T>);
+
+impl<T> Default for PhaseModifiersPlugin<T> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<T: Reflectable + FromReflect + Clone> Plugin for PhaseModifiersPlugin<T> {
+    fn build(&self, app: &mut App) {
+        app.register_type::<PhaseModifiers<T>>()
+            .register_type::<ActiveModifier<T>>();
+        // Phases are updated with the sky (and after it for observers), read them afterwards.
+        app.add_systems(
+            PostUpdate,
+            update_active_modifiers::<T>
+                .after(SunMoveSet)
+                .after(update_sky_observers),
+        );
+    }
+}
+
+/// `T` for each `TimeOfDayPhase`. Add it to a `SkyCenter` (or a `SkyObserver`, for its
+/// local phase) and read the entry of the current phase from its [`ActiveModifier<T>`].
+#[derive(Component, Reflect, Debug, Default, Clone, PartialEq)]
+#[reflect(Component)]
+#[require(TimeOfDayPhase)]
+pub struct PhaseModifiers<T: Reflectable + FromReflect + Clone> {
+    pub dawn: T,
+    pub day: T,
+    pub dusk: T,
+    pub night: T,
+}
+
+impl<T: Reflectable + FromReflect + Clone> PhaseModifiers<T> {
+    /// The same value for every phase.
+    pub fn uniform(value: T) -> Self {
+        Self {
+            dawn: value.clone(),
+            day: value.clone(),
+            dusk: value.clone(),
+            night: value,
+        }
+    }
+
+    pub fn for_phase(&self, phase: TimeOfDayPhase) -> &T {
+        match phase {
+            TimeOfDayPhase::Dawn => &self.dawn,
+            TimeOfDayPhase::Day => &self.day,
+            TimeOfDayPhase::Dusk => &self.dusk,
+            TimeOfDayPhase::Night => &self.night,
+        }
+    }
+}
+
+/// The entry of [`PhaseModifiers<T>`] for the current phase, inserted and swapped by the
+/// [`PhaseModifiersPlugin<T>`]. Use `Changed<ActiveModifier<T>>` to react to a switch.
+#[derive(Component, Reflect, Debug, Clone, PartialEq, Deref)]
+#[reflect(Component)]
+pub struct ActiveModifier<T: Reflectable + FromReflect + Clone>(pub T);
+
+fn update_active_modifiers<T: Reflectable + FromReflect + Clone>(
+    mut commands: Commands,
+    q_modifiers: Query<
+        (Entity, &PhaseModifiers<T>, &TimeOfDayPhase),
+        Or<(
+            Changed<PhaseModifiers<T>>,
+            Changed<TimeOfDayPhase>,
+            Without<ActiveModifier<T>>,
+        )>,
+    >,
+) {
+    for (entity, modifiers, phase) in q_modifiers.iter() {
+        commands
+            .entity(entity)
+            .insert(ActiveModifier(modifiers.for_phase(*phase).clone()));
+    }
+}
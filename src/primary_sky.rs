@@ -0,0 +1,39 @@
+// Picks the sky driving the world-wide outputs of this crate (ambient light, ground shadows,
+// UI lighting, the game clock and calendar, ...) in worlds with several `SkyCenter`s, e.g.
+// split screen or a sky per planet.
+
+use bevy::{
+    ecs::{query::QuerySingleError, system::SystemParam},
+    prelude::*,
+};
+
+use crate::SkyCenter;
+
+/// Marks the sky followed by the resources and global settings written by this crate's
+/// plugins (`GlobalAmbientLight`, `GroundShadowParams`, `UiLightingMode`, `SkyStats`,
+/// `GameClock`, `GameCalendar`, ...). Needed only with several `SkyCenter`s: without it they
+/// follow the only sky, and stop while there are several. Per-sky components like
+/// `SunState` are updated for every sky either way.
+#[derive(Component, Reflect, Debug, Default, Clone, Copy)]
+#[reflect(Component, Default, Debug)]
+#[require(SkyCenter)]
+pub struct PrimarySky;
+
+/// Finds the primary sky, see [`PrimarySky`].
+#[derive(SystemParam)]
+pub struct PrimarySkyQuery<'w, 's> {
+    q_primary: Query<'w, 's, Entity, With<PrimarySky>>,
+    q_sky_center: Query<'w, 's, Entity, With<SkyCenter>>,
+}
+
+impl PrimarySkyQuery<'_, '_> {
+    /// The sky marked [`PrimarySky`], or the only `SkyCenter`. `None` without a sky, with
+    /// several skies and none marked, or with several marked.
+    pub fn entity(&self) -> Option<Entity> {
+        match self.q_primary.single() {
+            Ok(sky) => Some(sky),
+            Err(QuerySingleError::NoEntities(_)) => self.q_sky_center.single().ok(),
+            Err(QuerySingleError::MultipleEntities(_)) => None,
+        }
+    }
+}
@@ -1,7 +1,12 @@
 // Its definetely not the best way to do this, better to use a texture or some particle system
 // So this is just for testing purposes
 
-use bevy::{light::NotShadowCaster, prelude::*};
+use bevy::{
+    camera::{Exposure, visibility::RenderLayers},
+    light::NotShadowCaster,
+    platform::collections::{HashMap, HashSet},
+    prelude::*,
+};
 use rand::Rng;
 
 use crate::SkyCenter;
@@ -13,47 +18,149 @@ impl Plugin for RandomStarsPlugin {
         // if !app.is_plugin_added::<AutoExposurePlugin>() {
         //     app.add_plugins(AutoExposurePlugin);
         // }
-        app.add_systems(Startup, setup_star_spawner);
-        app.add_systems(Update, on_change_spawner);
+        app.init_resource::<StarSpawnerCache>();
+        app.add_systems(
+            Update,
+            (
+                despawn_removed_spawner_stars,
+                fit_star_dome_to_camera,
+                on_change_spawner,
+            )
+                .chain(),
+        );
         app.add_systems(Update, update_star_illuminance);
+        app.add_systems(
+            Update,
+            (spawn_star_layer_cameras, sync_star_layer_cameras).chain(),
+        );
     }
 }
 
-#[derive(Component)]
+#[derive(Component, Reflect, Clone)]
+#[reflect(Component, Default)]
 pub struct StarSpawner {
     pub star_count: u32,
     pub spawn_radius: f32,
+    /// Star brightness kept during the day (0.0 = invisible, 1.0 = as bright as at night).
+    /// Useful for stylized skies or a "morning star" that stays visible after sunrise.
+    pub day_star_visibility: f32,
+    /// Fit `spawn_radius` to this fraction of the active camera's far plane (minus the
+    /// camera distance to the dome), so the stars are never clipped. `None` keeps the
+    /// `spawn_radius` as set and only warns when it doesn't fit.
+    pub fit_to_camera_far: Option<f32>,
+    /// Render the stars on this render layer only, drawn by the background camera of a
+    /// `StarBackgroundCamera`, so they always appear behind world geometry whatever
+    /// `spawn_radius` is.
+    pub background_layer: Option<usize>,
+    /// Scale the star brightness with the active camera's `Exposure`, so stars tuned for
+    /// the default exposure stay visible with e.g. `Exposure::SUNLIGHT`. Auto exposure
+    /// adapts on the GPU and needs no compensation. Off by default.
+    pub exposure_compensation: bool,
 }
 
-#[derive(Component)]
+impl Default for StarSpawner {
+    fn default() -> Self {
+        Self {
+            star_count: 1000,
+            spawn_radius: 5000.0,
+            day_star_visibility: 0.0,
+            fit_to_camera_far: None,
+            background_layer: None,
+            exposure_compensation: false,
+        }
+    }
+}
+
+#[derive(Component, Reflect, Default)]
+#[reflect(Component, Default)]
 pub struct Star;
 
-#[derive(Resource)]
+/// Multiplier of the star brightness of a `StarSpawner`, on the same entity. Changing it
+/// doesn't respawn the stars, unlike changing the spawner.
+#[derive(Component, Reflect, Debug, Clone, Copy, PartialEq)]
+#[reflect(Component, Default, Debug)]
+pub struct StarBrightness(pub f32);
+
+impl Default for StarBrightness {
+    fn default() -> Self {
+        Self(1.0)
+    }
+}
+
+/// Add to the main 3D camera to draw the stars of `StarSpawner::background_layer` behind
+/// everything: a child camera renders `layer` first with the same projection and an
+/// unlimited far plane, and the main camera is set to draw over it without clearing
+/// (`ClearColorConfig::None`). Skyboxes on the main camera still cover the stars.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct StarBackgroundCamera {
+    pub layer: usize,
+}
+
+/// Far plane of the background camera, far beyond any sensible star radius.
+const STAR_LAYER_FAR: f32 = 1.0e9;
+
+/// Background camera spawned for a `StarBackgroundCamera`.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct StarLayerCamera;
+
+/// Star meshes and materials of a world, created lazily by the spawners that need them
+/// (worlds without render assets never do). Meshes are shared per size class, each
+/// spawner gets its own material so star fields with different looks don't alias.
+#[derive(Resource, Default)]
 pub struct StarSpawnerCache {
-    pub mesh: Handle<Mesh>,
-    pub material: Handle<StandardMaterial>,
+    meshes: HashMap<i32, Handle<Mesh>>,
+    materials: HashMap<Entity, Handle<StandardMaterial>>,
 }
 
-fn setup_star_spawner(
-    mut commands: Commands,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<StandardMaterial>>,
-) {
-    let mesh = meshes.add(Cuboid::new(1.0, 1.0, 1.0));
-    let material = materials.add(StandardMaterial {
-        base_color: Color::srgba(0.0, 0.0, 0.0, 1.0),
-        alpha_mode: AlphaMode::Add,
-        ..default()
-    });
-    commands.insert_resource(StarSpawnerCache { mesh, material });
+impl StarSpawnerCache {
+    /// Material of the stars of `spawner`, if they were spawned.
+    pub fn material(&self, spawner: Entity) -> Option<&Handle<StandardMaterial>> {
+        self.materials.get(&spawner)
+    }
+
+    /// Size class of a star: power of two closest to its size.
+    fn size_class(star_size: f32) -> i32 {
+        star_size.max(f32::MIN_POSITIVE).log2().round() as i32
+    }
+
+    fn mesh_or_insert(&mut self, size_class: i32, meshes: &mut Assets<Mesh>) -> Handle<Mesh> {
+        self.meshes
+            .entry(size_class)
+            .or_insert_with(|| meshes.add(Cuboid::from_size(Vec3::splat(2f32.powi(size_class)))))
+            .clone()
+    }
+
+    fn material_or_insert(
+        &mut self,
+        spawner: Entity,
+        materials: &mut Assets<StandardMaterial>,
+    ) -> Handle<StandardMaterial> {
+        self.materials
+            .entry(spawner)
+            .or_insert_with(|| {
+                materials.add(StandardMaterial {
+                    base_color: Color::srgba(0.0, 0.0, 0.0, 1.0),
+                    alpha_mode: AlphaMode::Add,
+                    ..default()
+                })
+            })
+            .clone()
+    }
 }
 
 fn on_change_spawner(
     mut commands: Commands,
     mut q_star_spawner: Query<(Entity, &mut StarSpawner, Option<&Children>), Changed<StarSpawner>>,
     q_star: Query<Entity, With<Star>>,
-    star_spawner_cache: Res<StarSpawnerCache>,
+    mut star_spawner_cache: ResMut<StarSpawnerCache>,
+    meshes: Option<ResMut<Assets<Mesh>>>,
+    materials: Option<ResMut<Assets<StandardMaterial>>>,
 ) {
+    // No render assets in this world, there is nothing to draw stars with
+    let (Some(mut meshes), Some(mut materials)) = (meshes, materials) else {
+        return;
+    };
+
     for (entity, star_spawner, children) in q_star_spawner.iter_mut() {
         if let Some(children) = children {
             for star in children.iter() {
@@ -63,6 +170,13 @@ fn on_change_spawner(
             }
         }
 
+        let star_size = star_spawner.spawn_radius / 500.0;
+        let size_class = StarSpawnerCache::size_class(star_size);
+        let mesh = star_spawner_cache.mesh_or_insert(size_class, &mut meshes);
+        let material = star_spawner_cache.material_or_insert(entity, &mut materials);
+        // The class mesh is within a factor of sqrt(2) of the star size, scale the rest
+        let scale = star_size / 2f32.powi(size_class);
+
         let mut rng = rand::rng();
         for _ in 0..star_spawner.star_count {
             let phi = rng.random_range(0.0..2.0 * std::f32::consts::PI);
@@ -74,46 +188,205 @@ fn on_change_spawner(
             let id = commands
                 .spawn((
                     Star,
-                    Transform::from_xyz(x, y, z)
-                        .with_scale(Vec3::ONE * star_spawner.spawn_radius / 500.0),
-                    Mesh3d(star_spawner_cache.mesh.clone()),
-                    MeshMaterial3d(star_spawner_cache.material.clone()),
+                    Transform::from_xyz(x, y, z).with_scale(Vec3::splat(scale)),
+                    Mesh3d(mesh.clone()),
+                    MeshMaterial3d(material.clone()),
                     NotShadowCaster,
                 ))
                 .id();
+            if let Some(layer) = star_spawner.background_layer {
+                commands.entity(id).insert(RenderLayers::layer(layer));
+            }
 
             commands.entity(entity).add_child(id);
         }
     }
 }
 
+/// Far plane distance of a camera, `None` for custom projections.
+fn projection_far(projection: &Projection) -> Option<f32> {
+    match projection {
+        Projection::Perspective(perspective) => Some(perspective.far),
+        Projection::Orthographic(orthographic) => Some(orthographic.far),
+        _ => None,
+    }
+}
+
+fn fit_star_dome_to_camera(
+    mut q_star_spawner: Query<(
+        Entity,
+        &mut StarSpawner,
+        &GlobalTransform,
+        Option<&Children>,
+    )>,
+    mut q_star: Query<&mut Transform, With<Star>>,
+    q_camera: Query<(&Camera, &Projection, &GlobalTransform), Without<StarLayerCamera>>,
+    mut warned: Local<HashSet<Entity>>,
+) {
+    let Some((far, camera_position)) = q_camera
+        .iter()
+        .filter(|(camera, ..)| camera.is_active)
+        .find_map(|(_, projection, transform)| {
+            projection_far(projection).map(|far| (far, transform.translation()))
+        })
+    else {
+        return;
+    };
+
+    for (entity, mut star_spawner, spawner_transform, children) in q_star_spawner.iter_mut() {
+        // The background camera has no far limit
+        if star_spawner.background_layer.is_some() {
+            continue;
+        }
+        let camera_distance = camera_position.distance(spawner_transform.translation());
+        // Farthest star corner seen from the camera
+        let reach = |radius: f32| camera_distance + radius * 1.01;
+
+        match star_spawner.fit_to_camera_far {
+            Some(fraction) => {
+                let radius = ((far - camera_distance) * fraction.clamp(0.0, 1.0) / 1.01).max(1.0);
+                // Ignore small changes, e.g. from a camera moving around the dome
+                if (radius - star_spawner.spawn_radius).abs() > star_spawner.spawn_radius * 0.01 {
+                    // Scale the spawned stars instead of respawning them
+                    let ratio = radius / star_spawner.spawn_radius;
+                    for star in children.into_iter().flat_map(|children| children.iter()) {
+                        if let Ok(mut star_transform) = q_star.get_mut(star) {
+                            star_transform.translation *= ratio;
+                            star_transform.scale *= ratio;
+                        }
+                    }
+                    star_spawner.bypass_change_detection().spawn_radius = radius;
+                }
+            }
+            None if reach(star_spawner.spawn_radius) > far => {
+                if warned.insert(entity) {
+                    warn!(
+                        "Stars of {} reach {:.0} units from the camera, beyond its far plane at {:.0}: they will be clipped. Lower spawn_radius or set fit_to_camera_far.",
+                        entity,
+                        reach(star_spawner.spawn_radius),
+                        far
+                    );
+                }
+            }
+            None => {
+                warned.remove(&entity);
+            }
+        }
+    }
+}
+
+fn spawn_star_layer_cameras(
+    mut commands: Commands,
+    mut q_camera: Query<(Entity, &StarBackgroundCamera, &mut Camera), Added<StarBackgroundCamera>>,
+) {
+    for (entity, background, mut camera) in q_camera.iter_mut() {
+        let order = camera.order - 1;
+        camera.clear_color = ClearColorConfig::None;
+        let layer_camera = commands
+            .spawn((
+                StarLayerCamera,
+                Camera3d::default(),
+                Camera { order, ..default() },
+                RenderLayers::layer(background.layer),
+            ))
+            .id();
+        commands.entity(entity).add_child(layer_camera);
+    }
+}
+
+fn sync_star_layer_cameras(
+    q_main: Query<(&Projection, Option<&Exposure>), With<StarBackgroundCamera>>,
+    mut q_layer: Query<(&ChildOf, &mut Projection, &mut Exposure), With<StarLayerCamera>>,
+) {
+    for (child_of, mut projection, mut exposure) in q_layer.iter_mut() {
+        let Ok((main_projection, main_exposure)) = q_main.get(child_of.parent()) else {
+            continue;
+        };
+        let mut synced = main_projection.clone();
+        match &mut synced {
+            Projection::Perspective(perspective) => perspective.far = STAR_LAYER_FAR,
+            Projection::Orthographic(orthographic) => orthographic.far = STAR_LAYER_FAR,
+            _ => {}
+        }
+        *projection = synced;
+        *exposure = main_exposure.copied().unwrap_or_default();
+    }
+}
+
+/// Stars are children of their spawner, so despawning the spawner entity despawns them too,
+/// but removing only the `StarSpawner` component would leave the star cloud behind.
+fn despawn_removed_spawner_stars(
+    mut commands: Commands,
+    mut removed: RemovedComponents<StarSpawner>,
+    mut star_spawner_cache: ResMut<StarSpawnerCache>,
+    q_children: Query<&Children>,
+    q_star: Query<Entity, With<Star>>,
+) {
+    for entity in removed.read() {
+        star_spawner_cache.materials.remove(&entity);
+
+        let Ok(children) = q_children.get(entity) else {
+            continue;
+        };
+        for star in children.iter() {
+            if q_star.contains(star) {
+                commands.entity(star).despawn();
+            }
+        }
+    }
+}
+
 fn update_star_illuminance(
     cache: Res<StarSpawnerCache>,
-    q_sky_center: Query<&SkyCenter>,
+    q_sky_center: Query<(Entity, &SkyCenter, &StarSpawner, Option<&StarBrightness>)>,
     q_transforms: Query<&Transform>,
-    mut materials: ResMut<Assets<StandardMaterial>>,
+    q_camera: Query<(&Camera, &Exposure), Without<StarLayerCamera>>,
+    materials: Option<ResMut<Assets<StandardMaterial>>>,
 ) {
-    let Ok(sky_center) = q_sky_center.single() else {
+    let Some(mut materials) = materials else {
         return;
     };
 
-    let Ok(sun_transform) = q_transforms.get(sky_center.sun) else {
-        return;
-    };
+    // Each EV above the default exposure halves the rendered brightness, undo it
+    let exposure_scale = q_camera
+        .iter()
+        .find(|(camera, _)| camera.is_active)
+        .map_or(1.0, |(_, exposure)| {
+            2f32.powf(exposure.ev100 - Exposure::default().ev100)
+        });
+
+    for (entity, sky_center, star_spawner, brightness) in q_sky_center.iter() {
+        let Some(material) = cache
+            .material(entity)
+            .and_then(|material| materials.get_mut(material.id()))
+        else {
+            continue;
+        };
 
-    let mut sun_height = sun_transform.translation.y;
+        let Ok(sun_transform) = q_transforms.get(sky_center.sun) else {
+            continue;
+        };
 
-    let day_illuminance = 0.0;
-    let day_point = 0.1;
+        let mut sun_height = sun_transform.translation.y;
 
-    let night_illuminance = 1.0;
-    let night_point = -0.1;
+        let day_illuminance = star_spawner.day_star_visibility;
+        let day_point = 0.1;
 
-    sun_height = sun_height.clamp(night_point, day_point);
-    sun_height = (sun_height - night_point) / (day_point - night_point);
+        let night_illuminance = 1.0;
+        let night_point = -0.1;
 
-    let illuminance = night_illuminance + sun_height * (day_illuminance - night_illuminance);
+        sun_height = sun_height.clamp(night_point, day_point);
+        sun_height = (sun_height - night_point) / (day_point - night_point);
 
-    materials.get_mut(cache.material.id()).unwrap().emissive =
-        LinearRgba::rgb(illuminance, illuminance, illuminance);
+        let mut illuminance =
+            night_illuminance + sun_height * (day_illuminance - night_illuminance);
+        if star_spawner.exposure_compensation {
+            illuminance *= exposure_scale;
+        }
+        if let Some(brightness) = brightness {
+            illuminance *= brightness.0;
+        }
+
+        material.emissive = LinearRgba::rgb(illuminance, illuminance, illuminance);
+    }
 }
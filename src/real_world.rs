@@ -0,0 +1,237 @@
+// Real-world sky for archviz and solar studies: the sun of a `SkyCenter` follows an actual
+// UTC date and time at a geographic location, including the equation of time.
+
+use bevy::prelude::*;
+use chrono::{DateTime, TimeDelta, Timelike, Utc};
+
+use crate::{DEGREES_TO_RADIANS, SkyCenter, year_fraction_from_declination};
+
+/// Seconds in a real (mean solar) day.
+const SECONDS_PER_DAY: f32 = 86400.0;
+
+pub struct RealWorldSkyPlugin;
+
+impl Plugin for RealWorldSkyPlugin {
+    fn build(&self, app: &mut App) {
+        // Configures the SkyCenter before it moves the sun in Update
+        app.add_systems(PreUpdate, update_real_world_sky);
+    }
+}
+
+/// Add next to a `SkyCenter` to drive it from a real date and place. The `SkyCenter` is
+/// set to a 24 hour cycle and keeps its clock (pause, `time_scale`, sky commands), and
+/// `datetime` follows it, whole days and rewinds included.
+#[derive(Component, Debug, Clone)]
+#[require(SkyCenter)]
+pub struct RealWorldSky {
+    pub datetime: DateTime<Utc>,
+    /// Geographic latitude in degrees (north positive).
+    pub latitude_degrees: f32,
+    /// Geographic longitude in degrees (east positive).
+    pub longitude_degrees: f32,
+    /// Sky `day_index` and `current_cycle_time` as last written.
+    last_clock: Option<(u64, f32)>,
+}
+
+impl RealWorldSky {
+    pub fn new(datetime: DateTime<Utc>, latitude_degrees: f32, longitude_degrees: f32) -> Self {
+        Self {
+            datetime,
+            latitude_degrees,
+            longitude_degrees,
+            last_clock: None,
+        }
+    }
+
+    /// Sun position for the current `datetime` and location.
+    pub fn sun_position(&self) -> RealWorldSun {
+        real_world_sun(self.datetime, self.longitude_degrees)
+    }
+}
+
+/// Sun position at a date, from the low precision formulas of the Astronomical Almanac
+/// (about 0.01° over 1950-2050).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RealWorldSun {
+    pub declination_deg: f32,
+    /// Local hour angle in degrees (0 at the noon meridian, positive West).
+    pub hour_angle_deg: f32,
+    /// Apparent minus mean solar time, in minutes.
+    pub equation_of_time_min: f32,
+    /// Obliquity of the ecliptic in degrees, the axial tilt.
+    pub obliquity_deg: f32,
+    /// Ecliptic longitude of the sun in degrees, 0 at the Vernal Equinox.
+    pub ecliptic_longitude_deg: f32,
+}
+
+impl RealWorldSun {
+    /// Local apparent solar time as a cycle fraction (0.0 midnight, 0.5 noon).
+    pub fn hour_fraction(&self) -> f32 {
+        (self.hour_angle_deg / 360.0 + 0.5).rem_euclid(1.0)
+    }
+
+    /// Direction towards the sun (X east, Y up, Z north) at `latitude_degrees`.
+    pub fn direction(&self, latitude_degrees: f32) -> Vec3 {
+        crate::calculate_direction_from_hour_angle(
+            self.hour_angle_deg * DEGREES_TO_RADIANS,
+            latitude_degrees * DEGREES_TO_RADIANS,
+            self.declination_deg * DEGREES_TO_RADIANS,
+        )
+    }
+}
+
+/// Computes the sun position at `datetime` for an observer at `longitude_degrees`.
+pub fn real_world_sun(datetime: DateTime<Utc>, longitude_degrees: f32) -> RealWorldSun {
+    // Days since J2000.0 (2000-01-01 12:00 UTC), in f64 to keep sub-second precision
+    let days = (datetime.timestamp() - 946_728_000) as f64 / 86400.0
+        + datetime.timestamp_subsec_nanos() as f64 / 86400.0e9;
+
+    let mean_anomaly = (357.529 + 0.985_600_28 * days).to_radians();
+    let mean_longitude = (280.459 + 0.985_647_36 * days).rem_euclid(360.0);
+    let ecliptic_longitude =
+        (mean_longitude + 1.915 * mean_anomaly.sin() + 0.020 * (2.0 * mean_anomaly).sin())
+            .rem_euclid(360.0);
+    let obliquity = 23.439 - 0.000_000_36 * days;
+
+    let (sin_lon, cos_lon) = ecliptic_longitude.to_radians().sin_cos();
+    let right_ascension = (obliquity.to_radians().cos() * sin_lon)
+        .atan2(cos_lon)
+        .to_degrees();
+    let declination = (obliquity.to_radians().sin() * sin_lon).asin().to_degrees();
+
+    // Mean minus apparent right ascension, wrapped to [-180, 180)
+    let equation_of_time_deg = (mean_longitude - right_ascension + 180.0).rem_euclid(360.0) - 180.0;
+
+    let utc_hours = datetime.num_seconds_from_midnight() as f64 / 3600.0
+        + datetime.timestamp_subsec_nanos() as f64 / 3600.0e9;
+    let solar_hours = utc_hours + longitude_degrees as f64 / 15.0 + equation_of_time_deg / 15.0;
+    let hour_angle = ((solar_hours - 12.0) * 15.0 + 180.0).rem_euclid(360.0) - 180.0;
+
+    RealWorldSun {
+        declination_deg: declination as f32,
+        hour_angle_deg: hour_angle as f32,
+        equation_of_time_min: (equation_of_time_deg * 4.0) as f32,
+        obliquity_deg: obliquity as f32,
+        ecliptic_longitude_deg: ecliptic_longitude as f32,
+    }
+}
+
+fn update_real_world_sky(mut q_sky: Query<(&mut RealWorldSky, &mut SkyCenter)>) {
+    for (mut real_world_sky, mut sky_center) in q_sky.iter_mut() {
+        // Follow the sky clock, days included, backwards for a rewinding clock
+        if let Some((last_day_index, last_cycle_time)) = real_world_sky.last_clock
+            && !real_world_sky.is_changed()
+        {
+            let delta_secs = (sky_center.day_index as f64 - last_day_index as f64)
+                * sky_center.cycle_duration_secs as f64
+                + (sky_center.current_cycle_time - last_cycle_time) as f64;
+            if delta_secs != 0.0 {
+                let real_world_sky = real_world_sky.bypass_change_detection();
+                real_world_sky.datetime += TimeDelta::microseconds((delta_secs * 1.0e6) as i64);
+            }
+        }
+
+        let sun = real_world_sky.sun_position();
+        let tilt_degrees = sun.obliquity_deg;
+        let year_fraction = year_fraction_from_declination(
+            sun.declination_deg * DEGREES_TO_RADIANS,
+            tilt_degrees * DEGREES_TO_RADIANS,
+            sun.ecliptic_longitude_deg / 360.0,
+        )
+        .unwrap_or(sun.ecliptic_longitude_deg / 360.0);
+
+        let sky_center = sky_center.as_mut();
+        sky_center.latitude_degrees = real_world_sky.latitude_degrees;
+        sky_center.planet_tilt_degrees = tilt_degrees;
+        // The real declination already includes the orbit eccentricity
+        sky_center.year_fraction = year_fraction;
+        sky_center.orbital_eccentricity = 0.0;
+        sky_center.cycle_duration_secs = SECONDS_PER_DAY;
+        sky_center.current_cycle_time = sun.hour_fraction() * SECONDS_PER_DAY;
+        real_world_sky.bypass_change_detection().last_clock =
+            Some((sky_center.day_index, sky_center.current_cycle_time));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy::time::TimeUpdateStrategy;
+    use chrono::TimeZone;
+
+    use super::*;
+    use crate::SunMovePlugin;
+
+    fn utc(year: i32, month: u32, day: u32, hour: u32, min: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(year, month, day, hour, min, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn declination_at_equinox_and_solstice() {
+        // 2024 March equinox 03:06 UTC, June solstice 20:51 UTC
+        let equinox = real_world_sun(utc(2024, 3, 20, 3, 6), 0.0);
+        assert!(equinox.declination_deg.abs() < 0.05);
+        assert!(equinox.ecliptic_longitude_deg < 0.05 || equinox.ecliptic_longitude_deg > 359.95);
+        let solstice = real_world_sun(utc(2024, 6, 20, 20, 51), 0.0);
+        assert!((solstice.declination_deg - 23.44).abs() < 0.05);
+        assert!((solstice.obliquity_deg - 23.44).abs() < 0.01);
+    }
+
+    #[test]
+    fn equation_of_time_in_early_november() {
+        let sun = real_world_sun(utc(2024, 11, 3, 12, 0), 0.0);
+        assert!((sun.equation_of_time_min - 16.4).abs() < 0.2);
+    }
+
+    #[test]
+    fn solar_noon_at_greenwich() {
+        // The sun runs ahead of the clock by the equation of time
+        let noon = utc(2024, 11, 3, 12, 0);
+        let equation_of_time_min = real_world_sun(noon, 0.0).equation_of_time_min;
+        let solar_noon = noon - TimeDelta::seconds((equation_of_time_min * 60.0) as i64);
+        let sun = real_world_sun(solar_noon, 0.0);
+        assert!(sun.hour_angle_deg.abs() < 0.02);
+        assert!((sun.hour_fraction() - 0.5).abs() < 1e-4);
+        // 15 degrees east, the same moment is an hour later in the solar day
+        let east = real_world_sun(solar_noon, 15.0);
+        assert!((east.hour_angle_deg - 15.0).abs() < 0.02);
+    }
+
+    #[test]
+    fn datetime_follows_skips_and_rewinds() {
+        let mut app = App::new();
+        // Only the edits below move the sky clock
+        app.add_plugins((MinimalPlugins, SunMovePlugin::default(), RealWorldSkyPlugin))
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::ZERO));
+        let start = utc(2024, 11, 3, 15, 0);
+        let sky = app
+            .world_mut()
+            .spawn(RealWorldSky::new(start, 48.0, 0.0))
+            .id();
+        app.update();
+
+        let mut sky_center = app.world_mut().get_mut::<SkyCenter>(sky).unwrap();
+        sky_center.current_cycle_time -= 3600.0;
+        app.update();
+        let datetime = app.world().get::<RealWorldSky>(sky).unwrap().datetime;
+        assert!(
+            (datetime - utc(2024, 11, 3, 14, 0))
+                .num_milliseconds()
+                .abs()
+                < 50
+        );
+
+        let mut sky_center = app.world_mut().get_mut::<SkyCenter>(sky).unwrap();
+        sky_center.day_index += 2;
+        app.update();
+        let datetime = app.world().get::<RealWorldSky>(sky).unwrap().datetime;
+        assert!(
+            (datetime - utc(2024, 11, 5, 14, 0))
+                .num_milliseconds()
+                .abs()
+                < 50
+        );
+    }
+}
@@ -0,0 +1,249 @@
+// Scene / prefab support for sky rigs. `SkyCenter::sun` is remapped by Bevy when a
+// scene is spawned (it's marked `#[entities]`), but rigs authored by hand or merged
+// from several scenes can still end up pointing at an entity that doesn't exist,
+// and a cloned rig keeps pointing at the sun of the original. `SunRef` repairs those.
+// `sky_save_scene` extracts the skies of a world for `.scn.ron` save games. Skies
+// inserted without any sun get a default one, see `AutoSunSettings`, and skies losing
+// theirs are reported with `SunLinkBroken`.
+
+use bevy::{light::light_consts::lux, platform::collections::HashSet, prelude::*};
+
+use crate::{
+    MoonCenter, SkyCenter, SkyTimestamp, SunClock, TimedSkyConfig, floating_origin::SkyOrigin,
+};
+
+/// How to find the sun of a `SkyCenter` after it was cloned, loaded from a scene or
+/// a save game. Add next to the `SkyCenter`.
+#[derive(Component, Reflect, Debug, Clone, PartialEq, Eq)]
+#[reflect(Component, Debug)]
+pub enum SunRef {
+    /// The entity carrying the [`SkySun`] marker.
+    Marker,
+    /// The entity with this `Name`.
+    Name(String),
+}
+
+/// Marks the sun light for [`SunRef::Marker`].
+#[derive(Component, Reflect, Debug, Default, Clone, Copy)]
+#[reflect(Component, Default, Debug)]
+pub struct SkySun;
+
+/// Resolves `SunRef` when it's added or changed, or when the sun entity disappeared.
+/// Candidates in the same hierarchy as the `SkyCenter` win, so clones of a whole rig
+/// link to their own sun instead of the original's. Unresolved skies are retried every
+/// frame but only reported once, until they resolve or their `SunRef` changes.
+pub(crate) fn resolve_sun_ref(
+    mut q_sky_center: Query<(Entity, &mut SkyCenter, Ref<SunRef>)>,
+    q_parents: Query<&ChildOf>,
+    q_transforms: Query<(), With<Transform>>,
+    q_markers: Query<Entity, With<SkySun>>,
+    q_names: Query<(Entity, &Name)>,
+    mut warned: Local<HashSet<Entity>>,
+) {
+    for (entity, mut sky_center, sun_ref) in q_sky_center.iter_mut() {
+        if sun_ref.is_changed() {
+            warned.remove(&entity);
+        } else if q_transforms.contains(sky_center.sun) {
+            continue;
+        }
+
+        let candidates: Vec<Entity> = match sun_ref.as_ref() {
+            SunRef::Marker => q_markers.iter().collect(),
+            SunRef::Name(name) => q_names
+                .iter()
+                .filter(|(_, candidate)| candidate.as_str() == name)
+                .map(|(candidate, _)| candidate)
+                .collect(),
+        };
+
+        let root = q_parents.root_ancestor(entity);
+        let in_hierarchy: Vec<Entity> = candidates
+            .iter()
+            .copied()
+            .filter(|&candidate| root != entity && q_parents.root_ancestor(candidate) == root)
+            .collect();
+
+        let sun = match (in_hierarchy.as_slice(), candidates.as_slice()) {
+            ([sun], _) | ([], [sun]) => *sun,
+            ([], []) => {
+                if warned.insert(entity) {
+                    warn!("No sun matching {:?} for SkyCenter {}.", *sun_ref, entity);
+                }
+                continue;
+            }
+            _ => {
+                if warned.insert(entity) {
+                    warn!(
+                        "Several suns match {:?} for SkyCenter {}, keeping {}.",
+                        *sun_ref, entity, sky_center.sun
+                    );
+                }
+                continue;
+            }
+        };
+        warned.remove(&entity);
+
+        if sky_center.sun != sun {
+            debug!(
+                "Linked SkyCenter {} to sun {} ({:?}).",
+                entity, sun, *sun_ref
+            );
+            sky_center.sun = sun;
+        }
+    }
+}
+
+/// The sun light spawned for a `SkyCenter` inserted with `sun` left to
+/// `Entity::PLACEHOLDER` (and no [`SunRef`]), instead of a sky silently moving nothing.
+/// The light is a root entity: as a child it would turn with the sky sphere.
+#[derive(Resource, Debug, Clone, Copy)]
+pub struct AutoSunSettings {
+    pub enabled: bool,
+    pub illuminance: f32,
+    pub shadows_enabled: bool,
+}
+
+impl Default for AutoSunSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            illuminance: lux::RAW_SUNLIGHT,
+            shadows_enabled: true,
+        }
+    }
+}
+
+pub(crate) fn auto_spawn_sun(
+    add: On<Add, SkyCenter>,
+    settings: Res<AutoSunSettings>,
+    mut commands: Commands,
+    mut q_sky_center: Query<&mut SkyCenter, Without<SunRef>>,
+) {
+    if !settings.enabled {
+        return;
+    }
+    let Ok(mut sky_center) = q_sky_center.get_mut(add.entity) else {
+        return;
+    };
+    if sky_center.sun != Entity::PLACEHOLDER {
+        return;
+    }
+
+    sky_center.sun = commands
+        .spawn((
+            Name::new("Sun"),
+            DirectionalLight {
+                illuminance: settings.illuminance,
+                shadows_enabled: settings.shadows_enabled,
+                ..default()
+            },
+            Transform::default(),
+        ))
+        .id();
+    debug!(
+        "Spawned sun {} for SkyCenter {} without one.",
+        sky_center.sun, add.entity
+    );
+}
+
+/// Relinks newly spawned `SkyCenter`s whose sun doesn't exist to the single
+/// `DirectionalLight` spawned in the same hierarchy (e.g. the same scene instance).
+/// Skies with a [`SunRef`] are left to [`resolve_sun_ref`].
+pub(crate) fn resolve_scene_sun(
+    mut q_sky_center: Query<(Entity, &mut SkyCenter), (Added<SkyCenter>, Without<SunRef>)>,
+    q_parents: Query<&ChildOf>,
+    q_lights: Query<Entity, With<DirectionalLight>>,
+) {
+    for (entity, mut sky_center) in q_sky_center.iter_mut() {
+        if q_lights.contains(sky_center.sun) {
+            continue;
+        }
+
+        let root = q_parents.root_ancestor(entity);
+        if root == entity {
+            // Not part of a hierarchy, nothing to search
+            continue;
+        }
+        let mut candidates = q_lights
+            .iter()
+            .filter(|&light| q_parents.root_ancestor(light) == root);
+
+        match (candidates.next(), candidates.next()) {
+            (Some(sun), None) => {
+                debug!("Linked SkyCenter {} to sun {} of its scene.", entity, sun);
+                sky_center.sun = sun;
+            }
+            (Some(_), Some(_)) => warn!(
+                "SkyCenter {} has no valid sun and its scene holds several directional lights.",
+                entity
+            ),
+            (None, _) => warn!(
+                "SkyCenter {} has no valid sun and its scene holds no directional light.",
+                entity
+            ),
+        }
+    }
+}
+
+/// Sent once when the sun of a `SkyCenter` stops existing (despawned, or never spawned),
+/// after the `SunRef` and scene links had their chance. The sky keeps turning but moves no
+/// light until relinked: add `SunRef::Marker` to the sky and [`SkySun`] to the next sun
+/// to have it picked up automatically.
+#[derive(Message, Debug, Clone, Copy)]
+pub struct SunLinkBroken {
+    pub sky: Entity,
+    /// The missing sun entity.
+    pub sun: Entity,
+    pub at: SkyTimestamp,
+}
+
+pub(crate) fn detect_broken_sun_links<T: SunClock + Resource>(
+    q_sky_center: Query<(Entity, &SkyCenter)>,
+    q_transforms: Query<(), With<Transform>>,
+    mut broken_events: MessageWriter<SunLinkBroken>,
+    mut broken_skies: Local<HashSet<Entity>>,
+    time: Option<Res<T>>,
+) {
+    let elapsed_secs = time.map_or(0.0, |time| time.elapsed_secs());
+    for (entity, sky_center) in q_sky_center.iter() {
+        if q_transforms.contains(sky_center.sun) {
+            broken_skies.remove(&entity);
+        } else if broken_skies.insert(entity) {
+            warn!(
+                "Sun {} of SkyCenter {} doesn't exist, the sky moves no light.",
+                sky_center.sun, entity
+            );
+            broken_events.write(SunLinkBroken {
+                sky: entity,
+                sun: sky_center.sun,
+                at: sky_center.timestamp(elapsed_secs),
+            });
+        }
+    }
+    broken_skies.retain(|entity| q_sky_center.contains(*entity));
+}
+
+/// Scene with every `SkyCenter` of `world` (clock, day index, season and settings) and its
+/// timed config, moon, `SunRef`, `Name` and `Transform`, to save the time of day with a
+/// game. Write it as `.scn.ron` with `DynamicScene::serialize` and spawn it back with
+/// `DynamicSceneRoot`. The sun lights aren't included: add a `SunRef` so the loaded sky
+/// links to the sun of the running game.
+pub fn sky_save_scene(world: &World) -> DynamicScene {
+    let skies: Vec<Entity> = world
+        .try_query_filtered::<Entity, With<SkyCenter>>()
+        .map(|mut query| query.iter(world).collect())
+        .unwrap_or_default();
+
+    DynamicSceneBuilder::from_world(world)
+        .deny_all_resources()
+        .deny_all()
+        .allow_component::<SkyCenter>()
+        .allow_component::<TimedSkyConfig>()
+        .allow_component::<MoonCenter>()
+        .allow_component::<SkyOrigin>()
+        .allow_component::<SunRef>()
+        .allow_component::<Name>()
+        .allow_component::<Transform>()
+        .extract_entities(skies.into_iter())
+        .build()
+}
@@ -0,0 +1,55 @@
+// Global ambient light following the sun: bright and bluish during the day, dim dark
+// blue at night.
+
+use bevy::{light::GlobalAmbientLight, prelude::*};
+
+use crate::{SunMoveSet, SunState, daylight_factor, primary_sky::PrimarySkyQuery};
+
+pub struct SkyAmbientPlugin;
+
+impl Plugin for SkyAmbientPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SkyAmbientConfig>();
+        app.add_systems(PostUpdate, update_ambient_light.after(SunMoveSet));
+    }
+}
+
+/// Ambient light at full day and at full night, blended through twilight
+/// by the daylight of the [`PrimarySky`](crate::primary_sky::PrimarySky).
+#[derive(Resource, Debug, Clone)]
+pub struct SkyAmbientConfig {
+    pub day_color: Color,
+    pub day_brightness: f32,
+    pub night_color: Color,
+    pub night_brightness: f32,
+}
+
+impl Default for SkyAmbientConfig {
+    fn default() -> Self {
+        Self {
+            day_color: Color::srgb(0.75, 0.85, 1.0),
+            day_brightness: 400.0,
+            night_color: Color::srgb(0.1, 0.15, 0.35),
+            night_brightness: 15.0,
+        }
+    }
+}
+
+pub(crate) fn update_ambient_light(
+    config: Res<SkyAmbientConfig>,
+    mut ambient_light: ResMut<GlobalAmbientLight>,
+    primary_sky: PrimarySkyQuery,
+    q_sun_state: Query<&SunState>,
+) {
+    let Some(sun_state) = primary_sky
+        .entity()
+        .and_then(|sky| q_sun_state.get(sky).ok())
+    else {
+        return;
+    };
+
+    let daylight = daylight_factor(sun_state.direction);
+    ambient_light.color = config.night_color.mix(&config.day_color, daylight);
+    ambient_light.brightness =
+        config.night_brightness + (config.day_brightness - config.night_brightness) * daylight;
+}
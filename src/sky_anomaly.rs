@@ -0,0 +1,236 @@
+// Scripted sky anomalies: a supernova flaring up for a few nights, a colored glow over a
+// quest location. Temporary objects on the star dome, rotating with the sky like the stars,
+// with a brightness curve over their lifetime.
+
+use bevy::{
+    light::NotShadowCaster, math::curve::UnevenSampleAutoCurve, platform::collections::HashMap,
+    prelude::*,
+};
+
+use crate::{
+    DEGREES_TO_RADIANS, SkyCenter, SkyTimestamp, SunMoveSet, SunState,
+    calculate_direction_from_hour_angle,
+};
+
+pub struct SkyAnomalyPlugin;
+
+impl Plugin for SkyAnomalyPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<SkyAnomaly>();
+        app.add_message::<SkyAnomalyEnded>();
+        app.add_systems(Update, spawn_sky_anomaly_visuals);
+        // The sky clock is advanced in Update, so read it afterwards (and before the
+        // transforms are propagated).
+        app.add_systems(
+            PostUpdate,
+            update_sky_anomalies
+                .after(SunMoveSet)
+                .before(TransformSystems::Propagate),
+        );
+    }
+}
+
+/// A temporary object on the star dome. Spawn it as a child of its `SkyCenter` with
+/// [`spawn_sky_anomaly`]: it rotates with the sky, ages with the sky clock and is despawned
+/// once `lifetime_cycles` have passed.
+#[derive(Component, Reflect, Debug, Clone)]
+#[reflect(Component, Default, Debug)]
+#[require(Transform, Visibility)]
+pub struct SkyAnomaly {
+    /// Angle east of the sun's hour circle on the star dome, see `Comet::elongation_deg`.
+    pub right_ascension_deg: f32,
+    /// Angle above the celestial equator.
+    pub declination_deg: f32,
+    /// Distance from the sky center, inside the star dome.
+    pub distance: f32,
+    pub radius: f32,
+    pub color: Color,
+    pub lifetime_cycles: f32,
+    /// Brightness (emissive multiplier) by age, as a fraction of the lifetime (0.0 to 1.0).
+    pub brightness_by_age: UnevenSampleAutoCurve<f32>,
+    /// Brightness kept in full daylight (0.0 invisible, 1.0 as bright as at night).
+    pub day_visibility: f32,
+    /// Cycles since the anomaly appeared, advanced (or rewound) with the sky clock.
+    pub age_cycles: f32,
+}
+
+impl Default for SkyAnomaly {
+    fn default() -> Self {
+        Self::supernova(0.0, 0.0)
+    }
+}
+
+impl SkyAnomaly {
+    /// A star flaring up within a few hours, then fading over a week.
+    pub fn supernova(right_ascension_deg: f32, declination_deg: f32) -> Self {
+        Self {
+            right_ascension_deg,
+            declination_deg,
+            distance: 4500.0,
+            radius: 12.0,
+            color: Color::srgb(1.0, 0.95, 0.85),
+            lifetime_cycles: 7.0,
+            brightness_by_age: UnevenSampleAutoCurve::new([
+                (0.0, 0.0),
+                (0.02, 20.0),
+                (0.2, 8.0),
+                (1.0, 0.0),
+            ])
+            .unwrap(),
+            day_visibility: 0.2,
+            age_cycles: 0.0,
+        }
+    }
+
+    /// A wide, soft glow of `color` fading in and out over `lifetime_cycles`.
+    pub fn glow(
+        right_ascension_deg: f32,
+        declination_deg: f32,
+        color: Color,
+        lifetime_cycles: f32,
+    ) -> Self {
+        Self {
+            radius: 150.0,
+            color,
+            lifetime_cycles,
+            brightness_by_age: UnevenSampleAutoCurve::new([
+                (0.0, 0.0),
+                (0.2, 1.0),
+                (0.8, 1.0),
+                (1.0, 0.0),
+            ])
+            .unwrap(),
+            day_visibility: 0.0,
+            ..Self::supernova(right_ascension_deg, declination_deg)
+        }
+    }
+
+    /// Brightness at the current age.
+    pub fn brightness(&self) -> f32 {
+        let age_fraction = if self.lifetime_cycles > 0.0 {
+            self.age_cycles / self.lifetime_cycles
+        } else {
+            1.0
+        };
+        self.brightness_by_age
+            .sample_clamped(age_fraction.clamp(0.0, 1.0))
+            .max(0.0)
+    }
+
+    /// Direction in the sky's local frame (the sky as at solar noon), for a sky at
+    /// `latitude_degrees`.
+    pub fn local_direction(&self, latitude_degrees: f32) -> Vec3 {
+        calculate_direction_from_hour_angle(
+            -self.right_ascension_deg * DEGREES_TO_RADIANS,
+            latitude_degrees * DEGREES_TO_RADIANS,
+            self.declination_deg * DEGREES_TO_RADIANS,
+        )
+    }
+}
+
+/// Sent when an anomaly reached the end of its lifetime, just before it is despawned.
+#[derive(Message, Debug, Clone, Copy)]
+pub struct SkyAnomalyEnded {
+    pub anomaly: Entity,
+    pub sky: Entity,
+    pub at: SkyTimestamp,
+}
+
+/// Glowing material of an anomaly.
+#[derive(Component, Debug, Clone)]
+pub struct SkyAnomalyMaterial(pub Handle<StandardMaterial>);
+
+/// Spawns `anomaly` on the star dome of `sky` (a `SkyCenter` entity). Returns the anomaly
+/// entity.
+pub fn spawn_sky_anomaly(commands: &mut Commands, sky: Entity, anomaly: SkyAnomaly) -> Entity {
+    let entity = commands
+        .spawn((Name::new("Sky anomaly"), anomaly, NotShadowCaster))
+        .id();
+    commands.entity(sky).add_child(entity);
+    entity
+}
+
+fn spawn_sky_anomaly_visuals(
+    mut commands: Commands,
+    q_anomaly: Query<(Entity, &SkyAnomaly), Added<SkyAnomaly>>,
+    meshes: Option<ResMut<Assets<Mesh>>>,
+    materials: Option<ResMut<Assets<StandardMaterial>>>,
+) {
+    // No render assets in this world, there is nothing to draw anomalies with
+    let (Some(mut meshes), Some(mut materials)) = (meshes, materials) else {
+        return;
+    };
+
+    for (entity, anomaly) in q_anomaly.iter() {
+        let material = materials.add(StandardMaterial {
+            base_color: Color::BLACK,
+            alpha_mode: AlphaMode::Add,
+            unlit: true,
+            ..default()
+        });
+        commands.entity(entity).insert((
+            Mesh3d(meshes.add(Sphere::new(anomaly.radius))),
+            MeshMaterial3d(material.clone()),
+            SkyAnomalyMaterial(material),
+        ));
+    }
+}
+
+fn update_sky_anomalies(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut ended: MessageWriter<SkyAnomalyEnded>,
+    mut last_positions: Local<HashMap<Entity, (u64, f32)>>,
+    q_sky_center: Query<(&SkyCenter, &SunState)>,
+    mut q_anomaly: Query<(
+        Entity,
+        &ChildOf,
+        &mut SkyAnomaly,
+        &mut Transform,
+        Option<&SkyAnomalyMaterial>,
+    )>,
+    mut materials: Option<ResMut<Assets<StandardMaterial>>>,
+) {
+    for (entity, child_of, mut anomaly, mut transform, material) in q_anomaly.iter_mut() {
+        let sky = child_of.parent();
+        let Ok((sky_center, sun_state)) = q_sky_center.get(sky) else {
+            continue;
+        };
+        if !(sky_center.cycle_duration_secs > 0.0) {
+            continue;
+        }
+
+        // Age with the sky clock, rewinds included
+        let hour_fraction = sky_center.current_cycle_time / sky_center.cycle_duration_secs;
+        if let Some((last_day_index, last_hour_fraction)) =
+            last_positions.insert(entity, (sky_center.day_index, hour_fraction))
+        {
+            let cycles = sky_center.day_index.wrapping_sub(last_day_index) as i64 as f32
+                + hour_fraction
+                - last_hour_fraction;
+            anomaly.age_cycles = (anomaly.age_cycles + cycles).max(0.0);
+        }
+        if anomaly.age_cycles >= anomaly.lifetime_cycles {
+            ended.write(SkyAnomalyEnded {
+                anomaly: entity,
+                sky,
+                at: sky_center.timestamp(time.elapsed_secs()),
+            });
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        transform.translation =
+            anomaly.local_direction(sky_center.latitude_degrees) * anomaly.distance;
+
+        // Fades in daylight down to `day_visibility`, like the stars
+        let night = ((0.1 - sun_state.direction.y) / 0.2).clamp(0.0, 1.0);
+        let visibility = anomaly.day_visibility + night * (1.0 - anomaly.day_visibility);
+        if let (Some(material), Some(materials)) = (material, materials.as_mut())
+            && let Some(material) = materials.get_mut(material.0.id())
+        {
+            material.emissive = anomaly.color.to_linear() * (anomaly.brightness() * visibility);
+        }
+    }
+    last_positions.retain(|entity, _| q_anomaly.contains(*entity));
+}
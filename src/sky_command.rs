@@ -0,0 +1,102 @@
+// Message based control of SkyCenter clocks. Gameplay code, debug tools and UI
+// send SkyCommand instead of poking current_cycle_time directly.
+
+use bevy::prelude::*;
+
+use crate::{SkyCenter, horizon_profile::HorizonProfile};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SkyAction {
+    /// Moves the clock by a number of in-game hours (a cycle is 24 hours). Negative goes back.
+    AdvanceHours(f32),
+    /// Jumps to an hour fraction (0.0 midnight, 0.5 noon).
+    SetHourFraction(f32),
+    /// Jumps to sunrise, when the sun's upper limb crosses the horizon (or the skyline of the
+    /// sky's `HorizonProfile`). Ignored during polar day/night.
+    JumpToSunrise,
+    /// Jumps to solar noon.
+    JumpToNoon,
+    /// Jumps to sunset, when the sun's upper limb crosses the horizon (or the skyline of the
+    /// sky's `HorizonProfile`). Ignored during polar day/night.
+    JumpToSunset,
+    SetPaused(bool),
+    TogglePause,
+    SetTimeScale(f32),
+    /// Multiplies the current time scale (e.g. 2.0 to speed up, 0.5 to slow down).
+    MultiplyTimeScale(f32),
+}
+
+/// Applied to `sky` (or every `SkyCenter` when `None`) right before the sun update.
+#[derive(Message, Debug, Clone, Copy)]
+pub struct SkyCommand {
+    pub sky: Option<Entity>,
+    pub action: SkyAction,
+}
+
+impl SkyCommand {
+    /// Command for every `SkyCenter` in the world.
+    pub fn all(action: SkyAction) -> Self {
+        Self { sky: None, action }
+    }
+
+    /// Command for a single `SkyCenter` entity.
+    pub fn for_sky(sky: Entity, action: SkyAction) -> Self {
+        Self {
+            sky: Some(sky),
+            action,
+        }
+    }
+}
+
+pub(crate) fn apply_sky_commands(
+    mut commands: MessageReader<SkyCommand>,
+    mut q_sky_center: Query<(Entity, &mut SkyCenter, Option<&HorizonProfile>)>,
+) {
+    for command in commands.read() {
+        for (entity, mut sky_center, horizon_profile) in q_sky_center.iter_mut() {
+            if command.sky.is_some_and(|sky| sky != entity) {
+                continue;
+            }
+            apply_action(&mut sky_center, horizon_profile, command.action);
+        }
+    }
+}
+
+fn apply_action(
+    sky_center: &mut SkyCenter,
+    horizon_profile: Option<&HorizonProfile>,
+    action: SkyAction,
+) {
+    let cycle = sky_center.cycle_duration_secs;
+    // Noon follows the sun, which may run ahead of the clock
+    let solar_offset = sky_center.equation_of_time_offset();
+
+    match action {
+        SkyAction::AdvanceHours(hours) => {
+            sky_center.current_cycle_time =
+                (sky_center.current_cycle_time + hours / 24.0 * cycle).rem_euclid(cycle);
+        }
+        SkyAction::SetHourFraction(hour_fraction) => sky_center.set_hour_fraction(hour_fraction),
+        SkyAction::JumpToSunrise => {
+            if let &[sunrise, _] = sky_center
+                .sunrise_and_sunset_over(horizon_profile)
+                .as_slice()
+            {
+                sky_center.set_hour_fraction(sunrise);
+            }
+        }
+        SkyAction::JumpToNoon => sky_center.set_hour_fraction(0.5 - solar_offset),
+        SkyAction::JumpToSunset => {
+            if let &[_, sunset] = sky_center
+                .sunrise_and_sunset_over(horizon_profile)
+                .as_slice()
+            {
+                sky_center.set_hour_fraction(sunset);
+            }
+        }
+        SkyAction::SetPaused(paused) => sky_center.paused = paused,
+        SkyAction::TogglePause => sky_center.paused = !sky_center.paused,
+        SkyAction::SetTimeScale(time_scale) => sky_center.time_scale = time_scale,
+        SkyAction::MultiplyTimeScale(factor) => sky_center.time_scale *= factor,
+    }
+}
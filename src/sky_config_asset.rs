@@ -0,0 +1,274 @@
+// Sky configuration as a `.sky.ron` asset, so designers can tweak the day/night cycle
+// without recompiling. With Bevy's `file_watcher` feature, edits are applied while the
+// game runs (the sky keeps its time of day).
+
+use bevy::{
+    asset::{AssetLoader, LoadContext, io::Reader},
+    prelude::*,
+    scene::ron,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    HemispherePreference, SkyCenter, SkyConfigError, calculate_latitude_yearfraction,
+    random_stars::StarSpawner,
+    sun_light::{SunColorTemperature, SunIlluminance},
+};
+
+pub struct SkyConfigAssetPlugin;
+
+impl Plugin for SkyConfigAssetPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<SkyConfigAsset>()
+            .init_asset_loader::<SkyConfigAssetLoader>()
+            .register_type::<SkyConfigHandle>();
+        // Before the sky advances in Update
+        app.add_systems(PreUpdate, apply_sky_config_assets);
+    }
+}
+
+/// Where the sky is, either set directly or solved from the desired timings like a
+/// `TimedSkyConfig`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum SkyConfigLocation {
+    Exact {
+        latitude_degrees: f32,
+        year_fraction: f32,
+        cycle_duration_secs: f32,
+    },
+    Timed {
+        day_duration_secs: f32,
+        night_duration_secs: f32,
+        max_sun_height_deg: f32,
+        #[serde(default)]
+        hemisphere: HemispherePreference,
+    },
+}
+
+/// Star field settings, applied to the `StarSpawner` of the sky.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SkyConfigStars {
+    pub star_count: u32,
+    pub spawn_radius: f32,
+    #[serde(default)]
+    pub day_star_visibility: f32,
+}
+
+/// A whole sky setup loaded from a `.sky.ron` file. Add a [`SkyConfigHandle`] to a sky
+/// entity to apply it.
+///
+/// ```ron
+/// (
+///     planet_tilt_degrees: 23.5,
+///     location: Timed(day_duration_secs: 600.0, night_duration_secs: 300.0, max_sun_height_deg: 60.0),
+///     stars: Some((star_count: 1000, spawn_radius: 5000.0)),
+///     sun_illuminance: Some([(-6.0, 0.0), (0.0, 400.0), (90.0, 100000.0)]),
+/// )
+/// ```
+#[derive(Asset, TypePath, Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SkyConfigAsset {
+    pub planet_tilt_degrees: f32,
+    pub location: SkyConfigLocation,
+    #[serde(default = "default_time_scale")]
+    pub time_scale: f32,
+    /// Inserts or updates the `StarSpawner` of the sky, `None` leaves it alone.
+    #[serde(default)]
+    pub stars: Option<SkyConfigStars>,
+    /// `(altitude_deg, kelvin)` points of a `SunColorTemperature`, `None` leaves it alone.
+    #[serde(default)]
+    pub sun_color_kelvin: Option<Vec<(f32, f32)>>,
+    /// `(altitude_deg, lux)` points of a `SunIlluminance`, `None` leaves it alone.
+    #[serde(default)]
+    pub sun_illuminance: Option<Vec<(f32, f32)>>,
+}
+
+fn default_time_scale() -> f32 {
+    SkyCenter::default().time_scale
+}
+
+impl SkyConfigAsset {
+    /// Configures `sky_center` from this asset, keeping its sun, time of day and day index.
+    /// Left untouched on error.
+    pub fn apply_to(&self, sky_center: &mut SkyCenter) -> Result<(), SkyConfigError> {
+        let (latitude_degrees, year_fraction, cycle_duration_secs) = match self.location {
+            SkyConfigLocation::Exact {
+                latitude_degrees,
+                year_fraction,
+                cycle_duration_secs,
+            } => (latitude_degrees, year_fraction, cycle_duration_secs),
+            SkyConfigLocation::Timed {
+                day_duration_secs,
+                night_duration_secs,
+                max_sun_height_deg,
+                hemisphere,
+            } => {
+                let (latitude, year_fraction, _) = calculate_latitude_yearfraction(
+                    self.planet_tilt_degrees,
+                    day_duration_secs,
+                    night_duration_secs,
+                    max_sun_height_deg,
+                    hemisphere,
+                )?;
+                (
+                    latitude,
+                    // The solver's season is on a circular orbit
+                    sky_center.year_fraction_for_season(year_fraction),
+                    day_duration_secs + night_duration_secs,
+                )
+            }
+        };
+        if !(cycle_duration_secs > 0.0 && cycle_duration_secs.is_finite()) {
+            return Err(SkyConfigError::InvalidDurations {
+                day_duration_secs: cycle_duration_secs,
+                night_duration_secs: 0.0,
+            });
+        }
+
+        let hour_fraction = if sky_center.cycle_duration_secs > 0.0 {
+            sky_center.current_cycle_time / sky_center.cycle_duration_secs
+        } else {
+            0.0
+        };
+        sky_center.latitude_degrees = latitude_degrees;
+        sky_center.planet_tilt_degrees = self.planet_tilt_degrees;
+        sky_center.year_fraction = year_fraction;
+        sky_center.cycle_duration_secs = cycle_duration_secs;
+        sky_center.time_scale = self.time_scale;
+        sky_center.set_hour_fraction(hour_fraction);
+        Ok(())
+    }
+}
+
+/// The `SkyConfigAsset` of a sky entity. A `SkyCenter` is inserted if missing (link its
+/// sun with a `SunRef`), and re-configured whenever the asset is reloaded.
+#[derive(Component, Reflect, Debug, Default, Clone)]
+#[reflect(Component, Default, Debug)]
+pub struct SkyConfigHandle(pub Handle<SkyConfigAsset>);
+
+#[derive(Default, TypePath)]
+pub struct SkyConfigAssetLoader;
+
+/// Why a `.sky.ron` file couldn't be loaded.
+#[derive(Debug)]
+pub enum SkyConfigAssetError {
+    Io(std::io::Error),
+    Ron(ron::error::SpannedError),
+}
+
+impl std::fmt::Display for SkyConfigAssetError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SkyConfigAssetError::Io(err) => write!(f, "Could not read the sky config: {}", err),
+            SkyConfigAssetError::Ron(err) => write!(f, "Invalid sky config: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for SkyConfigAssetError {}
+
+impl From<std::io::Error> for SkyConfigAssetError {
+    fn from(err: std::io::Error) -> Self {
+        SkyConfigAssetError::Io(err)
+    }
+}
+
+impl From<ron::error::SpannedError> for SkyConfigAssetError {
+    fn from(err: ron::error::SpannedError) -> Self {
+        SkyConfigAssetError::Ron(err)
+    }
+}
+
+impl AssetLoader for SkyConfigAssetLoader {
+    type Asset = SkyConfigAsset;
+    type Settings = ();
+    type Error = SkyConfigAssetError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<SkyConfigAsset, SkyConfigAssetError> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        Ok(ron::de::from_bytes(&bytes)?)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["sky.ron"]
+    }
+}
+
+fn apply_sky_config_assets(
+    mut commands: Commands,
+    mut asset_events: MessageReader<AssetEvent<SkyConfigAsset>>,
+    assets: Res<Assets<SkyConfigAsset>>,
+    mut q_sky: Query<(
+        Entity,
+        Ref<SkyConfigHandle>,
+        Option<&mut SkyCenter>,
+        Option<&StarSpawner>,
+    )>,
+) {
+    let loaded: Vec<AssetId<SkyConfigAsset>> = asset_events
+        .read()
+        .filter_map(|event| match event {
+            AssetEvent::Added { id } | AssetEvent::Modified { id } => Some(*id),
+            _ => None,
+        })
+        .collect();
+
+    for (entity, handle, sky_center, star_spawner) in q_sky.iter_mut() {
+        if !handle.is_changed() && !loaded.contains(&handle.0.id()) {
+            continue;
+        }
+        let Some(config) = assets.get(&handle.0) else {
+            continue; // Applied once loaded
+        };
+
+        let result = match sky_center {
+            Some(mut sky_center) => config.apply_to(&mut sky_center),
+            None => {
+                let mut sky_center = SkyCenter::default();
+                config.apply_to(&mut sky_center).map(|()| {
+                    commands.entity(entity).insert(sky_center);
+                })
+            }
+        };
+        if let Err(err) = result {
+            warn!(
+                "Sky config {:?} of {} is impossible, keeping the previous sky: {}",
+                handle.0.path(),
+                entity,
+                err
+            );
+            continue;
+        }
+
+        let mut entity_commands = commands.entity(entity);
+        if let Some(stars) = &config.stars {
+            entity_commands.insert(StarSpawner {
+                star_count: stars.star_count,
+                spawn_radius: stars.spawn_radius,
+                day_star_visibility: stars.day_star_visibility,
+                ..star_spawner.cloned().unwrap_or_default()
+            });
+        }
+        if let Some(points) = &config.sun_color_kelvin {
+            match SunColorTemperature::from_points(points.iter().copied()) {
+                Some(temperature) => {
+                    entity_commands.insert(temperature);
+                }
+                None => warn!("Sky config sun_color_kelvin needs at least two points"),
+            }
+        }
+        if let Some(points) = &config.sun_illuminance {
+            match SunIlluminance::from_points(points.iter().copied()) {
+                Some(illuminance) => {
+                    entity_commands.insert(illuminance);
+                }
+                None => warn!("Sky config sun_illuminance needs at least two points"),
+            }
+        }
+    }
+}
@@ -0,0 +1,328 @@
+// Small CPU generated cubemap of the procedural sky (gradient + sun disk + stars),
+// used as a realtime filtered environment map so PBR reflections follow time of day.
+// It is not meant to look like the Atmosphere skybox, only to give matching tints.
+
+use std::f32::consts::PI;
+
+use bevy::{
+    asset::RenderAssetUsages,
+    pbr::GeneratedEnvironmentMapLight,
+    prelude::*,
+    render::render_resource::{
+        Extent3d, TextureDimension, TextureFormat, TextureViewDescriptor, TextureViewDimension,
+    },
+};
+
+use crate::{
+    DEGREES_TO_RADIANS, SkyCenter, SunState,
+    environment::{EnvironmentCadenceSet, SkyEnvironmentPlugin, environment_update_due},
+    primary_sky::PrimarySkyQuery,
+};
+
+pub struct SkyCubemapPlugin;
+
+impl Plugin for SkyCubemapPlugin {
+    fn build(&self, app: &mut App) {
+        if !app.is_plugin_added::<SkyEnvironmentPlugin>() {
+            app.add_plugins(SkyEnvironmentPlugin);
+        }
+        app.init_resource::<SkyCubemapSettings>();
+        app.add_systems(Startup, setup_sky_cubemap);
+        app.add_systems(
+            PostUpdate,
+            (
+                attach_sky_cubemap,
+                regenerate_sky_cubemap
+                    .after(EnvironmentCadenceSet)
+                    .run_if(environment_update_due),
+            ),
+        );
+    }
+}
+
+/// Marker for cameras that should receive the generated sky cubemap as environment light.
+#[derive(Component, Debug, Default, Clone, Copy)]
+pub struct SkyCubemapTarget;
+
+/// Regeneration frequency is controlled by `environment::EnvironmentUpdateCadence`.
+#[derive(Resource, Debug, Clone)]
+pub struct SkyCubemapSettings {
+    /// Face resolution in pixels. Changes apply on the next startup only.
+    pub size: u32,
+    /// `GeneratedEnvironmentMapLight::intensity` applied to target cameras.
+    pub intensity: f32,
+    pub day_zenith_color: LinearRgba,
+    pub day_horizon_color: LinearRgba,
+    pub night_zenith_color: LinearRgba,
+    pub night_horizon_color: LinearRgba,
+    pub sunset_color: LinearRgba,
+    pub ground_color: LinearRgba,
+    /// Angular radius of the sun disk in degrees (exaggerated so it survives the low resolution).
+    pub sun_disk_radius_deg: f32,
+    pub sun_disk_intensity: f32,
+    /// Vertical flattening of the drawn sun disk at the horizon (0.0 round, 0.3 squashes it to
+    /// 70% of its height), fading out as the sun climbs above `horizon_squash_fade_deg`.
+    /// Only affects the picture, not the light direction.
+    pub horizon_squash: f32,
+    pub horizon_squash_fade_deg: f32,
+    /// Fraction of sky cells holding a star (0.0 disables stars).
+    pub star_density: f32,
+    pub star_intensity: f32,
+}
+
+impl Default for SkyCubemapSettings {
+    fn default() -> Self {
+        Self {
+            size: 64,
+            intensity: 2000.0,
+            day_zenith_color: LinearRgba::rgb(0.15, 0.35, 0.9),
+            day_horizon_color: LinearRgba::rgb(0.6, 0.75, 0.95),
+            night_zenith_color: LinearRgba::rgb(0.002, 0.003, 0.01),
+            night_horizon_color: LinearRgba::rgb(0.01, 0.012, 0.025),
+            sunset_color: LinearRgba::rgb(1.0, 0.45, 0.15),
+            ground_color: LinearRgba::rgb(0.1, 0.09, 0.08),
+            sun_disk_radius_deg: 3.0,
+            sun_disk_intensity: 50.0,
+            horizon_squash: 0.0,
+            horizon_squash_fade_deg: 5.0,
+            star_density: 0.02,
+            star_intensity: 0.5,
+        }
+    }
+}
+
+/// Handle of the generated cubemap image.
+#[derive(Resource, Debug, Clone)]
+pub struct SkyCubemap {
+    pub image: Handle<Image>,
+}
+
+fn setup_sky_cubemap(
+    mut commands: Commands,
+    mut images: ResMut<Assets<Image>>,
+    settings: Res<SkyCubemapSettings>,
+) {
+    let size = settings.size.max(1);
+    let mut image = Image::new_fill(
+        Extent3d {
+            width: size,
+            height: size,
+            depth_or_array_layers: 6,
+        },
+        TextureDimension::D2,
+        &[0; 8],
+        TextureFormat::Rgba16Float,
+        // Keep the CPU copy, it's rewritten in place on every regeneration
+        RenderAssetUsages::MAIN_WORLD | RenderAssetUsages::RENDER_WORLD,
+    );
+    image.texture_view_descriptor = Some(TextureViewDescriptor {
+        dimension: Some(TextureViewDimension::Cube),
+        ..default()
+    });
+
+    commands.insert_resource(SkyCubemap {
+        image: images.add(image),
+    });
+}
+
+fn attach_sky_cubemap(
+    mut commands: Commands,
+    cubemap: Res<SkyCubemap>,
+    settings: Res<SkyCubemapSettings>,
+    q_camera: Query<
+        Entity,
+        (
+            With<SkyCubemapTarget>,
+            Without<GeneratedEnvironmentMapLight>,
+        ),
+    >,
+) {
+    for entity in q_camera.iter() {
+        commands
+            .entity(entity)
+            .insert(GeneratedEnvironmentMapLight {
+                environment_map: cubemap.image.clone(),
+                intensity: settings.intensity,
+                ..default()
+            });
+    }
+}
+
+fn regenerate_sky_cubemap(
+    cubemap: Res<SkyCubemap>,
+    settings: Res<SkyCubemapSettings>,
+    mut images: ResMut<Assets<Image>>,
+    primary_sky: PrimarySkyQuery,
+    q_sky_center: Query<(&SunState, &Transform), With<SkyCenter>>,
+) {
+    let Some((sun_state, sky_transform)) = primary_sky
+        .entity()
+        .and_then(|sky| q_sky_center.get(sky).ok())
+    else {
+        return;
+    };
+    let Some(image) = images.get_mut(&cubemap.image) else {
+        return;
+    };
+
+    let sun_direction = sun_state.direction;
+    // Stars are fixed on the celestial sphere, so hash them in the sky frame
+    let to_sky_frame = sky_transform.rotation.inverse();
+    let size = image.width();
+
+    let mut data = Vec::with_capacity((size * size * 6 * 8) as usize);
+    for face in 0..6 {
+        for y in 0..size {
+            for x in 0..size {
+                let u = (x as f32 + 0.5) / size as f32 * 2.0 - 1.0;
+                let v = (y as f32 + 0.5) / size as f32 * 2.0 - 1.0;
+                let direction = cube_face_direction(face, u, v);
+                let color = sky_color(
+                    &settings,
+                    direction,
+                    sun_direction,
+                    to_sky_frame * direction,
+                );
+                for channel in [color.red, color.green, color.blue, 1.0] {
+                    data.extend_from_slice(&f32_to_f16_bits(channel).to_le_bytes());
+                }
+            }
+        }
+    }
+    image.data = Some(data);
+}
+
+/// Direction through texel (u, v) in [-1, 1] of a cube face, in wgpu face order (+X, -X, +Y, -Y, +Z, -Z).
+fn cube_face_direction(face: u32, u: f32, v: f32) -> Vec3 {
+    match face {
+        0 => Vec3::new(1.0, -v, -u),
+        1 => Vec3::new(-1.0, -v, u),
+        2 => Vec3::new(u, 1.0, v),
+        3 => Vec3::new(u, -1.0, -v),
+        4 => Vec3::new(u, -v, 1.0),
+        _ => Vec3::new(-u, -v, -1.0),
+    }
+    .normalize()
+}
+
+fn sky_color(
+    settings: &SkyCubemapSettings,
+    direction: Vec3,
+    sun_direction: Vec3,
+    sky_frame_direction: Vec3,
+) -> LinearRgba {
+    // 0 at night, 1 during the day, smooth through twilight
+    let day_factor = ((sun_direction.y + 0.1) / 0.3).clamp(0.0, 1.0);
+
+    if direction.y < 0.0 {
+        return settings.ground_color * (0.02 + day_factor);
+    }
+
+    let zenith = lerp_color(
+        settings.night_zenith_color,
+        settings.day_zenith_color,
+        day_factor,
+    );
+    let horizon = lerp_color(
+        settings.night_horizon_color,
+        settings.day_horizon_color,
+        day_factor,
+    );
+    let mut color = lerp_color(horizon, zenith, direction.y.sqrt());
+
+    // Warm glow around a low sun
+    let sun_alignment = direction.dot(sun_direction).max(0.0);
+    let low_sun = (1.0 - sun_direction.y.abs() * 4.0).clamp(0.0, 1.0);
+    let glow = sun_alignment.powf(8.0) * low_sun * (1.0 - direction.y).powf(2.0);
+    color = lerp_color(color, settings.sunset_color * day_factor.max(0.2), glow);
+
+    if in_sun_disk(settings, direction, sun_direction) {
+        color = color + LinearRgba::WHITE * settings.sun_disk_intensity;
+    }
+
+    if settings.star_density > 0.0 {
+        let night_factor = 1.0 - day_factor;
+        let cell = (sky_frame_direction * 64.0).floor().as_ivec3();
+        if hash_cell(cell) < settings.star_density {
+            color = color + LinearRgba::WHITE * settings.star_intensity * night_factor;
+        }
+    }
+
+    color
+}
+
+/// Whether `direction` hits the sun disk, flattened near the horizon like seen through
+/// a thick atmosphere.
+fn in_sun_disk(settings: &SkyCubemapSettings, direction: Vec3, sun_direction: Vec3) -> bool {
+    let radius = settings.sun_disk_radius_deg * DEGREES_TO_RADIANS;
+    let sun_altitude = sun_direction.y.clamp(-1.0, 1.0).asin();
+    let fade = settings.horizon_squash_fade_deg.max(f32::EPSILON) * DEGREES_TO_RADIANS;
+    let flattening =
+        settings.horizon_squash.clamp(0.0, 0.95) * (1.0 - (sun_altitude.abs() / fade).min(1.0));
+    if flattening <= 0.0 {
+        return direction.dot(sun_direction) >= radius.cos();
+    }
+
+    // Angular offsets from the sun center, along the horizon and vertically
+    let vertical = direction.y.clamp(-1.0, 1.0).asin() - sun_altitude;
+    let azimuth_step = direction.x.atan2(direction.z) - sun_direction.x.atan2(sun_direction.z);
+    let azimuth_step = (azimuth_step + PI).rem_euclid(2.0 * PI) - PI;
+    let horizontal = azimuth_step * sun_altitude.cos();
+    let vertical_radius = radius * (1.0 - flattening);
+    (horizontal / radius).powi(2) + (vertical / vertical_radius).powi(2) <= 1.0
+}
+
+fn lerp_color(a: LinearRgba, b: LinearRgba, t: f32) -> LinearRgba {
+    a * (1.0 - t) + b * t
+}
+
+/// Cheap integer hash to [0, 1).
+fn hash_cell(cell: IVec3) -> f32 {
+    let mut h = (cell.x as u32).wrapping_mul(0x8da6_b343)
+        ^ (cell.y as u32).wrapping_mul(0xd816_3841)
+        ^ (cell.z as u32).wrapping_mul(0xcb1a_b31f);
+    h ^= h >> 15;
+    h = h.wrapping_mul(0x2c1b_3c6d);
+    h ^= h >> 12;
+    (h & 0x00ff_ffff) as f32 / 0x0100_0000 as f32
+}
+
+/// f32 -> IEEE half float bits, for the Rgba16Float texture.
+/// Only handles the values the sky produces (finite, non-negative), saturating at the f16 max.
+fn f32_to_f16_bits(value: f32) -> u16 {
+    let value = value.clamp(0.0, 65504.0);
+    let bits = value.to_bits();
+    let exponent = ((bits >> 23) & 0xff) as i32 - 127 + 15;
+    let mantissa = bits & 0x007f_ffff;
+
+    if exponent <= 0 {
+        // Subnormal half or zero
+        if exponent < -10 {
+            return 0;
+        }
+        let mantissa = mantissa | 0x0080_0000;
+        return (mantissa >> (14 - exponent)) as u16;
+    }
+    (((exponent as u32) << 10) | (mantissa >> 13)) as u16
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn half_float_bits() {
+        assert_eq!(f32_to_f16_bits(0.0), 0x0000);
+        assert_eq!(f32_to_f16_bits(1.0), 0x3c00);
+        assert_eq!(f32_to_f16_bits(0.5), 0x3800);
+        assert_eq!(f32_to_f16_bits(65504.0), 0x7bff);
+        // Smallest normal, then subnormals down to the smallest one
+        assert_eq!(f32_to_f16_bits(2.0f32.powi(-14)), 0x0400);
+        assert_eq!(f32_to_f16_bits(2.0f32.powi(-15)), 0x0200);
+        assert_eq!(f32_to_f16_bits(2.0f32.powi(-24)), 0x0001);
+        assert_eq!(f32_to_f16_bits(2.0f32.powi(-26)), 0x0000);
+        // Out of range values saturate
+        assert_eq!(f32_to_f16_bits(1.0e6), 0x7bff);
+        assert_eq!(f32_to_f16_bits(-1.0), 0x0000);
+    }
+}
@@ -0,0 +1,96 @@
+// All sky messages behind one system param, filtered by the emitting `SkyCenter`, so
+// games with several skies (planet map and local level) don't cross-trigger gameplay.
+
+use bevy::{ecs::system::SystemParam, prelude::*};
+
+use crate::{
+    NewCycleEvent, NewYearEvent, SkyCatchUp, SkyTimestamp, SunriseEvent, SunsetEvent,
+    time_of_day::TimeOfDayPhaseChanged,
+};
+
+/// Any sky message.
+#[derive(Debug, Clone, Copy)]
+pub enum SkyEvent {
+    Sunrise(SunriseEvent),
+    Sunset(SunsetEvent),
+    PhaseChanged(TimeOfDayPhaseChanged),
+    NewCycle(NewCycleEvent),
+    NewYear(NewYearEvent),
+    CatchUp(SkyCatchUp),
+}
+
+impl SkyEvent {
+    /// The `SkyCenter` that sent the event.
+    pub fn sky(&self) -> Entity {
+        match self {
+            SkyEvent::Sunrise(event) => event.sky,
+            SkyEvent::Sunset(event) => event.sky,
+            SkyEvent::PhaseChanged(event) => event.sky,
+            SkyEvent::NewCycle(event) => event.sky,
+            SkyEvent::NewYear(event) => event.sky,
+            SkyEvent::CatchUp(event) => event.sky,
+        }
+    }
+
+    pub fn at(&self) -> SkyTimestamp {
+        match self {
+            SkyEvent::Sunrise(event) => event.at,
+            SkyEvent::Sunset(event) => event.at,
+            SkyEvent::PhaseChanged(event) => event.at,
+            SkyEvent::NewCycle(event) => event.at,
+            SkyEvent::NewYear(event) => event.at,
+            SkyEvent::CatchUp(event) => event.at,
+        }
+    }
+}
+
+/// Reads every sky message once, in the order they happened.
+///
+/// Like any message reader, reading consumes the messages for this system: use
+/// [`SkyEvents::for_sky`] once per system, or [`SkyEvents::read`] and route them yourself.
+#[derive(SystemParam)]
+pub struct SkyEvents<'w, 's> {
+    sunrises: MessageReader<'w, 's, SunriseEvent>,
+    sunsets: MessageReader<'w, 's, SunsetEvent>,
+    phase_changes: MessageReader<'w, 's, TimeOfDayPhaseChanged>,
+    new_cycles: MessageReader<'w, 's, NewCycleEvent>,
+    new_years: MessageReader<'w, 's, NewYearEvent>,
+    catch_ups: MessageReader<'w, 's, SkyCatchUp>,
+}
+
+impl SkyEvents<'_, '_> {
+    /// All unread sky events of every sky, sorted by day and cycle time.
+    pub fn read(&mut self) -> Vec<SkyEvent> {
+        let mut events: Vec<SkyEvent> = self
+            .sunrises
+            .read()
+            .copied()
+            .map(SkyEvent::Sunrise)
+            .chain(self.sunsets.read().copied().map(SkyEvent::Sunset))
+            .chain(
+                self.phase_changes
+                    .read()
+                    .copied()
+                    .map(SkyEvent::PhaseChanged),
+            )
+            .chain(self.new_cycles.read().copied().map(SkyEvent::NewCycle))
+            .chain(self.new_years.read().copied().map(SkyEvent::NewYear))
+            .chain(self.catch_ups.read().copied().map(SkyEvent::CatchUp))
+            .collect();
+        events.sort_by(|a, b| {
+            let (a, b) = (a.at(), b.at());
+            a.day_index
+                .cmp(&b.day_index)
+                .then(a.cycle_time_secs.total_cmp(&b.cycle_time_secs))
+        });
+        events
+    }
+
+    /// The unread events sent by `sky`, sorted by day and cycle time. Events of other skies
+    /// are consumed too.
+    pub fn for_sky(&mut self, sky: Entity) -> impl Iterator<Item = SkyEvent> + use<> {
+        self.read()
+            .into_iter()
+            .filter(move |event| event.sky() == sky)
+    }
+}
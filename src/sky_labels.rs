@@ -0,0 +1,187 @@
+// Planetarium-style annotations: UI text labels following the sun, the moon, stars and
+// the cardinal points on screen, fading out near the horizon and in daylight.
+
+use bevy::prelude::*;
+
+use crate::{MoonCenter, SunMoveSet, SunState};
+
+/// Distance from the camera at which direction targets are projected.
+const DIRECTION_PROJECTION_DISTANCE: f32 = 10.0;
+
+pub struct SkyLabelsPlugin;
+
+impl Plugin for SkyLabelsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SkyLabelSettings>();
+        app.add_systems(PostUpdate, update_sky_labels.after(SunMoveSet));
+    }
+}
+
+/// What a [`SkyLabel`] points at.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SkyLabelTarget {
+    /// The sun of the sky.
+    Sun,
+    /// The moon of the sky's `MoonCenter`.
+    Moon,
+    /// An entity on the sky, e.g. a star, a comet or a sky anomaly.
+    Entity(Entity),
+    /// A fixed direction around the observer (X east, Y up, Z north), e.g. a cardinal point.
+    /// Never faded by altitude.
+    Direction(Vec3),
+}
+
+/// A UI text label drawn over `target`, as seen by the `SkyLabelCamera`. Spawn it with a
+/// `Text`, e.g. `(SkyLabel::new(sky, SkyLabelTarget::Sun), Text::new("Sun"))`.
+#[derive(Component, Debug, Clone)]
+#[require(Node, TextColor)]
+pub struct SkyLabel {
+    /// The `SkyCenter` the target belongs to.
+    pub sky: Entity,
+    pub target: SkyLabelTarget,
+    /// Label color at full opacity.
+    pub color: Color,
+    /// Fade the label out as the sun rises, like the stars.
+    pub fade_in_daylight: bool,
+    /// Screen offset from the target, in logical pixels.
+    pub offset: Vec2,
+}
+
+impl SkyLabel {
+    pub fn new(sky: Entity, target: SkyLabelTarget) -> Self {
+        Self {
+            sky,
+            target,
+            color: Color::WHITE,
+            fade_in_daylight: true,
+            offset: Vec2::new(8.0, -8.0),
+        }
+    }
+}
+
+/// The camera the sky labels are projected with.
+#[derive(Component, Debug, Default, Clone, Copy)]
+pub struct SkyLabelCamera;
+
+/// Fade rules of the sky labels, by altitude of the target and of the sun.
+#[derive(Resource, Debug, Clone, Copy)]
+pub struct SkyLabelSettings {
+    /// Celestial targets fade out from `(fade_start, fade_end)` degrees of altitude,
+    /// fully hidden below the second one.
+    pub altitude_fade_deg: (f32, f32),
+    /// Labels fading in daylight are fully visible with the sun below the first altitude
+    /// and hidden with the sun above the second one.
+    pub sun_altitude_fade_deg: (f32, f32),
+}
+
+impl Default for SkyLabelSettings {
+    fn default() -> Self {
+        Self {
+            altitude_fade_deg: (5.0, -2.0),
+            sun_altitude_fade_deg: (-12.0, -3.0),
+        }
+    }
+}
+
+/// Spawns "N", "E", "S" and "W" labels on the horizon of `sky`, visible day and night.
+/// Returns the label entities.
+pub fn spawn_cardinal_labels(commands: &mut Commands, sky: Entity) -> [Entity; 4] {
+    [
+        ("N", Vec3::Z),
+        ("E", Vec3::X),
+        ("S", Vec3::NEG_Z),
+        ("W", Vec3::NEG_X),
+    ]
+    .map(|(text, direction)| {
+        commands
+            .spawn((
+                SkyLabel {
+                    fade_in_daylight: false,
+                    ..SkyLabel::new(sky, SkyLabelTarget::Direction(direction))
+                },
+                Text::new(text),
+            ))
+            .id()
+    })
+}
+
+/// 1.0 at `from`, 0.0 at `to`, linear in between.
+fn fade(value: f32, (from, to): (f32, f32)) -> f32 {
+    if from == to {
+        return if value >= from { 1.0 } else { 0.0 };
+    }
+    ((value - to) / (from - to)).clamp(0.0, 1.0)
+}
+
+fn update_sky_labels(
+    settings: Res<SkyLabelSettings>,
+    q_camera: Query<(&Camera, &GlobalTransform), With<SkyLabelCamera>>,
+    q_sky_center: Query<(&SunState, &GlobalTransform, Option<&MoonCenter>)>,
+    q_target: Query<&GlobalTransform, Without<SkyLabel>>,
+    mut q_label: Query<(&SkyLabel, &mut Node, &mut TextColor, &mut Visibility)>,
+) {
+    let Ok((camera, camera_transform)) = q_camera.single() else {
+        return;
+    };
+    let camera_position = camera_transform.translation();
+
+    for (label, mut node, mut text_color, mut visibility) in q_label.iter_mut() {
+        let Ok((sun_state, sky_transform, moon_center)) = q_sky_center.get(label.sky) else {
+            visibility.set_if_neq(Visibility::Hidden);
+            continue;
+        };
+
+        // Where the target is drawn, and its direction from the sky center
+        let (world_position, direction, celestial) = match label.target {
+            SkyLabelTarget::Sun => (None, Some(sun_state.direction), true),
+            SkyLabelTarget::Moon => (
+                None,
+                // The moon light is placed along the moon direction
+                moon_center
+                    .and_then(|moon_center| q_target.get(moon_center.moon).ok())
+                    .map(GlobalTransform::translation),
+                true,
+            ),
+            SkyLabelTarget::Entity(entity) => match q_target.get(entity) {
+                Ok(target) => (
+                    Some(target.translation()),
+                    Some(target.translation() - sky_transform.translation()),
+                    true,
+                ),
+                Err(_) => (None, None, true),
+            },
+            SkyLabelTarget::Direction(direction) => (None, Some(direction), false),
+        };
+        let Some(direction) = direction.and_then(Vec3::try_normalize) else {
+            visibility.set_if_neq(Visibility::Hidden);
+            continue;
+        };
+        // Directions are infinitely far away, project a point along them
+        let world_position =
+            world_position.unwrap_or(camera_position + direction * DIRECTION_PROJECTION_DISTANCE);
+        let Ok(viewport_position) = camera.world_to_viewport(camera_transform, world_position)
+        else {
+            // Behind the camera
+            visibility.set_if_neq(Visibility::Hidden);
+            continue;
+        };
+
+        let mut alpha = 1.0;
+        if celestial {
+            alpha *= fade(direction.y.asin().to_degrees(), settings.altitude_fade_deg);
+        }
+        if label.fade_in_daylight {
+            alpha *= fade(sun_state.altitude_deg, settings.sun_altitude_fade_deg);
+        }
+        if alpha <= 0.0 {
+            visibility.set_if_neq(Visibility::Hidden);
+            continue;
+        }
+
+        visibility.set_if_neq(Visibility::Inherited);
+        node.position_type = PositionType::Absolute;
+        node.left = Val::Px(viewport_position.x + label.offset.x);
+        node.top = Val::Px(viewport_position.y + label.offset.y);
+        text_color.0 = label.color.with_alpha(label.color.alpha() * alpha);
+    }
+}
@@ -0,0 +1,77 @@
+// Several skies in one world (split-screen, planets in different scenes): the
+// `RenderLayers` of a `SkyCenter` are forwarded to its sun and moon lights, stars, comets,
+// anomalies and celestial sphere, so each sky only lights and shows up for the cameras of
+// its layers.
+
+use bevy::{camera::visibility::RenderLayers, prelude::*};
+
+use crate::{
+    MoonCenter, SkyCenter, SunMoveSet,
+    celestial_sphere::CelestialSphereMesh,
+    comet::Comet,
+    random_stars::{Star, StarSpawner},
+    sky_anomaly::SkyAnomaly,
+};
+
+pub struct SkyLayersPlugin;
+
+impl Plugin for SkyLayersPlugin {
+    fn build(&self, app: &mut App) {
+        // After the stars are respawned and the sun links resolved
+        app.add_systems(PostUpdate, forward_sky_render_layers.after(SunMoveSet));
+    }
+}
+
+fn forward_sky_render_layers(
+    mut commands: Commands,
+    q_sky_center: Query<(
+        &SkyCenter,
+        Ref<RenderLayers>,
+        Option<&MoonCenter>,
+        Option<&StarSpawner>,
+        Option<Ref<Children>>,
+    )>,
+    q_layers: Query<Option<&RenderLayers>, Without<SkyCenter>>,
+    q_star: Query<(), With<Star>>,
+    q_anomaly: Query<(), Or<(With<SkyAnomaly>, With<CelestialSphereMesh>)>>,
+    q_comet: Query<(Entity, &Comet, Option<&Children>)>,
+) {
+    let mut forward = |entity: Entity, layers: &RenderLayers| {
+        if let Ok(current) = q_layers.get(entity)
+            && current != Some(layers)
+        {
+            commands.entity(entity).insert(layers.clone());
+        }
+    };
+
+    for (sky_center, layers, moon_center, star_spawner, children) in q_sky_center.iter() {
+        forward(sky_center.sun, &layers);
+        if let Some(moon_center) = moon_center {
+            forward(moon_center.moon, &layers);
+        }
+
+        // Thousands of stars: only when they were respawned or the layers changed
+        if let Some(children) = children
+            && (layers.is_changed() || children.is_changed())
+        {
+            // Background stars stay on the layer of their background camera
+            let forward_stars =
+                star_spawner.is_none_or(|spawner| spawner.background_layer.is_none());
+            for child in children.iter() {
+                if q_anomaly.contains(child) || (forward_stars && q_star.contains(child)) {
+                    forward(child, &layers);
+                }
+            }
+        }
+    }
+
+    for (comet_entity, comet, children) in q_comet.iter() {
+        let Ok((_, layers, ..)) = q_sky_center.get(comet.sky) else {
+            continue;
+        };
+        forward(comet_entity, &layers);
+        for child in children.into_iter().flat_map(|children| children.iter()) {
+            forward(child, &layers);
+        }
+    }
+}
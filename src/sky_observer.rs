@@ -0,0 +1,203 @@
+// Several observers under one sky, e.g. co-op players far apart on a big map: each one
+// sees the sun from its own position (local solar time and latitude) over its own skyline,
+// and gets its own sunrises, sunsets and phase changes.
+
+use bevy::{platform::collections::HashMap, prelude::*};
+
+use crate::{
+    SkyCenter, SkyTimestamp, SunMoveSet, SunState,
+    horizon_profile::HorizonProfile,
+    time_of_day::{TimeOfDayPhase, TimeOfDayThresholds, classify_time_of_day},
+    times_passed,
+};
+
+pub struct SkyObserverPlugin;
+
+impl Plugin for SkyObserverPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<SkyObserver>();
+        app.add_message::<ObserverSunrise>()
+            .add_message::<ObserverSunset>()
+            .add_message::<ObserverPhaseChanged>();
+        // The sky clock is advanced in Update, so read it afterwards.
+        app.add_systems(PostUpdate, update_sky_observers.after(SunMoveSet));
+    }
+}
+
+/// A point of view on the sky of `sky` (a `SkyCenter` entity), at the position of this
+/// entity. Its `SunState` and `TimeOfDayPhase` follow the sun as seen from there.
+#[derive(Component, Reflect, Debug, Clone)]
+#[reflect(Component, Default, Debug)]
+#[require(Transform, SunState, TimeOfDayPhase)]
+pub struct SkyObserver {
+    #[entities]
+    pub sky: Entity,
+    /// World units per degree of latitude and longitude, relative to the `SkyCenter`
+    /// position. An observer further east (+X) sees the sun earlier, one further north
+    /// (+Z) sees it lower in the south. `f32::INFINITY` ignores the position.
+    pub units_per_degree: f32,
+    /// Skyline around the observer, see [`HorizonProfile`].
+    pub horizon: HorizonProfile,
+}
+
+impl Default for SkyObserver {
+    fn default() -> Self {
+        Self {
+            sky: Entity::PLACEHOLDER,
+            units_per_degree: 1000.0,
+            horizon: HorizonProfile::default(),
+        }
+    }
+}
+
+/// Sent when the sun rises over the skyline of `observer`.
+#[derive(Message, Debug, Clone, Copy)]
+pub struct ObserverSunrise {
+    pub observer: Entity,
+    pub sky: Entity,
+    /// Exact time of the crossing, on the sky's clock.
+    pub at: SkyTimestamp,
+}
+
+/// Sent when the sun sets behind the skyline of `observer`.
+#[derive(Message, Debug, Clone, Copy)]
+pub struct ObserverSunset {
+    pub observer: Entity,
+    pub sky: Entity,
+    /// Exact time of the crossing, on the sky's clock.
+    pub at: SkyTimestamp,
+}
+
+/// Sent when the `TimeOfDayPhase` of `observer` changes.
+#[derive(Message, Debug, Clone, Copy)]
+pub struct ObserverPhaseChanged {
+    pub observer: Entity,
+    pub sky: Entity,
+    pub from: TimeOfDayPhase,
+    pub to: TimeOfDayPhase,
+    pub at: SkyTimestamp,
+}
+
+impl SkyCenter {
+    /// This sky as seen `east_deg` of longitude and `north_deg` of latitude away: local solar
+    /// time runs ahead by `east_deg / 360` of a cycle, the latitude is shifted (clamped to
+    /// the poles). `day_index` is kept, even if local midnight has passed.
+    pub fn at_offset(&self, east_deg: f32, north_deg: f32) -> SkyCenter {
+        let mut sky_center = self.clone();
+        sky_center.latitude_degrees = (self.latitude_degrees + north_deg).clamp(-90.0, 90.0);
+        sky_center.current_cycle_time = (self.current_cycle_time
+            + east_deg / 360.0 * self.cycle_duration_secs)
+            .rem_euclid(self.cycle_duration_secs);
+        sky_center
+    }
+}
+
+pub(crate) fn update_sky_observers(
+    thresholds: Res<TimeOfDayThresholds>,
+    time: Res<Time>,
+    mut sunrises: MessageWriter<ObserverSunrise>,
+    mut sunsets: MessageWriter<ObserverSunset>,
+    mut phase_changes: MessageWriter<ObserverPhaseChanged>,
+    mut last_positions: Local<HashMap<Entity, (u64, f32)>>,
+    q_sky_center: Query<(&SkyCenter, &GlobalTransform)>,
+    mut q_observer: Query<(
+        Entity,
+        &SkyObserver,
+        &GlobalTransform,
+        &mut SunState,
+        &mut TimeOfDayPhase,
+    )>,
+) {
+    let elapsed_secs = time.elapsed_secs();
+    for (entity, observer, transform, mut sun_state, mut phase) in q_observer.iter_mut() {
+        let Ok((sky_center, sky_transform)) = q_sky_center.get(observer.sky) else {
+            continue;
+        };
+        if !(sky_center.cycle_duration_secs > 0.0 && sky_center.cycle_duration_secs.is_finite()) {
+            continue;
+        }
+
+        let offset =
+            (transform.translation() - sky_transform.translation()) / observer.units_per_degree;
+        let (east_deg, north_deg) = if offset.is_finite() {
+            (offset.x, offset.z)
+        } else {
+            (0.0, 0.0)
+        };
+        let local_sky = sky_center.at_offset(east_deg, north_deg);
+        let hour_fraction = local_sky.current_cycle_time / local_sky.cycle_duration_secs;
+        let state = local_sky.sun_state_at(hour_fraction);
+        *sun_state = sun_state.followed_by(SunState {
+            horizon_elevation_deg: observer.horizon.elevation_at(state.azimuth_deg),
+            ..state
+        });
+
+        // The local day starts at local midnight, before or after the sky's one
+        let shift = east_deg / 360.0;
+        let sky_hour_fraction = sky_center.current_cycle_time / sky_center.cycle_duration_secs;
+        let local_day_index = sky_center
+            .day_index
+            .wrapping_add_signed((sky_hour_fraction + shift).floor() as i64);
+
+        // Crossings since the last frame, on the observer's local clock. Rewinds and
+        // moves of the observer by more than a cycle send none.
+        if let Some((last_day_index, last_hour_fraction)) =
+            last_positions.insert(entity, (local_day_index, hour_fraction))
+        {
+            let cycles = local_day_index.wrapping_sub(last_day_index) as f32 + hour_fraction
+                - last_hour_fraction;
+            if cycles > 0.0
+                && cycles < 1.0
+                && let &[sunrise, sunset] = local_sky
+                    .sunrise_and_sunset_over(Some(&observer.horizon))
+                    .as_slice()
+            {
+                let timestamp = |crossing: f32| {
+                    let local_day_offset = (last_hour_fraction
+                        + (crossing - last_hour_fraction).rem_euclid(1.0))
+                    .floor() as i64;
+                    // Back on the sky's clock, where the crossing happened for everyone else
+                    let sky_position = crossing - shift;
+                    SkyTimestamp {
+                        cycle_time_secs: sky_position.rem_euclid(1.0)
+                            * sky_center.cycle_duration_secs,
+                        hour_fraction: sky_position.rem_euclid(1.0),
+                        day_index: last_day_index
+                            .wrapping_add_signed(local_day_offset + sky_position.floor() as i64),
+                        elapsed_secs,
+                    }
+                };
+                if times_passed(sunrise, last_hour_fraction, cycles) > 0 {
+                    sunrises.write(ObserverSunrise {
+                        observer: entity,
+                        sky: observer.sky,
+                        at: timestamp(sunrise),
+                    });
+                }
+                if times_passed(sunset, last_hour_fraction, cycles) > 0 {
+                    sunsets.write(ObserverSunset {
+                        observer: entity,
+                        sky: observer.sky,
+                        at: timestamp(sunset),
+                    });
+                }
+            }
+        }
+
+        let new_phase = classify_time_of_day(
+            sun_state.upper_limb_altitude_deg,
+            hour_fraction,
+            &thresholds,
+        );
+        if let Some(from) = phase.replace_if_neq(new_phase) {
+            phase_changes.write(ObserverPhaseChanged {
+                observer: entity,
+                sky: observer.sky,
+                from,
+                to: new_phase,
+                at: sky_center.timestamp(elapsed_secs),
+            });
+        }
+    }
+    last_positions.retain(|entity, _| q_observer.contains(*entity));
+}
@@ -0,0 +1,160 @@
+// Scripted sky moments ("blood red sky during the boss fight"): colors blended over the
+// computed sun color, ambient light and fog, fading in and out smoothly.
+
+use bevy::{light::GlobalAmbientLight, platform::collections::HashMap, prelude::*};
+
+use crate::{SkyCenter, sky_ambient::update_ambient_light, sun_light::update_sun_color};
+
+pub struct SkyOverridePlugin;
+
+impl Plugin for SkyOverridePlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<SkyOverride>();
+        // Blended over what the sun light and ambient plugins computed this frame
+        app.add_systems(
+            PostUpdate,
+            (fade_sky_overrides, apply_sky_overrides)
+                .chain()
+                .after(update_sun_color)
+                .after(update_ambient_light),
+        );
+    }
+}
+
+/// Output a [`SkyOverride`] blends into.
+#[derive(Reflect, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SkyChannel {
+    /// Color of the sun `DirectionalLight` of every `SkyCenter`.
+    SunColor,
+    /// Color of the `GlobalAmbientLight`.
+    AmbientColor,
+    /// Color of every `DistanceFog`.
+    FogColor,
+}
+
+/// Blends `value` over the computed value of `channel`, by `weight`. Spawn it on its own
+/// entity; several overrides of one channel are applied one after another.
+#[derive(Component, Reflect, Debug, Clone)]
+#[reflect(Component, Debug)]
+pub struct SkyOverride {
+    pub channel: SkyChannel,
+    pub value: Color,
+    /// Current strength, from 0.0 (computed value) to 1.0 (`value`).
+    pub weight: f32,
+    /// Strength `weight` fades towards.
+    pub target_weight: f32,
+    /// Seconds a fade from 0.0 to 1.0 takes. 0.0 jumps to `target_weight` instantly.
+    pub fade_secs: f32,
+    /// Despawn the entity once faded out to 0.0.
+    pub despawn_when_faded_out: bool,
+}
+
+impl SkyOverride {
+    /// Override fading in from the computed value over `fade_secs`.
+    pub fn fade_in(channel: SkyChannel, value: Color, fade_secs: f32) -> Self {
+        Self {
+            channel,
+            value,
+            weight: 0.0,
+            target_weight: 1.0,
+            fade_secs,
+            despawn_when_faded_out: false,
+        }
+    }
+
+    /// Fades back to the computed value, then despawns the override.
+    pub fn fade_out(&mut self) {
+        self.target_weight = 0.0;
+        self.despawn_when_faded_out = true;
+    }
+}
+
+fn fade_sky_overrides(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut q_overrides: Query<(Entity, &mut SkyOverride)>,
+) {
+    for (entity, mut sky_override) in q_overrides.iter_mut() {
+        if sky_override.weight != sky_override.target_weight {
+            let max_step = if sky_override.fade_secs > 0.0 {
+                time.delta_secs() / sky_override.fade_secs
+            } else {
+                f32::INFINITY
+            };
+            let step =
+                (sky_override.target_weight - sky_override.weight).clamp(-max_step, max_step);
+            sky_override.weight += step;
+        }
+        if sky_override.despawn_when_faded_out
+            && sky_override.weight <= 0.0
+            && sky_override.target_weight <= 0.0
+        {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+/// Value of a channel before and after the overrides of the last frame.
+struct BlendedColor {
+    computed: Color,
+    written: Color,
+}
+
+fn apply_sky_overrides(
+    q_overrides: Query<&SkyOverride>,
+    mut blended: Local<HashMap<(SkyChannel, Entity), BlendedColor>>,
+    ambient_light: Option<ResMut<GlobalAmbientLight>>,
+    q_sky_center: Query<&SkyCenter>,
+    mut q_light: Query<&mut DirectionalLight>,
+    mut q_fog: Query<(Entity, &mut DistanceFog)>,
+) {
+    if q_overrides.is_empty() && blended.is_empty() {
+        return;
+    }
+
+    let mut blend = |channel: SkyChannel, entity: Entity, current: Color| {
+        // Outputs nobody recomputes (e.g. fog) still hold last frame's blend
+        let computed = match blended.get(&(channel, entity)) {
+            Some(last) if last.written == current => last.computed,
+            _ => current,
+        };
+        let written = q_overrides
+            .iter()
+            .filter(|sky_override| sky_override.channel == channel)
+            .fold(computed, |color, sky_override| {
+                color.mix(&sky_override.value, sky_override.weight.clamp(0.0, 1.0))
+            });
+        blended.insert((channel, entity), BlendedColor { computed, written });
+        written
+    };
+
+    for sky_center in q_sky_center.iter() {
+        if let Ok(mut light) = q_light.get_mut(sky_center.sun) {
+            let color = blend(SkyChannel::SunColor, sky_center.sun, light.color);
+            if light.color != color {
+                light.color = color;
+            }
+        }
+    }
+    if let Some(mut ambient_light) = ambient_light {
+        let color = blend(
+            SkyChannel::AmbientColor,
+            Entity::PLACEHOLDER,
+            ambient_light.color,
+        );
+        if ambient_light.color != color {
+            ambient_light.color = color;
+        }
+    }
+    for (entity, mut fog) in q_fog.iter_mut() {
+        let color = blend(SkyChannel::FogColor, entity, fog.color);
+        if fog.color != color {
+            fog.color = color;
+        }
+    }
+
+    // Everything is back to its computed value
+    if q_overrides.is_empty() {
+        blended.clear();
+    }
+}
@@ -0,0 +1,165 @@
+// "What am I looking at": picks the sky object closest to a camera ray, for telescopes and
+// stargazing minigames.
+
+use bevy::{ecs::system::SystemParam, prelude::*};
+
+use crate::{
+    MoonCenter, MoonPhase, RADIANS_TO_DEGREES, SkyCenter, SunState, comet::Comet,
+    random_stars::Star, sky_anomaly::SkyAnomaly,
+};
+
+/// Apparent magnitude of the sun.
+const SUN_MAGNITUDE: f32 = -26.74;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SkyObjectKind {
+    Sun,
+    Moon,
+    Star,
+    Comet,
+    Anomaly,
+}
+
+/// A sky object seen along a ray, see [`SkyObjects::identify_sky_object`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct SkyObjectInfo {
+    /// The sun or moon light, the star, comet or anomaly entity.
+    pub entity: Entity,
+    /// The `SkyCenter` the object belongs to.
+    pub sky: Entity,
+    pub kind: SkyObjectKind,
+    /// Its `Name`, or the name of its kind.
+    pub name: String,
+    /// Apparent magnitude, when known (the sun, and the moon from its phase).
+    pub magnitude: Option<f32>,
+    pub altitude_deg: f32,
+    /// Azimuth in degrees from North towards East, in `[0, 360)`.
+    pub azimuth_deg: f32,
+    /// Angle between the ray and the object.
+    pub angular_distance_deg: f32,
+}
+
+/// The objects of every sky, to pick them with a ray.
+#[derive(SystemParam)]
+pub struct SkyObjects<'w, 's> {
+    q_sky_center: Query<
+        'w,
+        's,
+        (
+            Entity,
+            &'static SkyCenter,
+            &'static SunState,
+            Option<&'static MoonCenter>,
+            Option<&'static MoonPhase>,
+        ),
+    >,
+    q_transform: Query<'w, 's, &'static Transform>,
+    q_dome_object: Query<
+        'w,
+        's,
+        (
+            Entity,
+            &'static GlobalTransform,
+            &'static InheritedVisibility,
+            Option<&'static ChildOf>,
+            Has<Star>,
+            Option<&'static Comet>,
+        ),
+        Or<(With<Star>, With<Comet>, With<SkyAnomaly>)>,
+    >,
+    q_name: Query<'w, 's, &'static Name>,
+}
+
+impl SkyObjects<'_, '_> {
+    /// The sky object closest to `ray` within `tolerance_deg`, if any. The sun and moon are
+    /// infinitely far away, stars, comets and anomalies are seen from the ray origin.
+    /// Objects below the horizon are included, hidden ones aren't.
+    pub fn identify_sky_object(&self, ray: Ray3d, tolerance_deg: f32) -> Option<SkyObjectInfo> {
+        let ray_direction = *ray.direction;
+        let mut best: Option<SkyObjectInfo> = None;
+        let mut consider = |entity: Entity,
+                            sky: Entity,
+                            kind: SkyObjectKind,
+                            direction: Vec3,
+                            magnitude: Option<f32>| {
+            let Some(direction) = direction.try_normalize() else {
+                return;
+            };
+            let angular_distance_deg = ray_direction.angle_between(direction) * RADIANS_TO_DEGREES;
+            if angular_distance_deg > tolerance_deg
+                || best
+                    .as_ref()
+                    .is_some_and(|best| best.angular_distance_deg <= angular_distance_deg)
+            {
+                return;
+            }
+            best = Some(SkyObjectInfo {
+                entity,
+                sky,
+                kind,
+                name: self
+                    .q_name
+                    .get(entity)
+                    .map_or_else(|_| format!("{:?}", kind), |name| name.as_str().to_string()),
+                magnitude,
+                altitude_deg: direction.y.clamp(-1.0, 1.0).asin() * RADIANS_TO_DEGREES,
+                azimuth_deg: (direction.x.atan2(direction.z) * RADIANS_TO_DEGREES)
+                    .rem_euclid(360.0),
+                angular_distance_deg,
+            });
+        };
+
+        for (sky, sky_center, sun_state, moon_center, moon_phase) in self.q_sky_center.iter() {
+            consider(
+                sky_center.sun,
+                sky,
+                SkyObjectKind::Sun,
+                sun_state.direction,
+                Some(SUN_MAGNITUDE),
+            );
+            if let Some(moon_center) = moon_center
+                && let Ok(moon_transform) = self.q_transform.get(moon_center.moon)
+            {
+                consider(
+                    moon_center.moon,
+                    sky,
+                    SkyObjectKind::Moon,
+                    moon_transform.translation,
+                    moon_phase.map(|phase| moon_magnitude(phase.phase_angle_deg)),
+                );
+            }
+        }
+
+        for (entity, transform, visibility, child_of, is_star, comet) in self.q_dome_object.iter() {
+            if !visibility.get() {
+                continue;
+            }
+            let (kind, sky) = if is_star {
+                (SkyObjectKind::Star, child_of.map(ChildOf::parent))
+            } else if let Some(comet) = comet {
+                (SkyObjectKind::Comet, Some(comet.sky))
+            } else {
+                (SkyObjectKind::Anomaly, child_of.map(ChildOf::parent))
+            };
+            let Some(sky) = sky else {
+                continue;
+            };
+            consider(
+                entity,
+                sky,
+                kind,
+                transform.translation() - ray.origin,
+                None,
+            );
+        }
+        best
+    }
+}
+
+/// Apparent magnitude of the moon with the sun and moon `elongation_deg` apart as seen
+/// from the observer (0 at new moon, 180 at full moon).
+pub fn moon_magnitude(elongation_deg: f32) -> f32 {
+    // Phase angle: sun-moon-observer, 0 at full moon
+    let phase_angle_deg = (180.0 - elongation_deg).abs();
+    -12.73 + 0.026 * phase_angle_deg + 4.0e-9 * phase_angle_deg.powi(4)
+}
@@ -0,0 +1,66 @@
+// One-call sky setup: spawns the sun light and the `SkyCenter` already linked, instead of
+// wiring the sun entity by hand.
+
+use bevy::{light::light_consts::lux, prelude::*};
+
+use crate::{SkyCenter, random_stars::StarSpawner};
+
+/// Everything [`SpawnSkyExt::spawn_sky`] spawns. `sky_center.sun` is ignored, the spawned
+/// sun is linked instead.
+#[derive(Clone)]
+pub struct SkySettings {
+    pub sky_center: SkyCenter,
+    pub sun_light: DirectionalLight,
+    /// Star field of the sky, `None` for no stars (needs the `RandomStarsPlugin`).
+    pub stars: Option<StarSpawner>,
+}
+
+impl Default for SkySettings {
+    fn default() -> Self {
+        Self {
+            sky_center: SkyCenter::default(),
+            sun_light: DirectionalLight {
+                shadows_enabled: true,
+                illuminance: lux::RAW_SUNLIGHT,
+                ..default()
+            },
+            stars: Some(StarSpawner::default()),
+        }
+    }
+}
+
+/// The entities of a sky spawned with [`SpawnSkyExt::spawn_sky`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SpawnedSky {
+    /// The `SkyCenter` entity.
+    pub sky: Entity,
+    /// The sun `DirectionalLight`.
+    pub sun: Entity,
+}
+
+pub trait SpawnSkyExt {
+    /// Spawns a sun light and a `SkyCenter` moving it, with an optional star field.
+    fn spawn_sky(&mut self, settings: SkySettings) -> SpawnedSky;
+}
+
+impl SpawnSkyExt for Commands<'_, '_> {
+    fn spawn_sky(&mut self, settings: SkySettings) -> SpawnedSky {
+        let sun = self
+            .spawn((Name::new("Sun"), settings.sun_light, Transform::default()))
+            .id();
+        let mut sky_commands = self.spawn((
+            Name::new("Sky"),
+            SkyCenter {
+                sun,
+                ..settings.sky_center
+            },
+        ));
+        if let Some(stars) = settings.stars {
+            sky_commands.insert(stars);
+        }
+        SpawnedSky {
+            sky: sky_commands.id(),
+            sun,
+        }
+    }
+}
@@ -0,0 +1,151 @@
+// The whole sky state packed in one uniform buffer, updated once per frame, so custom
+// WGSL sky, water or foliage shaders only have to bind a single resource of this crate.
+
+use bevy::{
+    light::GlobalAmbientLight,
+    prelude::*,
+    render::{
+        Render, RenderApp, RenderSystems,
+        extract_resource::{ExtractResource, ExtractResourcePlugin},
+        render_resource::{ShaderType, UniformBuffer},
+        renderer::{RenderDevice, RenderQueue},
+    },
+    transform::TransformSystems,
+};
+
+use crate::{
+    MoonCenter, MoonPhase, SkyCenter, SunMoveSet, SunState, daylight_factor,
+    primary_sky::PrimarySkyQuery,
+};
+
+pub struct SkyStateUniformPlugin;
+
+impl Plugin for SkyStateUniformPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SkyStateUniform>()
+            .init_resource::<SkyFogTint>();
+        app.add_plugins(ExtractResourcePlugin::<SkyStateUniform>::default());
+        // Light directions are read from the propagated global transforms
+        app.add_systems(
+            PostUpdate,
+            update_sky_state_uniform
+                .after(SunMoveSet)
+                .after(TransformSystems::Propagate),
+        );
+
+        if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
+            render_app.init_resource::<SkyStateBuffer>().add_systems(
+                Render,
+                write_sky_state_buffer.in_set(RenderSystems::PrepareResources),
+            );
+        }
+    }
+}
+
+/// Sky state of the [`PrimarySky`](crate::primary_sky::PrimarySky) as laid out in the shader:
+///
+/// ```wgsl
+/// struct SkyState {
+///     sun_direction: vec4<f32>,
+///     sun_color: vec4<f32>,
+///     moon_direction: vec4<f32>,
+///     fog_tint: vec4<f32>,
+///     time: vec4<f32>,
+/// }
+/// ```
+#[derive(Resource, ExtractResource, ShaderType, Debug, Default, Clone, Copy)]
+pub struct SkyStateUniform {
+    /// xyz: world direction towards the sun, w: sun altitude in degrees.
+    pub sun_direction: Vec4,
+    /// rgb: linear color of the sun light, w: its illuminance in lux.
+    pub sun_color: Vec4,
+    /// xyz: world direction towards the moon, w: lit fraction of the moon disk.
+    /// Zero without a `MoonCenter`.
+    pub moon_direction: Vec4,
+    /// rgb: linear fog tint from [`SkyFogTint`], w: daylight factor (0.0 night, 1.0 day).
+    pub fog_tint: Vec4,
+    /// x: hour fraction, y: year fraction, z: moon phase angle in degrees, w: ambient
+    /// brightness.
+    pub time: Vec4,
+}
+
+/// Fog color published in [`SkyStateUniform::fog_tint`], blended by the daylight.
+#[derive(Resource, Debug, Clone)]
+pub struct SkyFogTint {
+    pub day_color: Color,
+    pub night_color: Color,
+}
+
+impl Default for SkyFogTint {
+    fn default() -> Self {
+        Self {
+            day_color: Color::srgb(0.7, 0.8, 0.95),
+            night_color: Color::srgb(0.02, 0.03, 0.08),
+        }
+    }
+}
+
+/// GPU buffer holding the [`SkyStateUniform`], in the render world. Bind it with
+/// `SkyStateBuffer::buffer.binding()`.
+#[derive(Resource, Default)]
+pub struct SkyStateBuffer {
+    pub buffer: UniformBuffer<SkyStateUniform>,
+}
+
+fn update_sky_state_uniform(
+    fog_tint: Res<SkyFogTint>,
+    ambient_light: Option<Res<GlobalAmbientLight>>,
+    mut sky_state: ResMut<SkyStateUniform>,
+    primary_sky: PrimarySkyQuery,
+    q_sky_center: Query<(
+        &SkyCenter,
+        &SunState,
+        Option<&MoonCenter>,
+        Option<&MoonPhase>,
+    )>,
+    q_light: Query<(&GlobalTransform, &DirectionalLight)>,
+) {
+    let Some((sky_center, sun_state, moon_center, moon_phase)) = primary_sky
+        .entity()
+        .and_then(|sky| q_sky_center.get(sky).ok())
+    else {
+        return;
+    };
+
+    let mut state = SkyStateUniform::default();
+    if let Ok((transform, light)) = q_light.get(sky_center.sun) {
+        // Lights shine along their forward axis, away from the sun
+        state.sun_direction = (-transform.forward().as_vec3()).extend(sun_state.altitude_deg);
+        state.sun_color = light.color.to_linear().to_vec3().extend(light.illuminance);
+    }
+    if let Some(moon_center) = moon_center
+        && let Ok((transform, _)) = q_light.get(moon_center.moon)
+    {
+        let lit_fraction = moon_phase.map_or(0.0, |phase| phase.illuminated_fraction);
+        state.moon_direction = (-transform.forward().as_vec3()).extend(lit_fraction);
+    }
+
+    let daylight = daylight_factor(sun_state.direction);
+    let fog_color = fog_tint.night_color.mix(&fog_tint.day_color, daylight);
+    state.fog_tint = fog_color.to_linear().to_vec3().extend(daylight);
+    state.time = Vec4::new(
+        sky_center.current_cycle_time / sky_center.cycle_duration_secs,
+        sky_center.year_fraction,
+        moon_phase.map_or(0.0, |phase| phase.phase_angle_deg),
+        ambient_light.map_or(0.0, |ambient_light| ambient_light.brightness),
+    );
+
+    *sky_state = state;
+}
+
+fn write_sky_state_buffer(
+    sky_state: Res<SkyStateUniform>,
+    mut sky_state_buffer: ResMut<SkyStateBuffer>,
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+) {
+    sky_state_buffer.buffer.set(*sky_state);
+    sky_state_buffer
+        .buffer
+        .write_buffer(&render_device, &render_queue);
+}
@@ -0,0 +1,96 @@
+// Measured per-cycle statistics of the running sky, to confirm in tests or live telemetry
+// that a solver-produced config really gives the requested day length and sun height.
+
+use bevy::prelude::*;
+
+use crate::{SkyCenter, SunMoveSet, SunState, primary_sky::PrimarySkyQuery};
+
+pub struct SkyStatsPlugin;
+
+impl Plugin for SkyStatsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SkyStats>();
+        app.add_systems(PostUpdate, track_sky_stats.after(SunMoveSet));
+    }
+}
+
+/// Sampled once per frame, so the precision depends on the frame rate.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CycleStats {
+    /// Sky time spent with the sun above the horizon (seconds).
+    pub day_length_secs: f32,
+    /// Sky time spent with the sun below the horizon (seconds).
+    pub night_length_secs: f32,
+    pub min_altitude_deg: f32,
+    pub max_altitude_deg: f32,
+}
+
+impl Default for CycleStats {
+    fn default() -> Self {
+        Self {
+            day_length_secs: 0.0,
+            night_length_secs: 0.0,
+            min_altitude_deg: f32::INFINITY,
+            max_altitude_deg: f32::NEG_INFINITY,
+        }
+    }
+}
+
+/// Statistics of the [`PrimarySky`](crate::primary_sky::PrimarySky). A cycle completes when
+/// the cycle time wraps at midnight.
+#[derive(Resource, Debug, Default, Clone)]
+pub struct SkyStats {
+    /// Last fully observed cycle.
+    pub last_cycle: Option<CycleStats>,
+    /// The cycle in progress.
+    pub current_cycle: CycleStats,
+    /// Cycles completed since tracking started (the first, partial cycle isn't counted).
+    pub completed_cycles: u32,
+    last_cycle_time: Option<f32>,
+    observed_full_cycle: bool,
+}
+
+impl SkyStats {
+    /// Starts measuring from scratch, e.g. after changing the sky config.
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+fn track_sky_stats(
+    mut stats: ResMut<SkyStats>,
+    primary_sky: PrimarySkyQuery,
+    q_sky_center: Query<(&SkyCenter, &SunState)>,
+) {
+    let Some((sky_center, sun_state)) = primary_sky
+        .entity()
+        .and_then(|sky| q_sky_center.get(sky).ok())
+    else {
+        return;
+    };
+
+    if let Some(last_cycle_time) = stats.last_cycle_time {
+        let delta = sky_center.current_cycle_time - last_cycle_time;
+        if delta < 0.0 {
+            // Wrapped at midnight (or the clock was set back): the cycle is over
+            let finished = std::mem::take(&mut stats.current_cycle);
+            if stats.observed_full_cycle {
+                stats.last_cycle = Some(finished);
+                stats.completed_cycles += 1;
+            }
+            stats.observed_full_cycle = true;
+        }
+
+        let sky_delta = delta.rem_euclid(sky_center.cycle_duration_secs);
+        if sun_state.altitude_deg > 0.0 {
+            stats.current_cycle.day_length_secs += sky_delta;
+        } else {
+            stats.current_cycle.night_length_secs += sky_delta;
+        }
+    }
+    stats.last_cycle_time = Some(sky_center.current_cycle_time);
+
+    let current = &mut stats.current_cycle;
+    current.min_altitude_deg = current.min_altitude_deg.min(sun_state.altitude_deg);
+    current.max_altitude_deg = current.max_altitude_deg.max(sun_state.altitude_deg);
+}
@@ -0,0 +1,152 @@
+// Observer hooks on the sky entity: sunrise, sunset, solar noon and altitude crossings are
+// triggered as entity events on their `SkyCenter`, for gameplay reacting without polling,
+// e.g. `commands.entity(sky).observe(|sunrise: On<OnSunrise>| ...)`.
+
+use bevy::{platform::collections::HashMap, prelude::*};
+
+use crate::{
+    SkyCatchUpSettings, SkyCenter, SkyTimestamp, SunMoveSet, SunriseEvent, SunsetEvent,
+    times_passed,
+};
+
+pub struct SkyTriggersPlugin;
+
+impl Plugin for SkyTriggersPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<SunAltitudeTriggers>();
+        // The sky clock is advanced in Update, so read it afterwards.
+        app.add_systems(
+            PostUpdate,
+            (trigger_sunrise_and_sunset, trigger_noon_and_altitudes).after(SunMoveSet),
+        );
+    }
+}
+
+/// Triggered on the `SkyCenter` for every [`SunriseEvent`].
+#[derive(EntityEvent, Debug, Clone, Copy)]
+pub struct OnSunrise {
+    #[event_target]
+    pub sky: Entity,
+    pub at: SkyTimestamp,
+}
+
+/// Triggered on the `SkyCenter` for every [`SunsetEvent`].
+#[derive(EntityEvent, Debug, Clone, Copy)]
+pub struct OnSunset {
+    #[event_target]
+    pub sky: Entity,
+    pub at: SkyTimestamp,
+}
+
+/// Triggered on the `SkyCenter` when the sun culminates (solar hour fraction 0.5).
+#[derive(EntityEvent, Debug, Clone, Copy)]
+pub struct OnSolarNoon {
+    #[event_target]
+    pub sky: Entity,
+    pub at: SkyTimestamp,
+}
+
+/// Triggered on the `SkyCenter` when the sun crosses one of its [`SunAltitudeTriggers`].
+#[derive(EntityEvent, Debug, Clone, Copy)]
+pub struct OnSunAltitudeCrossed {
+    #[event_target]
+    pub sky: Entity,
+    pub threshold_deg: f32,
+    /// Crossed going up (morning), `false` going down (evening).
+    pub rising: bool,
+    pub at: SkyTimestamp,
+}
+
+/// Sun altitudes (degrees) triggering [`OnSunAltitudeCrossed`] on this `SkyCenter`, e.g.
+/// `-6.0` for civil dusk and dawn or `30.0` for the hottest part of the day.
+#[derive(Component, Reflect, Debug, Default, Clone, PartialEq)]
+#[reflect(Component, Default, Debug)]
+pub struct SunAltitudeTriggers {
+    pub thresholds_deg: Vec<f32>,
+}
+
+fn trigger_sunrise_and_sunset(
+    mut commands: Commands,
+    mut sunrise_events: MessageReader<SunriseEvent>,
+    mut sunset_events: MessageReader<SunsetEvent>,
+) {
+    for event in sunrise_events.read() {
+        commands.trigger(OnSunrise {
+            sky: event.sky,
+            at: event.at,
+        });
+    }
+    for event in sunset_events.read() {
+        commands.trigger(OnSunset {
+            sky: event.sky,
+            at: event.at,
+        });
+    }
+}
+
+/// Crossings of this frame, in the order they happened. Like the sunrise and sunset
+/// events, none are sent for catch-ups and rewinds.
+fn trigger_noon_and_altitudes(
+    mut commands: Commands,
+    q_sky_center: Query<(Entity, &SkyCenter, Option<&SunAltitudeTriggers>)>,
+    catch_up_settings: Res<SkyCatchUpSettings>,
+    mut last_positions: Local<HashMap<Entity, (u64, f32)>>,
+    time: Res<Time>,
+) {
+    for (entity, sky_center, altitude_triggers) in q_sky_center.iter() {
+        let hour_fraction = sky_center.current_cycle_time / sky_center.cycle_duration_secs;
+        let Some((previous_day_index, previous_hour_fraction)) =
+            last_positions.insert(entity, (sky_center.day_index, hour_fraction))
+        else {
+            continue;
+        };
+        let delta_fraction = (sky_center.day_index as f32 - previous_day_index as f32)
+            + (hour_fraction - previous_hour_fraction);
+        if delta_fraction <= 0.0 || delta_fraction >= catch_up_settings.threshold_cycles {
+            continue;
+        }
+
+        // (cycles after the frame start, threshold and rising, `None` for noon)
+        let mut crossings: Vec<(f32, Option<(f32, bool)>)> = Vec::new();
+        let mut add_crossings = |crossing: f32, kind: Option<(f32, bool)>| {
+            let first = (crossing - previous_hour_fraction).rem_euclid(1.0);
+            let count = times_passed(crossing, previous_hour_fraction, delta_fraction);
+            crossings.extend((0..count).map(|k| (first + k as f32, kind)));
+        };
+        add_crossings(
+            (0.5 - sky_center.equation_of_time_offset()).rem_euclid(1.0),
+            None,
+        );
+        for &threshold_deg in altitude_triggers
+            .map(|triggers| triggers.thresholds_deg.as_slice())
+            .unwrap_or_default()
+        {
+            if let [rising, setting] = sky_center.times_at_altitude(threshold_deg).as_slice() {
+                add_crossings(*rising, Some((threshold_deg, true)));
+                add_crossings(*setting, Some((threshold_deg, false)));
+            }
+        }
+        crossings.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        for (cycles_after_start, kind) in crossings {
+            let position = previous_hour_fraction + cycles_after_start;
+            let hour_fraction = position.rem_euclid(1.0);
+            let at = SkyTimestamp {
+                cycle_time_secs: hour_fraction * sky_center.cycle_duration_secs,
+                hour_fraction,
+                day_index: previous_day_index + position.floor().max(0.0) as u64,
+                elapsed_secs: time.elapsed_secs(),
+            };
+            match kind {
+                None => commands.trigger(OnSolarNoon { sky: entity, at }),
+                Some((threshold_deg, rising)) => commands.trigger(OnSunAltitudeCrossed {
+                    sky: entity,
+                    threshold_deg,
+                    rising,
+                    at,
+                }),
+            }
+        }
+    }
+    last_positions.retain(|entity, _| q_sky_center.contains(*entity));
+}
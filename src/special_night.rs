@@ -0,0 +1,310 @@
+// Scripted special nights (blood moons, long nights) for survival games: every few days, or
+// on demand, the next night plays slower with a tinted moon and brighter stars, and the
+// sky is restored at dawn.
+
+use bevy::prelude::*;
+
+use crate::{
+    MoonCenter, SkyCenter, SkyTimestamp, SunMoveSet,
+    random_stars::StarBrightness,
+    time_of_day::{TimeOfDayPhase, TimeOfDayPhaseChanged},
+};
+
+pub struct SpecialNightPlugin;
+
+impl Plugin for SpecialNightPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<SpecialNight>();
+        app.add_message::<SpecialNightStarted>()
+            .add_message::<SpecialNightEnded>();
+        // Phase changes are sent in Update, so read them afterwards.
+        app.add_systems(PostUpdate, run_special_nights.after(SunMoveSet));
+    }
+}
+
+/// Makes some nights of this `SkyCenter` special: from the start of `Night` to the next
+/// `Dawn`, the night clock runs `night_time_scale` times as fast, the moon light is tinted
+/// `moon_color` and the stars are `star_brightness` times as bright. Everything is restored
+/// when the night ends.
+#[derive(Component, Reflect, Debug, Clone, PartialEq)]
+#[reflect(Component, Default, Debug)]
+#[require(SkyCenter)]
+pub struct SpecialNight {
+    /// Every this many days the night is special, `None` for on demand only.
+    pub every_days: Option<u32>,
+    /// `SkyCenter::day_index` of the first scheduled special night.
+    pub first_day: u64,
+    /// Makes the next night special, scheduled or not. Cleared when it starts.
+    pub next_night: bool,
+    /// Multiplier of the night clock speed, e.g. 0.5 for a night twice as long.
+    pub night_time_scale: f32,
+    /// Moon light color during the night, `None` to keep it (needs a `MoonCenter`).
+    pub moon_color: Option<Color>,
+    /// Multiplier of the star brightness (needs a `StarSpawner`).
+    pub star_brightness: f32,
+}
+
+impl Default for SpecialNight {
+    fn default() -> Self {
+        Self::blood_moon(7)
+    }
+}
+
+impl SpecialNight {
+    /// A long night with a red moon every `every_days` days.
+    pub fn blood_moon(every_days: u32) -> Self {
+        Self {
+            every_days: Some(every_days),
+            first_day: every_days.saturating_sub(1) as u64,
+            next_night: false,
+            night_time_scale: 0.5,
+            moon_color: Some(Color::srgb(1.0, 0.25, 0.15)),
+            star_brightness: 1.5,
+        }
+    }
+
+    /// Whether the night starting on `day_index` is special, on demand or scheduled.
+    pub fn is_special(&self, day_index: u64) -> bool {
+        self.next_night
+            || self.every_days.is_some_and(|every_days| {
+                every_days > 0
+                    && day_index >= self.first_day
+                    && (day_index - self.first_day) % every_days as u64 == 0
+            })
+    }
+}
+
+/// The sky settings replaced during a special night, restored when it ends unless they
+/// were changed in the meantime. Inserted on the `SkyCenter` while the night lasts.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct ActiveSpecialNight {
+    night_time_scale: f32,
+    moon_color: Option<Color>,
+    star_brightness: Option<StarBrightness>,
+    /// What the night set, to tell its own settings from later changes
+    special_night_time_scale: f32,
+    special_moon_color: Option<Color>,
+    special_star_brightness: StarBrightness,
+}
+
+#[derive(Message, Debug, Clone, Copy)]
+pub struct SpecialNightStarted {
+    pub sky: Entity,
+    pub at: SkyTimestamp,
+}
+
+#[derive(Message, Debug, Clone, Copy)]
+pub struct SpecialNightEnded {
+    pub sky: Entity,
+    pub at: SkyTimestamp,
+}
+
+fn run_special_nights(
+    mut commands: Commands,
+    mut phase_changes: MessageReader<TimeOfDayPhaseChanged>,
+    mut started_events: MessageWriter<SpecialNightStarted>,
+    mut ended_events: MessageWriter<SpecialNightEnded>,
+    mut q_sky_center: Query<(
+        &mut SkyCenter,
+        &mut SpecialNight,
+        Option<&ActiveSpecialNight>,
+        Option<&MoonCenter>,
+        Option<&StarBrightness>,
+    )>,
+    mut q_lights: Query<&mut DirectionalLight>,
+) {
+    for event in phase_changes.read() {
+        let Ok((mut sky_center, mut special_night, active, moon_center, star_brightness)) =
+            q_sky_center.get_mut(event.sky)
+        else {
+            continue;
+        };
+        let mut moon_light =
+            moon_center.and_then(|moon_center| q_lights.get_mut(moon_center.moon).ok());
+
+        match (event.to, active) {
+            (TimeOfDayPhase::Night, None) if special_night.is_special(event.at.day_index) => {
+                special_night.next_night = false;
+                let night_time_scale = sky_center.phase_time_scales.night;
+                sky_center.phase_time_scales.night *= special_night.night_time_scale;
+                let moon_color = moon_light.as_ref().map(|light| light.color);
+                let mut special_moon_color = moon_color;
+                if let (Some(light), Some(color)) = (moon_light.as_mut(), special_night.moon_color)
+                {
+                    light.color = color;
+                    special_moon_color = Some(color);
+                }
+                let brightness = star_brightness.map_or(1.0, |brightness| brightness.0);
+                let special_star_brightness =
+                    StarBrightness(brightness * special_night.star_brightness);
+                commands.entity(event.sky).insert((
+                    ActiveSpecialNight {
+                        night_time_scale,
+                        moon_color,
+                        star_brightness: star_brightness.copied(),
+                        special_night_time_scale: sky_center.phase_time_scales.night,
+                        special_moon_color,
+                        special_star_brightness,
+                    },
+                    special_star_brightness,
+                ));
+                started_events.write(SpecialNightStarted {
+                    sky: event.sky,
+                    at: event.at,
+                });
+            }
+            // Also when the night is skipped back to dusk
+            // Settings changed during the night are kept
+            (to, Some(saved)) if to != TimeOfDayPhase::Night => {
+                if sky_center.phase_time_scales.night == saved.special_night_time_scale {
+                    sky_center.phase_time_scales.night = saved.night_time_scale;
+                }
+                if let (Some(light), Some(color)) = (moon_light.as_mut(), saved.moon_color)
+                    && Some(light.color) == saved.special_moon_color
+                {
+                    light.color = color;
+                }
+                let mut entity_commands = commands.entity(event.sky);
+                entity_commands.remove::<ActiveSpecialNight>();
+                if star_brightness == Some(&saved.special_star_brightness) {
+                    match saved.star_brightness {
+                        Some(brightness) => entity_commands.insert(brightness),
+                        None => entity_commands.remove::<StarBrightness>(),
+                    };
+                }
+                ended_events.write(SpecialNightEnded {
+                    sky: event.sky,
+                    at: event.at,
+                });
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy::time::TimeUpdateStrategy;
+
+    use super::*;
+    use crate::SunMovePlugin;
+
+    /// `(started, day_index)` of the special night messages, in order.
+    #[derive(Resource, Default)]
+    struct SpecialNightLog(Vec<(bool, u64)>);
+
+    fn log_special_nights(
+        mut log: ResMut<SpecialNightLog>,
+        mut started: MessageReader<SpecialNightStarted>,
+        mut ended: MessageReader<SpecialNightEnded>,
+    ) {
+        // Never both in one frame, a night lasts longer
+        for event in started.read() {
+            log.0.push((true, event.at.day_index));
+        }
+        for event in ended.read() {
+            log.0.push((false, event.at.day_index));
+        }
+    }
+
+    #[test]
+    fn scheduled_nights_start_end_and_restore() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, SunMovePlugin::default(), SpecialNightPlugin))
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+                100,
+            )))
+            .init_resource::<SpecialNightLog>()
+            .add_systems(Last, log_special_nights);
+        let sun = app.world_mut().spawn(Transform::default()).id();
+        let moon = app
+            .world_mut()
+            .spawn((Transform::default(), DirectionalLight::default()))
+            .id();
+        let sky = app
+            .world_mut()
+            .spawn((
+                SkyCenter {
+                    sun,
+                    cycle_duration_secs: 10.0,
+                    ..default()
+                },
+                MoonCenter {
+                    moon,
+                    full_moon_illuminance: None,
+                    ..default()
+                },
+                SpecialNight::blood_moon(2),
+            ))
+            .id();
+        let moon_color = app.world().get::<DirectionalLight>(moon).unwrap().color;
+
+        let mut changed_during_night = false;
+        for _ in 0..2000 {
+            app.update();
+            let world = app.world_mut();
+            let day_index = world.get::<SkyCenter>(sky).unwrap().day_index;
+            if day_index >= 7 {
+                break;
+            }
+            // The user slows down the nights during the second special night
+            if day_index == 3 && world.get::<ActiveSpecialNight>(sky).is_some() {
+                let mut sky_center = world.get_mut::<SkyCenter>(sky).unwrap();
+                if !changed_during_night {
+                    assert_eq!(sky_center.phase_time_scales.night, 0.5);
+                    sky_center.phase_time_scales.night = 2.0;
+                    changed_during_night = true;
+                }
+            }
+        }
+
+        let world = app.world();
+        assert_eq!(world.get::<SkyCenter>(sky).unwrap().day_index, 7);
+        assert!(changed_during_night);
+        assert_eq!(
+            world.resource::<SpecialNightLog>().0,
+            [
+                (true, 1),
+                (false, 2),
+                (true, 3),
+                (false, 4),
+                (true, 5),
+                (false, 6)
+            ]
+        );
+        assert!(world.get::<ActiveSpecialNight>(sky).is_none());
+        assert!(world.get::<StarBrightness>(sky).is_none());
+        assert_eq!(
+            world.get::<DirectionalLight>(moon).unwrap().color,
+            moon_color
+        );
+        // Kept over the restore, then scaled and restored by the third night
+        assert_eq!(
+            world.get::<SkyCenter>(sky).unwrap().phase_time_scales.night,
+            2.0
+        );
+    }
+
+    #[test]
+    fn on_demand_and_scheduled_nights() {
+        let scheduled = SpecialNight::blood_moon(2);
+        let special_days: Vec<u64> = (0..8).filter(|day| scheduled.is_special(*day)).collect();
+        assert_eq!(special_days, [1, 3, 5, 7]);
+
+        let on_demand = SpecialNight {
+            every_days: None,
+            next_night: true,
+            ..scheduled
+        };
+        assert!(on_demand.is_special(0));
+        assert!(
+            !SpecialNight {
+                next_night: false,
+                ..on_demand
+            }
+            .is_special(0)
+        );
+    }
+}
@@ -0,0 +1,107 @@
+// Per-frame memo of sun positions, for servers hosting many matches whose skies share
+// the same config: skies with the same latitude, tilt, season and time of day compute
+// the sun position once.
+
+use bevy::{platform::collections::HashMap, prelude::*};
+
+use crate::{
+    RADIANS_TO_DEGREES, calculate_declination, calculate_noon_azimuth_deg, calculate_sun_direction,
+};
+
+/// Inputs are rounded to this many steps per radian (or per cycle for the hour fraction),
+/// far below anything visible.
+const STEPS: f32 = 1e6;
+
+/// Sun position shared by every sky with the same inputs.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CachedSunPosition {
+    /// Direction towards the sun (X east, Y up, Z north).
+    pub direction: Vec3,
+    pub altitude_deg: f32,
+    pub azimuth_deg: f32,
+    pub noon_azimuth_deg: f32,
+}
+
+impl CachedSunPosition {
+    /// Computes the position without any cache, see [`calculate_sun_direction`].
+    pub fn compute(
+        solar_hour_fraction: f32,
+        latitude_rad: f32,
+        axial_tilt_rad: f32,
+        year_fraction: f32,
+    ) -> Self {
+        let direction = calculate_sun_direction(
+            solar_hour_fraction,
+            latitude_rad,
+            axial_tilt_rad,
+            year_fraction,
+        );
+        Self {
+            direction,
+            altitude_deg: direction.y.clamp(-1.0, 1.0).asin() * RADIANS_TO_DEGREES,
+            azimuth_deg: (direction.x.atan2(direction.z) * RADIANS_TO_DEGREES).rem_euclid(360.0),
+            noon_azimuth_deg: calculate_noon_azimuth_deg(
+                latitude_rad,
+                calculate_declination(axial_tilt_rad, year_fraction),
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct SunPositionKey {
+    solar_hour_fraction: i32,
+    latitude: i32,
+    tilt: i32,
+    year_fraction: i32,
+}
+
+/// Sun positions computed during the current frame, cleared every time the skies move.
+#[derive(Resource, Debug, Default)]
+pub struct SunPositionCache {
+    positions: HashMap<SunPositionKey, CachedSunPosition>,
+    /// Lookups answered from the cache since it was last cleared.
+    pub hits: u32,
+    /// Lookups that computed a new position since it was last cleared.
+    pub misses: u32,
+}
+
+impl SunPositionCache {
+    pub fn clear(&mut self) {
+        self.positions.clear();
+        self.hits = 0;
+        self.misses = 0;
+    }
+
+    /// Sun position for a solar hour fraction, see [`calculate_sun_direction`]. `year_fraction`
+    /// is the season one (see `SkyCenter::season_year_fraction`).
+    pub fn sun_position(
+        &mut self,
+        solar_hour_fraction: f32,
+        latitude_rad: f32,
+        axial_tilt_rad: f32,
+        year_fraction: f32,
+    ) -> CachedSunPosition {
+        let quantize = |value: f32| (value * STEPS).round() as i32;
+        let key = SunPositionKey {
+            solar_hour_fraction: quantize(solar_hour_fraction.rem_euclid(1.0)),
+            latitude: quantize(latitude_rad),
+            tilt: quantize(axial_tilt_rad),
+            year_fraction: quantize(year_fraction.rem_euclid(1.0)),
+        };
+        if let Some(position) = self.positions.get(&key) {
+            self.hits += 1;
+            return *position;
+        }
+        self.misses += 1;
+
+        let position = CachedSunPosition::compute(
+            solar_hour_fraction,
+            latitude_rad,
+            axial_tilt_rad,
+            year_fraction,
+        );
+        self.positions.insert(key, position);
+        position
+    }
+}
@@ -0,0 +1,139 @@
+// Sky from photo references: fits latitude and season to a few sun directions observed at
+// known times of day (e.g. read from a set of reference HDRIs), so artists can replicate
+// real lighting.
+
+use bevy::prelude::*;
+
+use crate::{
+    DEGREES_TO_RADIANS, RADIANS_TO_DEGREES, SkyCenter, calculate_direction_from_hour_angle,
+    year_fraction_from_declination, year_fraction_from_orbital,
+};
+
+/// A sun direction observed at a known time of day.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SunSample {
+    /// Solar hour fraction of the observation (0.0 midnight, 0.5 noon).
+    pub hour_fraction: f32,
+    /// Direction towards the sun (X east, Y up, Z north).
+    pub direction: Vec3,
+}
+
+/// The sky best matching a set of [`SunSample`]s, see [`SkyCenter::fit_sun_samples`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SunSampleFit {
+    pub latitude_degrees: f32,
+    pub declination_deg: f32,
+    /// Year fraction with this declination, the one closest to the sky's season (and
+    /// shifted by its orbital eccentricity).
+    pub year_fraction: f32,
+    /// Root mean square angle between the samples and the fitted sun, in degrees.
+    pub rms_error_deg: f32,
+}
+
+impl SunSampleFit {
+    /// Moves `sky_center` to the fitted latitude and season.
+    pub fn apply_to(&self, sky_center: &mut SkyCenter) {
+        sky_center.latitude_degrees = self.latitude_degrees;
+        sky_center.year_fraction = self.year_fraction;
+    }
+}
+
+/// Mean squared angle (radians) between the samples and the sun at `latitude_deg` and
+/// `declination_deg`.
+fn mean_squared_error(samples: &[SunSample], latitude_deg: f32, declination_deg: f32) -> f32 {
+    let latitude_rad = latitude_deg * DEGREES_TO_RADIANS;
+    let dec_rad = declination_deg * DEGREES_TO_RADIANS;
+    samples
+        .iter()
+        .map(|sample| {
+            let model = calculate_direction_from_hour_angle(
+                sample.hour_fraction * std::f32::consts::TAU - std::f32::consts::PI,
+                latitude_rad,
+                dec_rad,
+            );
+            model.angle_between(sample.direction).powi(2)
+        })
+        .sum::<f32>()
+        / samples.len() as f32
+}
+
+impl SkyCenter {
+    /// Latitude and season whose sun best matches `samples` (least squares on the angle),
+    /// for the planet tilt of this sky. The declination is searched within the tilt, so
+    /// samples from further north or south than the tilt allows fit the closest season.
+    ///
+    /// Returns `None` without samples (or with zero-length directions). Two samples at
+    /// different times of day are usually enough; more average out measuring errors.
+    pub fn fit_sun_samples(&self, samples: &[SunSample]) -> Option<SunSampleFit> {
+        let samples: Vec<SunSample> = samples
+            .iter()
+            .filter_map(|sample| {
+                sample.direction.try_normalize().map(|direction| SunSample {
+                    direction,
+                    ..*sample
+                })
+            })
+            .collect();
+        if samples.is_empty() {
+            return None;
+        }
+        let max_declination_deg = self.planet_tilt_degrees.abs().min(90.0);
+
+        // Coarse grid, then zoom in around the best cell
+        let mut best = (0.0, 0.0, f32::INFINITY);
+        let mut step = 2.0;
+        let mut center = (0.0, 0.0);
+        let mut radius = (90.0, max_declination_deg);
+        for _ in 0..8 {
+            let lat_steps = (radius.0 / step).ceil() as i32;
+            let dec_steps = (radius.1 / step).ceil() as i32;
+            for i in -lat_steps..=lat_steps {
+                for j in -dec_steps..=dec_steps {
+                    let latitude_deg = (center.0 + i as f32 * step).clamp(-90.0, 90.0);
+                    let declination_deg = (center.1 + j as f32 * step)
+                        .clamp(-max_declination_deg, max_declination_deg);
+                    let error = mean_squared_error(&samples, latitude_deg, declination_deg);
+                    if error < best.2 {
+                        best = (latitude_deg, declination_deg, error);
+                    }
+                }
+            }
+            center = (best.0, best.1);
+            radius = (step * 2.0, step * 2.0);
+            step *= 0.25;
+        }
+
+        let (latitude_degrees, declination_deg, error) = best;
+        let year_fraction = year_fraction_from_declination(
+            declination_deg * DEGREES_TO_RADIANS,
+            self.planet_tilt_degrees * DEGREES_TO_RADIANS,
+            self.season_year_fraction(),
+        )
+        .map_or(self.year_fraction, |season_year_fraction| {
+            year_fraction_from_orbital(
+                season_year_fraction,
+                self.orbital_eccentricity,
+                self.perihelion_year_fraction,
+            )
+        });
+        Some(SunSampleFit {
+            latitude_degrees,
+            declination_deg,
+            year_fraction,
+            rms_error_deg: error.sqrt() * RADIANS_TO_DEGREES,
+        })
+    }
+
+    /// A sky (with the default settings and `planet_tilt_degrees`) whose sun best matches
+    /// `samples`, see [`SkyCenter::fit_sun_samples`].
+    pub fn from_sun_samples(samples: &[SunSample], planet_tilt_degrees: f32) -> Option<Self> {
+        let mut sky_center = SkyCenter {
+            planet_tilt_degrees,
+            ..default()
+        };
+        sky_center
+            .fit_sun_samples(samples)?
+            .apply_to(&mut sky_center);
+        Some(sky_center)
+    }
+}
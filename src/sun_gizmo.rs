@@ -0,0 +1,177 @@
+// Dev-build sun handle: draws the sun direction as a gizmo and lets you drag it
+// with the mouse. The drag is back-solved into SkyCenter time (and optionally season),
+// so the sun always stays on a path the astronomical model can produce.
+
+use bevy::{prelude::*, window::PrimaryWindow};
+
+use crate::{
+    DEGREES_TO_RADIANS, SkyCenter, SunMoveSet, declination_from_direction,
+    hour_fraction_from_direction, year_fraction_from_declination,
+};
+
+pub struct SunGizmoPlugin;
+
+impl Plugin for SunGizmoPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SunGizmoSettings>();
+        app.init_resource::<SunGizmoDrag>();
+        // The dragged time is applied by the sky the same frame, and the handle drawn on
+        // the moved sun
+        app.add_systems(
+            Update,
+            (
+                drag_sun_gizmo.before(SunMoveSet),
+                draw_sun_gizmo.after(SunMoveSet),
+            )
+                .chain(),
+        );
+    }
+}
+
+#[derive(Resource, Debug, Clone)]
+pub struct SunGizmoSettings {
+    /// Distance from the camera at which the handle is drawn.
+    pub distance: f32,
+    /// Visual radius of the handle.
+    pub radius: f32,
+    /// Angular tolerance (degrees) between the cursor ray and the sun direction to grab the handle.
+    pub pick_angle_deg: f32,
+    /// Also change `year_fraction` so the sun can leave its current daily path.
+    pub solve_year_fraction: bool,
+    pub color: Color,
+    pub drag_color: Color,
+}
+
+impl Default for SunGizmoSettings {
+    fn default() -> Self {
+        Self {
+            distance: 10.0,
+            radius: 0.3,
+            pick_angle_deg: 3.0,
+            solve_year_fraction: false,
+            color: Color::srgb(1.0, 0.85, 0.2),
+            drag_color: Color::srgb(1.0, 0.4, 0.1),
+        }
+    }
+}
+
+/// The `SkyCenter` entity whose sun is currently being dragged.
+#[derive(Resource, Default, Debug)]
+pub struct SunGizmoDrag {
+    pub sky: Option<Entity>,
+}
+
+fn active_camera<'a>(
+    q_camera: &'a Query<(&Camera, &GlobalTransform), With<Camera3d>>,
+) -> Option<(&'a Camera, &'a GlobalTransform)> {
+    q_camera.iter().find(|(camera, _)| camera.is_active)
+}
+
+fn drag_sun_gizmo(
+    mut drag: ResMut<SunGizmoDrag>,
+    settings: Res<SunGizmoSettings>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    q_window: Query<&Window, With<PrimaryWindow>>,
+    q_camera: Query<(&Camera, &GlobalTransform), With<Camera3d>>,
+    mut q_sky_center: Query<(Entity, &mut SkyCenter)>,
+    q_transforms: Query<&Transform, Without<SkyCenter>>,
+) {
+    if !mouse.pressed(MouseButton::Left) {
+        drag.sky = None;
+        return;
+    }
+
+    let Ok(window) = q_window.single() else {
+        return;
+    };
+    let Some(cursor) = window.cursor_position() else {
+        return;
+    };
+    let Some((camera, camera_transform)) = active_camera(&q_camera) else {
+        return;
+    };
+    let Ok(ray) = camera.viewport_to_world(camera_transform, cursor) else {
+        return;
+    };
+    let ray_direction = *ray.direction;
+
+    if mouse.just_pressed(MouseButton::Left) {
+        // Grab the sun closest to the cursor ray, if within tolerance
+        let pick_cos = (settings.pick_angle_deg * DEGREES_TO_RADIANS).cos();
+        drag.sky = q_sky_center
+            .iter()
+            .filter_map(|(entity, sky_center)| {
+                let sun_transform = q_transforms.get(sky_center.sun).ok()?;
+                let cos_angle = sun_transform
+                    .translation
+                    .normalize_or_zero()
+                    .dot(ray_direction);
+                (cos_angle >= pick_cos).then_some((entity, cos_angle))
+            })
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(entity, _)| entity);
+    }
+
+    let Some(sky_entity) = drag.sky else {
+        return;
+    };
+    let Ok((_, mut sky_center)) = q_sky_center.get_mut(sky_entity) else {
+        drag.sky = None;
+        return;
+    };
+
+    let latitude_rad = sky_center.latitude_degrees * DEGREES_TO_RADIANS;
+
+    if settings.solve_year_fraction {
+        let tilt_rad = sky_center.planet_tilt_degrees * DEGREES_TO_RADIANS;
+        // Clamp to the reachable band, so dragging past the solstice path sticks to it
+        let dec_rad = declination_from_direction(ray_direction, latitude_rad)
+            .clamp(-tilt_rad.abs(), tilt_rad.abs());
+        if let Some(season_year_fraction) =
+            year_fraction_from_declination(dec_rad, tilt_rad, sky_center.season_year_fraction())
+        {
+            sky_center.year_fraction = sky_center.year_fraction_for_season(season_year_fraction);
+        }
+    }
+
+    // The direction gives the solar time, the clock may lag behind it
+    let hour_fraction = (hour_fraction_from_direction(ray_direction, latitude_rad)
+        - sky_center.equation_of_time_offset())
+    .rem_euclid(1.0);
+    sky_center.current_cycle_time = hour_fraction * sky_center.cycle_duration_secs;
+}
+
+fn draw_sun_gizmo(
+    mut gizmos: Gizmos,
+    drag: Res<SunGizmoDrag>,
+    settings: Res<SunGizmoSettings>,
+    q_camera: Query<(&Camera, &GlobalTransform), With<Camera3d>>,
+    q_sky_center: Query<(Entity, &SkyCenter)>,
+    q_transforms: Query<&Transform>,
+) {
+    let Some((_, camera_transform)) = active_camera(&q_camera) else {
+        return;
+    };
+    let camera_position = camera_transform.translation();
+
+    for (entity, sky_center) in q_sky_center.iter() {
+        let Ok(sun_transform) = q_transforms.get(sky_center.sun) else {
+            continue;
+        };
+
+        // The sun is infinitely far away, so place the handle relative to the camera
+        let handle_position =
+            camera_position + sun_transform.translation.normalize_or_zero() * settings.distance;
+        let color = if drag.sky == Some(entity) {
+            settings.drag_color
+        } else {
+            settings.color
+        };
+
+        gizmos.sphere(
+            Isometry3d::from_translation(handle_position),
+            settings.radius,
+            color,
+        );
+    }
+}
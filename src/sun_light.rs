@@ -0,0 +1,143 @@
+// Opt-in modulation of the sun DirectionalLight from its altitude, so low suns look warm
+// even without the Atmosphere plugin and the light fades through twilight instead of
+// switching off at the horizon.
+
+use bevy::{light::light_consts::lux, math::curve::UnevenSampleAutoCurve, prelude::*};
+
+use crate::{SkyCenter, SunMoveSet, SunState};
+
+pub struct SunLightPlugin;
+
+impl Plugin for SunLightPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<SunColorTemperature>()
+            .register_type::<SunIlluminance>();
+        app.add_systems(
+            PostUpdate,
+            (update_sun_color, update_sun_illuminance).after(SunMoveSet),
+        );
+    }
+}
+
+/// Add to a `SkyCenter` entity to drive the color of its sun light from the sun altitude.
+#[derive(Component, Reflect, Debug, Clone)]
+#[reflect(Component, Default, Debug)]
+pub struct SunColorTemperature {
+    /// Color temperature in Kelvin by sun altitude in degrees, clamped outside its domain.
+    pub kelvin_by_altitude: UnevenSampleAutoCurve<f32>,
+}
+
+impl SunColorTemperature {
+    /// Gradient through `(altitude_deg, kelvin)` points. Needs at least two points.
+    pub fn from_points(points: impl IntoIterator<Item = (f32, f32)>) -> Option<Self> {
+        UnevenSampleAutoCurve::new(points)
+            .ok()
+            .map(|kelvin_by_altitude| Self { kelvin_by_altitude })
+    }
+
+    pub fn color_at(&self, altitude_deg: f32) -> Color {
+        color_from_temperature(self.kelvin_by_altitude.sample_clamped(altitude_deg))
+    }
+}
+
+impl Default for SunColorTemperature {
+    /// ~2000K at the horizon, neutral white from ~40° up.
+    fn default() -> Self {
+        Self::from_points([
+            (-2.0, 1800.0),
+            (0.0, 2000.0),
+            (5.0, 3000.0),
+            (15.0, 4500.0),
+            (40.0, 6500.0),
+        ])
+        .unwrap()
+    }
+}
+
+/// Add to a `SkyCenter` entity to drive the illuminance of its sun light from the altitude
+/// of the sun's upper limb, so the light starts with the first sliver at sunrise. With a
+/// `HorizonProfile` the altitude is measured above the skyline.
+#[derive(Component, Reflect, Debug, Clone)]
+#[reflect(Component, Default, Debug)]
+pub struct SunIlluminance {
+    /// Illuminance in lux by sun altitude in degrees, clamped outside its domain.
+    pub lux_by_altitude: UnevenSampleAutoCurve<f32>,
+}
+
+impl SunIlluminance {
+    /// Curve through `(altitude_deg, lux)` points. Needs at least two points.
+    pub fn from_points(points: impl IntoIterator<Item = (f32, f32)>) -> Option<Self> {
+        UnevenSampleAutoCurve::new(points)
+            .ok()
+            .map(|lux_by_altitude| Self { lux_by_altitude })
+    }
+
+    pub fn illuminance_at(&self, altitude_deg: f32) -> f32 {
+        self.lux_by_altitude.sample_clamped(altitude_deg).max(0.0)
+    }
+}
+
+impl Default for SunIlluminance {
+    /// Dark below -6° (civil twilight), `lux::RAW_SUNLIGHT` at the zenith.
+    fn default() -> Self {
+        Self::from_points([
+            (-6.0, 0.0),
+            (0.0, lux::CLEAR_SUNRISE),
+            (10.0, lux::FULL_DAYLIGHT),
+            (30.0, lux::DIRECT_SUNLIGHT),
+            (90.0, lux::RAW_SUNLIGHT),
+        ])
+        .unwrap()
+    }
+}
+
+/// Approximate sRGB color of a black body at `kelvin` (1000K to 40000K), normalized so
+/// 6500K is white.
+pub fn color_from_temperature(kelvin: f32) -> Color {
+    // Tanner Helland's fit of the black body spectrum
+    let t = kelvin.clamp(1000.0, 40000.0) / 100.0;
+    let red = if t <= 66.0 {
+        255.0
+    } else {
+        329.69873 * (t - 60.0).powf(-0.133_204_76)
+    };
+    let green = if t <= 66.0 {
+        99.470_8 * t.ln() - 161.119_57
+    } else {
+        288.122_16 * (t - 60.0).powf(-0.075_514_85)
+    };
+    let blue = if t >= 66.0 {
+        255.0
+    } else if t <= 19.0 {
+        0.0
+    } else {
+        138.517_73 * (t - 10.0).ln() - 305.044_8
+    };
+    Color::srgb(
+        (red / 255.0).clamp(0.0, 1.0),
+        (green / 255.0).clamp(0.0, 1.0),
+        (blue / 255.0).clamp(0.0, 1.0),
+    )
+}
+
+pub(crate) fn update_sun_color(
+    q_sky_center: Query<(&SkyCenter, &SunState, &SunColorTemperature)>,
+    mut q_light: Query<&mut DirectionalLight>,
+) {
+    for (sky_center, sun_state, temperature) in q_sky_center.iter() {
+        if let Ok(mut light) = q_light.get_mut(sky_center.sun) {
+            light.color = temperature.color_at(sun_state.altitude_deg);
+        }
+    }
+}
+
+fn update_sun_illuminance(
+    q_sky_center: Query<(&SkyCenter, &SunState, &SunIlluminance)>,
+    mut q_light: Query<&mut DirectionalLight>,
+) {
+    for (sky_center, sun_state, illuminance) in q_sky_center.iter() {
+        if let Ok(mut light) = q_light.get_mut(sky_center.sun) {
+            light.illuminance = illuminance.illuminance_at(sun_state.altitude_above_skyline_deg());
+        }
+    }
+}
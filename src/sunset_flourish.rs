@@ -0,0 +1,93 @@
+// Rare "green flash" hook: a message sent at the moment the sun's upper limb disappears,
+// for games to trigger a brief color flash, a sound or an achievement.
+
+use bevy::{platform::collections::HashMap, prelude::*};
+use rand::Rng;
+
+use crate::{SkyCenter, SkyTimestamp, SunMoveSet, horizon_profile::HorizonProfile, times_passed};
+
+pub struct SunsetFlourishPlugin;
+
+impl Plugin for SunsetFlourishPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SunsetFlourishSettings>();
+        app.add_message::<SunsetFlourish>();
+        // The sky clock is advanced in Update, so read it afterwards.
+        app.add_systems(PostUpdate, send_sunset_flourish.after(SunMoveSet));
+    }
+}
+
+#[derive(Resource, Debug, Clone)]
+pub struct SunsetFlourishSettings {
+    /// Chance of a flourish at each sunset (0.0 never, 1.0 every sunset).
+    pub probability: f32,
+}
+
+impl Default for SunsetFlourishSettings {
+    fn default() -> Self {
+        Self { probability: 0.05 }
+    }
+}
+
+/// Sent, with the probability of [`SunsetFlourishSettings`], when the sun's upper limb sets.
+#[derive(Message, Debug, Clone, Copy)]
+pub struct SunsetFlourish {
+    pub sky: Entity,
+    /// Exact moment the upper limb touched the horizon.
+    pub at: SkyTimestamp,
+}
+
+fn send_sunset_flourish(
+    settings: Res<SunsetFlourishSettings>,
+    time: Res<Time>,
+    mut flourishes: MessageWriter<SunsetFlourish>,
+    mut last_positions: Local<HashMap<Entity, (u64, f32)>>,
+    q_sky_center: Query<(Entity, &SkyCenter, Option<&HorizonProfile>)>,
+) {
+    let mut rng = rand::rng();
+    for (entity, sky_center, horizon_profile) in q_sky_center.iter() {
+        if !(sky_center.cycle_duration_secs > 0.0) {
+            continue;
+        }
+        let hour_fraction = sky_center.current_cycle_time / sky_center.cycle_duration_secs;
+        let Some((last_day_index, last_hour_fraction)) =
+            last_positions.insert(entity, (sky_center.day_index, hour_fraction))
+        else {
+            continue;
+        };
+
+        // Skip rewinds and catch-up jumps, a flourish only makes sense when seen
+        let cycles = sky_center.day_index.wrapping_sub(last_day_index) as f32 + hour_fraction
+            - last_hour_fraction;
+        if !(cycles > 0.0 && cycles < 1.0) {
+            continue;
+        }
+
+        // Sunset is the moment the upper limb sets, see `SkyCenter::sun_angular_diameter_deg`,
+        // behind the skyline with a `HorizonProfile`
+        let &[_, limb_set] = sky_center
+            .sunrise_and_sunset_over(horizon_profile)
+            .as_slice()
+        else {
+            continue;
+        };
+        if times_passed(limb_set, last_hour_fraction, cycles) == 0
+            || !rng.random_bool(settings.probability.clamp(0.0, 1.0) as f64)
+        {
+            continue;
+        }
+
+        let day_offset =
+            (last_hour_fraction + (limb_set - last_hour_fraction).rem_euclid(1.0)).floor() as u64;
+        flourishes.write(SunsetFlourish {
+            sky: entity,
+            at: SkyTimestamp {
+                cycle_time_secs: limb_set * sky_center.cycle_duration_secs,
+                hour_fraction: limb_set,
+                day_index: last_day_index + day_offset,
+                elapsed_secs: time.elapsed_secs(),
+            },
+        });
+    }
+    last_positions.retain(|entity, _| q_sky_center.contains(*entity));
+}
@@ -0,0 +1,169 @@
+// Day/night surface conditions for gameplay and physics crates (bevy_rapier, avian):
+// an approximate ground temperature following the sun with some thermal lag, and the
+// friction multiplier it implies for icy surfaces. This crate only publishes the
+// resource, physics integration is left to the game (see the `icy_roads` example).
+
+use bevy::prelude::*;
+
+use crate::{SkyCenter, SunMoveSet, SunState, daylight_factor, primary_sky::PrimarySkyQuery};
+
+pub struct SurfaceConditionsPlugin;
+
+impl Plugin for SurfaceConditionsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SurfaceConditionsSettings>();
+        app.init_resource::<SurfaceConditions>();
+        app.add_systems(PostUpdate, update_surface_conditions.after(SunMoveSet));
+    }
+}
+
+#[derive(Resource, Debug, Clone)]
+pub struct SurfaceConditionsSettings {
+    /// Temperature reached after a long time in full daylight.
+    pub day_temperature_c: f32,
+    /// Temperature reached after a long time in darkness.
+    pub night_temperature_c: f32,
+    /// Time the temperature needs to cover ~63% of a change, as a fraction of the day/night
+    /// cycle. 0.0 follows the sun instantly.
+    pub thermal_lag_cycle_fraction: f32,
+    pub freezing_point_c: f32,
+    /// Degrees below the freezing point at which surfaces are fully iced.
+    pub freeze_transition_c: f32,
+    /// Friction multiplier of a fully iced surface.
+    pub ice_friction_multiplier: f32,
+}
+
+impl Default for SurfaceConditionsSettings {
+    fn default() -> Self {
+        Self {
+            day_temperature_c: 8.0,
+            night_temperature_c: -6.0,
+            thermal_lag_cycle_fraction: 0.05,
+            freezing_point_c: 0.0,
+            freeze_transition_c: 3.0,
+            ice_friction_multiplier: 0.15,
+        }
+    }
+}
+
+#[derive(Resource, Debug, Clone, Copy, PartialEq)]
+pub struct SurfaceConditions {
+    pub temperature_c: f32,
+    /// 0.0 for dry ground, 1.0 for fully iced surfaces.
+    pub ice_factor: f32,
+    /// Multiply surface friction coefficients by this value.
+    pub friction_multiplier: f32,
+}
+
+impl Default for SurfaceConditions {
+    fn default() -> Self {
+        Self {
+            temperature_c: f32::NAN, // Snapped to the sun on the first update
+            ice_factor: 0.0,
+            friction_multiplier: 1.0,
+        }
+    }
+}
+
+fn update_surface_conditions(
+    settings: Res<SurfaceConditionsSettings>,
+    mut conditions: ResMut<SurfaceConditions>,
+    mut last_clock: Local<Option<(u64, f32)>>,
+    primary_sky: PrimarySkyQuery,
+    q_sky_center: Query<(&SkyCenter, &SunState)>,
+) {
+    let Some((sky_center, sun_state)) = primary_sky
+        .entity()
+        .and_then(|sky| q_sky_center.get(sky).ok())
+    else {
+        return;
+    };
+
+    let target_temperature_c = settings.night_temperature_c
+        + (settings.day_temperature_c - settings.night_temperature_c)
+            * daylight_factor(sun_state.direction);
+
+    // Elapsed sky time, so pausing or scaling the sky also affects the thermal lag. A
+    // rewinding sky doesn't cool or warm the ground, it just shows the temperature later.
+    let sky_delta_secs = last_clock.map_or(0.0, |(last_day_index, last_cycle_time)| {
+        let delta_secs = (sky_center.day_index as f64 - last_day_index as f64)
+            * sky_center.cycle_duration_secs as f64
+            + (sky_center.current_cycle_time - last_cycle_time) as f64;
+        delta_secs.max(0.0) as f32
+    });
+    *last_clock = Some((sky_center.day_index, sky_center.current_cycle_time));
+
+    let lag_secs = settings.thermal_lag_cycle_fraction * sky_center.cycle_duration_secs;
+    let temperature_c = if conditions.temperature_c.is_nan() || lag_secs <= 0.0 {
+        target_temperature_c
+    } else {
+        let blend = 1.0 - (-sky_delta_secs / lag_secs).exp();
+        conditions.temperature_c + (target_temperature_c - conditions.temperature_c) * blend
+    };
+
+    let ice_factor = ((settings.freezing_point_c - temperature_c)
+        / settings.freeze_transition_c.max(f32::EPSILON))
+    .clamp(0.0, 1.0);
+
+    let new_conditions = SurfaceConditions {
+        temperature_c,
+        ice_factor,
+        friction_multiplier: 1.0 + (settings.ice_friction_multiplier - 1.0) * ice_factor,
+    };
+    // Only trigger change detection when something actually changed
+    conditions.set_if_neq(new_conditions);
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy::time::TimeUpdateStrategy;
+
+    use super::*;
+    use crate::SunMovePlugin;
+
+    fn temperature_after(app: &mut App, sky: Entity, delta_secs: f32) -> f32 {
+        app.world_mut()
+            .get_mut::<SkyCenter>(sky)
+            .unwrap()
+            .current_cycle_time += delta_secs;
+        app.update();
+        app.world().resource::<SurfaceConditions>().temperature_c
+    }
+
+    #[test]
+    fn rewinds_leave_the_temperature_alone() {
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            SunMovePlugin::default(),
+            SurfaceConditionsPlugin,
+        ))
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+            100,
+        )));
+        let sun = app.world_mut().spawn(Transform::default()).id();
+        // Paused at noon, only the edits below move the clock
+        let sky = app
+            .world_mut()
+            .spawn(SkyCenter {
+                sun,
+                current_cycle_time: 300.0,
+                paused: true,
+                ..default()
+            })
+            .id();
+        app.update();
+        let noon_temperature_c = app.world().resource::<SurfaceConditions>().temperature_c;
+        assert_eq!(
+            noon_temperature_c,
+            SurfaceConditionsSettings::default().day_temperature_c
+        );
+
+        // Back to midnight: no time passed for the ground
+        assert_eq!(temperature_after(&mut app, sky, -300.0), noon_temperature_c);
+        // A night moving forward cools it
+        assert!(temperature_after(&mut app, sky, 30.0) < noon_temperature_c);
+    }
+}
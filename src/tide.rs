@@ -0,0 +1,102 @@
+// Simple tide proxy for coastal gameplay and water plane drivers: the equilibrium tide
+// of the moon and the sun, delayed by a configurable lag. Needs a `MoonCenter`.
+
+use bevy::prelude::*;
+
+use crate::{
+    DEGREES_TO_RADIANS, MoonCenter, SkyCenter, SunMoveSet, calculate_moon_direction,
+    calculate_sun_direction, primary_sky::PrimarySkyQuery,
+};
+
+pub struct TidePlugin;
+
+impl Plugin for TidePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<TideSettings>();
+        app.init_resource::<TideLevel>();
+        // The moon orbit is advanced in Update, so read it afterwards.
+        app.add_systems(PostUpdate, update_tide_level.after(SunMoveSet));
+    }
+}
+
+#[derive(Resource, Debug, Clone)]
+pub struct TideSettings {
+    /// Water height at the mean tide level.
+    pub mean_level: f32,
+    /// Height difference between the mean level and the highest spring tide.
+    pub amplitude: f32,
+    /// Strength of the solar tide relative to the lunar one (0.46 on Earth).
+    pub sun_ratio: f32,
+    /// Delay of the tide behind the moon, in in-game hours (a cycle is 24 hours).
+    pub lag_hours: f32,
+}
+
+impl Default for TideSettings {
+    fn default() -> Self {
+        Self {
+            mean_level: 0.0,
+            amplitude: 1.0,
+            sun_ratio: 0.46,
+            lag_hours: 1.0,
+        }
+    }
+}
+
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq)]
+pub struct TideLevel {
+    /// Water height, `mean_level` plus the tide.
+    pub height: f32,
+    /// Tide without the mean level and amplitude, roughly -0.5 (low) to 1.0 (high spring tide).
+    pub normalized: f32,
+}
+
+/// Equilibrium tide of a body in direction `direction` (X east, Y up, Z north):
+/// 1.0 with the body overhead or underfoot, -0.5 with the body on the horizon.
+fn tidal_bulge(direction: Vec3) -> f32 {
+    let cos_zenith = direction.normalize_or_zero().y;
+    (3.0 * cos_zenith * cos_zenith - 1.0) * 0.5
+}
+
+fn update_tide_level(
+    settings: Res<TideSettings>,
+    mut tide_level: ResMut<TideLevel>,
+    primary_sky: PrimarySkyQuery,
+    q_sky_center: Query<(&SkyCenter, &MoonCenter)>,
+) {
+    let Some((sky_center, moon_center)) = primary_sky
+        .entity()
+        .and_then(|sky| q_sky_center.get(sky).ok())
+    else {
+        return;
+    };
+
+    let hour_fraction = sky_center.current_cycle_time / sky_center.cycle_duration_secs;
+    let lagged_hour_fraction =
+        sky_center.solar_hour_fraction(hour_fraction - settings.lag_hours / 24.0);
+    let latitude_rad = sky_center.latitude_degrees * DEGREES_TO_RADIANS;
+    let tilt_rad = sky_center.planet_tilt_degrees * DEGREES_TO_RADIANS;
+
+    let sun_direction = calculate_sun_direction(
+        lagged_hour_fraction,
+        latitude_rad,
+        tilt_rad,
+        sky_center.season_year_fraction(),
+    );
+    let moon_direction = calculate_moon_direction(
+        lagged_hour_fraction,
+        latitude_rad,
+        tilt_rad,
+        sky_center.season_year_fraction(),
+        moon_center.orbit_fraction + moon_center.phase_offset,
+        moon_center.inclination_degrees * DEGREES_TO_RADIANS,
+    );
+
+    let normalized = (tidal_bulge(moon_direction)
+        + settings.sun_ratio * tidal_bulge(sun_direction))
+        / (1.0 + settings.sun_ratio);
+
+    tide_level.set_if_neq(TideLevel {
+        height: settings.mean_level + settings.amplitude * normalized,
+        normalized,
+    });
+}
@@ -0,0 +1,324 @@
+// Coarse classification of the cycle into dawn, day, dusk and night, the states most
+// gameplay (spawning, AI schedules, lighting presets) is built on.
+
+use bevy::prelude::*;
+
+use crate::{SkyCenter, SkyTimestamp, SunClock, SunState, primary_sky::PrimarySkyQuery};
+
+#[derive(Component, Reflect, Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[reflect(Component, Default, Debug)]
+pub enum TimeOfDayPhase {
+    Dawn,
+    Day,
+    Dusk,
+    #[default]
+    Night,
+}
+
+/// Clock speed multipliers per phase, see `SkyCenter::phase_time_scales`.
+#[derive(Reflect, Debug, Clone, Copy, PartialEq)]
+#[reflect(Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PhaseTimeScales {
+    pub dawn: f32,
+    pub day: f32,
+    pub dusk: f32,
+    pub night: f32,
+}
+
+impl Default for PhaseTimeScales {
+    fn default() -> Self {
+        Self::uniform(1.0)
+    }
+}
+
+impl PhaseTimeScales {
+    pub fn uniform(scale: f32) -> Self {
+        Self {
+            dawn: scale,
+            day: scale,
+            dusk: scale,
+            night: scale,
+        }
+    }
+
+    /// Plays only the night faster (or slower), e.g. `night_compression(3.0)`.
+    pub fn night_compression(factor: f32) -> Self {
+        Self {
+            night: factor,
+            ..Self::uniform(1.0)
+        }
+    }
+
+    pub fn for_phase(&self, phase: TimeOfDayPhase) -> f32 {
+        match phase {
+            TimeOfDayPhase::Dawn => self.dawn,
+            TimeOfDayPhase::Day => self.day,
+            TimeOfDayPhase::Dusk => self.dusk,
+            TimeOfDayPhase::Night => self.night,
+        }
+    }
+
+    /// All phases play at the same speed, so there is nothing to warp.
+    pub fn is_uniform(&self) -> bool {
+        self.dawn == self.day && self.dusk == self.day && self.night == self.day
+    }
+}
+
+/// Altitudes of the sun's upper limb separating the phases, shared by all skies.
+/// Between the two thresholds it is `Dawn` before noon and `Dusk` after it.
+#[derive(Resource, Debug, Clone, Copy)]
+pub struct TimeOfDayThresholds {
+    /// Below this altitude (degrees) it is `Night`. -6° is the end of civil twilight.
+    pub night_below_deg: f32,
+    /// Above this altitude (degrees) it is `Day`.
+    pub day_above_deg: f32,
+}
+
+impl Default for TimeOfDayThresholds {
+    fn default() -> Self {
+        Self {
+            night_below_deg: -6.0,
+            day_above_deg: 6.0,
+        }
+    }
+}
+
+/// Sent when the `TimeOfDayPhase` of `sky` changes.
+#[derive(Message, Debug, Clone, Copy)]
+pub struct TimeOfDayPhaseChanged {
+    pub sky: Entity,
+    pub from: TimeOfDayPhase,
+    pub to: TimeOfDayPhase,
+    pub at: SkyTimestamp,
+}
+
+/// Phase for a sun altitude (of the upper limb, see `SunState`) and hour fraction (0.0 midnight, 0.5 noon).
+pub fn classify_time_of_day(
+    altitude_deg: f32,
+    hour_fraction: f32,
+    thresholds: &TimeOfDayThresholds,
+) -> TimeOfDayPhase {
+    if altitude_deg < thresholds.night_below_deg {
+        TimeOfDayPhase::Night
+    } else if altitude_deg > thresholds.day_above_deg {
+        TimeOfDayPhase::Day
+    } else if hour_fraction < 0.5 {
+        TimeOfDayPhase::Dawn
+    } else {
+        TimeOfDayPhase::Dusk
+    }
+}
+
+pub(crate) fn update_time_of_day_phase<T: SunClock + Resource>(
+    thresholds: Res<TimeOfDayThresholds>,
+    time: Option<Res<T>>,
+    mut phase_changes: MessageWriter<TimeOfDayPhaseChanged>,
+    mut q_sky_center: Query<(Entity, &SkyCenter, &SunState, &mut TimeOfDayPhase)>,
+) {
+    for (entity, sky_center, sun_state, mut phase) in q_sky_center.iter_mut() {
+        // Stopped like the sky itself, see `update_sky_center`
+        let cycle_duration = sky_center.cycle_duration_secs;
+        if !(cycle_duration > 0.0 && cycle_duration.is_finite()) {
+            continue;
+        }
+        let new_phase = classify_time_of_day(
+            sun_state.upper_limb_altitude_deg,
+            sky_center.current_cycle_time / cycle_duration,
+            &thresholds,
+        );
+        if let Some(from) = phase.replace_if_neq(new_phase) {
+            phase_changes.write(TimeOfDayPhaseChanged {
+                sky: entity,
+                from,
+                to: new_phase,
+                at: sky_center.timestamp(time.as_ref().map_or(0.0, |time| time.elapsed_secs())),
+            });
+        }
+    }
+}
+
+/// Run condition: the sun of the [`PrimarySky`](crate::primary_sky::PrimarySky) is up,
+/// between its `SunriseEvent` and `SunsetEvent` (over the skyline, with a
+/// `HorizonProfile`), e.g. `run_if(in_daytime())`. False without a primary sky.
+pub fn in_daytime() -> impl FnMut(PrimarySkyQuery, Query<&SunState>) -> bool + Clone {
+    move |primary_sky: PrimarySkyQuery, q_sun_state: Query<&SunState>| {
+        primary_sun_state(&primary_sky, &q_sun_state)
+            .is_some_and(|sun_state| sun_state.altitude_above_skyline_deg() > 0.0)
+    }
+}
+
+/// Run condition: the sun of the primary sky is down, the complement of [`in_daytime`].
+/// False without a primary sky.
+pub fn in_night() -> impl FnMut(PrimarySkyQuery, Query<&SunState>) -> bool + Clone {
+    move |primary_sky: PrimarySkyQuery, q_sun_state: Query<&SunState>| {
+        primary_sun_state(&primary_sky, &q_sun_state)
+            .is_some_and(|sun_state| sun_state.altitude_above_skyline_deg() <= 0.0)
+    }
+}
+
+/// Run condition: the center of the sun of the primary sky is above `altitude_deg`, e.g.
+/// `sun_above(-6.0)` until the end of civil twilight. False without a primary sky.
+pub fn sun_above(
+    altitude_deg: f32,
+) -> impl FnMut(PrimarySkyQuery, Query<&SunState>) -> bool + Clone {
+    move |primary_sky: PrimarySkyQuery, q_sun_state: Query<&SunState>| {
+        primary_sun_state(&primary_sky, &q_sun_state)
+            .is_some_and(|sun_state| sun_state.altitude_deg > altitude_deg)
+    }
+}
+
+/// Run condition: the primary sky is in `phase`. False without a primary sky.
+pub fn in_phase(
+    phase: TimeOfDayPhase,
+) -> impl FnMut(PrimarySkyQuery, Query<&TimeOfDayPhase>) -> bool + Clone {
+    move |primary_sky: PrimarySkyQuery, q_phase: Query<&TimeOfDayPhase>| {
+        primary_sky
+            .entity()
+            .and_then(|sky| q_phase.get(sky).ok())
+            .is_some_and(|current| *current == phase)
+    }
+}
+
+fn primary_sun_state<'a>(
+    primary_sky: &PrimarySkyQuery,
+    q_sun_state: &'a Query<&SunState>,
+) -> Option<&'a SunState> {
+    primary_sky
+        .entity()
+        .and_then(|sky| q_sun_state.get(sky).ok())
+}
+
+/// Run condition: the sun of `sky` is up, [`in_daytime`] for one of several skies. `sky`
+/// may also be a `SkyObserver`, for its local sun. False if it has no `SunState`.
+pub fn in_daytime_for(sky: Entity) -> impl FnMut(Query<&SunState>) -> bool + Clone {
+    move |q_sun_state: Query<&SunState>| {
+        q_sun_state
+            .get(sky)
+            .is_ok_and(|sun_state| sun_state.altitude_above_skyline_deg() > 0.0)
+    }
+}
+
+/// Run condition: the sun of `sky` (or `SkyObserver`) is down, see [`in_daytime_for`].
+pub fn in_night_for(sky: Entity) -> impl FnMut(Query<&SunState>) -> bool + Clone {
+    move |q_sun_state: Query<&SunState>| {
+        q_sun_state
+            .get(sky)
+            .is_ok_and(|sun_state| sun_state.altitude_above_skyline_deg() <= 0.0)
+    }
+}
+
+/// Run condition: the center of the sun of `sky` (or `SkyObserver`) is above
+/// `altitude_deg`, see [`sun_above`].
+pub fn sun_above_for(
+    sky: Entity,
+    altitude_deg: f32,
+) -> impl FnMut(Query<&SunState>) -> bool + Clone {
+    move |q_sun_state: Query<&SunState>| {
+        q_sun_state
+            .get(sky)
+            .is_ok_and(|sun_state| sun_state.altitude_deg > altitude_deg)
+    }
+}
+
+/// Run condition: `sky` (or `SkyObserver`) is in `phase`, see [`in_phase`].
+pub fn in_phase_for(
+    sky: Entity,
+    phase: TimeOfDayPhase,
+) -> impl FnMut(Query<&TimeOfDayPhase>) -> bool + Clone {
+    move |q_phase: Query<&TimeOfDayPhase>| q_phase.get(sky).is_ok_and(|current| *current == phase)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy::time::TimeUpdateStrategy;
+
+    use super::*;
+    use crate::{SunMovePlugin, primary_sky::PrimarySky};
+
+    #[derive(Resource, Default)]
+    struct Ran(Vec<&'static str>);
+
+    fn ran(name: &'static str) -> impl FnMut(ResMut<Ran>) {
+        move |mut ran: ResMut<Ran>| ran.0.push(name)
+    }
+
+    /// Runs one update with a noon sky and a midnight sky, returns the conditions that held.
+    fn conditions_holding(mark_noon_sky: bool) -> Vec<&'static str> {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, SunMovePlugin::default()))
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+                100,
+            )))
+            .init_resource::<Ran>()
+            .add_systems(
+                PostUpdate,
+                (
+                    ran("day").run_if(in_daytime()),
+                    ran("night").run_if(in_night()),
+                    ran("high sun").run_if(sun_above(60.0)),
+                    ran("phase day").run_if(in_phase(TimeOfDayPhase::Day)),
+                )
+                    .chain()
+                    .after(crate::SunMoveSet),
+            );
+        for hour in [12.0, 0.0] {
+            let sun = app.world_mut().spawn(Transform::default()).id();
+            let mut sky_center = SkyCenter {
+                sun,
+                paused: true,
+                ..default()
+            };
+            sky_center.set_hour_of_day(hour);
+            let sky = app.world_mut().spawn(sky_center).id();
+            if hour == 12.0 && mark_noon_sky {
+                app.world_mut().entity_mut(sky).insert(PrimarySky);
+            }
+        }
+        app.update();
+        app.world().resource::<Ran>().0.clone()
+    }
+
+    #[test]
+    fn conditions_follow_the_primary_sky() {
+        assert_eq!(conditions_holding(true), ["day", "high sun", "phase day"]);
+    }
+
+    #[test]
+    fn conditions_fail_without_a_primary_sky() {
+        assert!(conditions_holding(false).is_empty());
+    }
+
+    #[test]
+    fn phase_holds_without_a_cycle_duration() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, SunMovePlugin::default()))
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+                100,
+            )));
+        let sun = app.world_mut().spawn(Transform::default()).id();
+        let mut sky_center = SkyCenter {
+            sun,
+            paused: true,
+            ..default()
+        };
+        sky_center.set_hour_of_day(12.0);
+        sky_center.cycle_duration_secs = 0.0;
+        let sky = app.world_mut().spawn(sky_center).id();
+        app.update();
+        app.update();
+
+        let world = app.world();
+        assert_eq!(
+            *world.get::<TimeOfDayPhase>(sky).unwrap(),
+            TimeOfDayPhase::Night
+        );
+        assert!(
+            world
+                .resource::<Messages<TimeOfDayPhaseChanged>>()
+                .is_empty()
+        );
+    }
+}
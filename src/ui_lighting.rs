@@ -0,0 +1,75 @@
+// Debounced day/night signal for UI theming (dark mode, contrast). Switching uses
+// two thresholds on the daylight factor, so the mode doesn't flicker while the sun
+// hovers around the horizon at dawn and dusk.
+
+use bevy::prelude::*;
+
+use crate::{SunMoveSet, SunState, daylight_factor, primary_sky::PrimarySkyQuery};
+
+pub struct UiLightingPlugin;
+
+impl Plugin for UiLightingPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<UiLightingThresholds>();
+        app.init_resource::<UiLightingMode>();
+        app.add_systems(PostUpdate, update_ui_lighting_mode.after(SunMoveSet));
+    }
+}
+
+/// Only changes (and triggers change detection) when a threshold is crossed.
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum UiLightingMode {
+    #[default]
+    Day,
+    Night,
+}
+
+/// Daylight factor thresholds (0.0 night, 1.0 day). `to_day` should be above `to_night`,
+/// the gap between them is the hysteresis band.
+#[derive(Resource, Debug, Clone, Copy)]
+pub struct UiLightingThresholds {
+    pub to_day: f32,
+    pub to_night: f32,
+}
+
+impl Default for UiLightingThresholds {
+    fn default() -> Self {
+        Self {
+            to_day: 0.6,
+            to_night: 0.3,
+        }
+    }
+}
+
+fn update_ui_lighting_mode(
+    thresholds: Res<UiLightingThresholds>,
+    mut mode: ResMut<UiLightingMode>,
+    mut initialized: Local<bool>,
+    primary_sky: PrimarySkyQuery,
+    q_sun_state: Query<&SunState>,
+) {
+    let Some(sun_state) = primary_sky
+        .entity()
+        .and_then(|sky| q_sun_state.get(sky).ok())
+    else {
+        return;
+    };
+
+    let daylight = daylight_factor(sun_state.direction);
+    let new_mode = if !*initialized {
+        // No previous mode to stick to, split the band in the middle
+        *initialized = true;
+        if daylight >= (thresholds.to_day + thresholds.to_night) * 0.5 {
+            UiLightingMode::Day
+        } else {
+            UiLightingMode::Night
+        }
+    } else {
+        match *mode {
+            UiLightingMode::Night if daylight >= thresholds.to_day => UiLightingMode::Day,
+            UiLightingMode::Day if daylight <= thresholds.to_night => UiLightingMode::Night,
+            current => current,
+        }
+    };
+    mode.set_if_neq(new_mode);
+}
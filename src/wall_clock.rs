@@ -0,0 +1,232 @@
+// Resuming the sky after the app was backgrounded or suspended (mobile games constantly
+// are): either continue where it stopped, or catch up with the real time that passed.
+
+use bevy::{platform::time::Instant, prelude::*, window::AppLifecycle};
+
+use crate::{PreciseCycleTime, SkyCenter, time_of_day::TimeOfDayThresholds};
+
+pub struct WallClockPlugin;
+
+impl Plugin for WallClockPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<WallClockSettings>();
+        // Before the sky advances in Update
+        app.add_systems(PreUpdate, anchor_on_app_lifecycle);
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ResumePolicy {
+    /// Continue exactly where the sky was anchored, as if no time passed.
+    ContinueWhereLeft,
+    /// Advance the sky by the wall clock time since the anchor (times `time_scale`).
+    #[default]
+    CatchUp,
+}
+
+/// Policy used by [`WallClockPlugin`] when the app is suspended and resumed.
+#[derive(Resource, Debug, Default, Clone, Copy)]
+pub struct WallClockSettings {
+    pub policy: ResumePolicy,
+}
+
+/// Sky state at a wall clock instant, created by [`SkyCenter::anchor_to_wall_clock`].
+/// Inserted on `SkyCenter` entities by [`WallClockPlugin`] while the app is suspended.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct WallClockAnchor {
+    pub instant: Instant,
+    pub cycle_time: f32,
+    pub policy: ResumePolicy,
+}
+
+impl WallClockAnchor {
+    pub fn with_policy(mut self, policy: ResumePolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+}
+
+impl SkyCenter {
+    /// Remembers the current cycle time at `instant` (usually `Instant::now()` when the app
+    /// is backgrounded), to be applied later with [`SkyCenter::resume_from_wall_clock`].
+    pub fn anchor_to_wall_clock(&self, instant: Instant) -> WallClockAnchor {
+        WallClockAnchor {
+            instant,
+            cycle_time: self.current_cycle_time,
+            policy: ResumePolicy::default(),
+        }
+    }
+
+    /// Restores the sky from `anchor` following its policy: goes back to the anchored cycle
+    /// time and, to catch up, queues the wall clock time since the anchor on the sky's
+    /// `precise_cycle_time` (see [`PreciseCycleTime::skip`]). The next update advances the
+    /// day index, seasons and events like any clock delta. Returns the queued sky seconds
+    /// (0.0 when continuing where it left). `thresholds` place the phases of
+    /// `phase_time_scales`.
+    pub fn resume_from_wall_clock(
+        &mut self,
+        precise_cycle_time: &mut PreciseCycleTime,
+        anchor: &WallClockAnchor,
+        now: Instant,
+        thresholds: &TimeOfDayThresholds,
+    ) -> f32 {
+        self.current_cycle_time = anchor.cycle_time;
+        let skipped_secs = match anchor.policy {
+            ResumePolicy::ContinueWhereLeft => 0.0,
+            ResumePolicy::CatchUp if self.paused => 0.0,
+            ResumePolicy::CatchUp => {
+                let real_secs =
+                    now.saturating_duration_since(anchor.instant).as_secs_f32() * self.time_scale;
+                self.warped_cycle_delta(real_secs, thresholds)
+            }
+        };
+        precise_cycle_time.skip(skipped_secs as f64);
+        skipped_secs
+    }
+}
+
+fn anchor_on_app_lifecycle(
+    mut commands: Commands,
+    mut lifecycle: MessageReader<AppLifecycle>,
+    settings: Res<WallClockSettings>,
+    thresholds: Res<TimeOfDayThresholds>,
+    mut q_sky_center: Query<(
+        Entity,
+        &mut SkyCenter,
+        &mut PreciseCycleTime,
+        Option<&WallClockAnchor>,
+    )>,
+) {
+    for event in lifecycle.read() {
+        match event {
+            AppLifecycle::WillSuspend => {
+                let now = Instant::now();
+                for (entity, sky_center, _, _) in q_sky_center.iter() {
+                    commands.entity(entity).insert(
+                        sky_center
+                            .anchor_to_wall_clock(now)
+                            .with_policy(settings.policy),
+                    );
+                }
+            }
+            AppLifecycle::WillResume => {
+                let now = Instant::now();
+                for (entity, mut sky_center, mut precise_cycle_time, anchor) in
+                    q_sky_center.iter_mut()
+                {
+                    let Some(anchor) = anchor else {
+                        continue;
+                    };
+                    // The sky update sends the catch-up (or the events) for the skipped time
+                    sky_center.resume_from_wall_clock(
+                        &mut precise_cycle_time,
+                        anchor,
+                        now,
+                        &thresholds,
+                    );
+                    commands.entity(entity).remove::<WallClockAnchor>();
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy::time::TimeUpdateStrategy;
+
+    use super::*;
+    use crate::{NewYearEvent, SkyCatchUp, SunMovePlugin};
+
+    #[derive(Resource, Default)]
+    struct CatchUpLog {
+        days_passed: Vec<f32>,
+        new_years: usize,
+    }
+
+    fn log_catch_ups(
+        mut log: ResMut<CatchUpLog>,
+        mut catch_ups: MessageReader<SkyCatchUp>,
+        mut new_years: MessageReader<NewYearEvent>,
+    ) {
+        log.days_passed
+            .extend(catch_ups.read().map(|catch_up| catch_up.days_passed));
+        log.new_years += new_years.read().count();
+    }
+
+    #[test]
+    fn catching_up_advances_days_and_seasons() {
+        let mut app = App::new();
+        // The app clock stands still, only the wall clock moves the sky
+        app.add_plugins((MinimalPlugins, SunMovePlugin::default()))
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::ZERO))
+            .init_resource::<CatchUpLog>()
+            .add_systems(PostUpdate, log_catch_ups);
+        let sun = app.world_mut().spawn(Transform::default()).id();
+        let sky = app
+            .world_mut()
+            .spawn(SkyCenter {
+                sun,
+                cycle_duration_secs: 10.0,
+                current_cycle_time: 5.0,
+                year_fraction: 0.0,
+                days_per_year: Some(2.0),
+                ..default()
+            })
+            .id();
+        app.update();
+
+        let suspended_at = Instant::now();
+        let anchor = app
+            .world()
+            .get::<SkyCenter>(sky)
+            .unwrap()
+            .anchor_to_wall_clock(suspended_at);
+        let world = app.world_mut();
+        let mut q_sky_clock = world.query::<(&mut SkyCenter, &mut PreciseCycleTime)>();
+        let (mut sky_center, mut precise_cycle_time) = q_sky_clock.get_mut(world, sky).unwrap();
+        let skipped_secs = sky_center.resume_from_wall_clock(
+            &mut precise_cycle_time,
+            &anchor,
+            suspended_at + Duration::from_secs(25),
+            &TimeOfDayThresholds::default(),
+        );
+        assert_eq!(skipped_secs, 25.0);
+        app.update();
+
+        // From noon of day 0, 2.5 cycles later: noon of day 2, a full year of 2 days later
+        let sky_center = app.world().get::<SkyCenter>(sky).unwrap();
+        assert_eq!(sky_center.day_index, 2);
+        assert!((sky_center.current_cycle_time - 5.0).abs() < 1e-3);
+        assert!(sky_center.year_fraction.abs() < 1e-4);
+        let log = app.world().resource::<CatchUpLog>();
+        assert_eq!(log.days_passed, [2.5]);
+        assert_eq!(log.new_years, 1);
+    }
+
+    #[test]
+    fn continuing_where_left_skips_nothing() {
+        let mut sky_center = SkyCenter {
+            current_cycle_time: 5.0,
+            ..default()
+        };
+        let suspended_at = Instant::now();
+        let anchor = sky_center
+            .anchor_to_wall_clock(suspended_at)
+            .with_policy(ResumePolicy::ContinueWhereLeft);
+        sky_center.current_cycle_time = 8.0;
+        let mut precise_cycle_time = PreciseCycleTime::default();
+        let skipped_secs = sky_center.resume_from_wall_clock(
+            &mut precise_cycle_time,
+            &anchor,
+            suspended_at + Duration::from_secs(100),
+            &TimeOfDayThresholds::default(),
+        );
+        assert_eq!(skipped_secs, 0.0);
+        assert_eq!(sky_center.current_cycle_time, 5.0);
+        assert_eq!(precise_cycle_time.skipped_secs, 0.0);
+    }
+}
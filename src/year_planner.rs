@@ -0,0 +1,115 @@
+// Season overview for strategy-layer UIs: sunrise, sunset and day length sampled over the
+// year, ready to plot as a season planner.
+
+use crate::{
+    SkyCenter,
+    ephemeris::{EphemerisRow, ephemeris_row},
+};
+
+/// One period of the year (a month, a week, ...), sampled at its middle.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct YearSample {
+    /// Index of the period, from 0 at the Vernal Equinox.
+    pub period: u32,
+    /// Year fraction at the start of the period.
+    pub start_year_fraction: f32,
+    /// Year fraction at the middle of the period, where it was sampled.
+    pub year_fraction: f32,
+    /// Hour fraction of sunrise, `None` during polar day or night.
+    pub sunrise_hour_fraction: Option<f32>,
+    /// Hour fraction of sunset, `None` during polar day or night.
+    pub sunset_hour_fraction: Option<f32>,
+    /// Fraction of the cycle the sun is up (0.0 polar night, 1.0 polar day).
+    pub day_fraction: f32,
+    pub day_length_secs: f32,
+    pub noon_altitude_deg: f32,
+}
+
+/// The year of a sky, see [`year_overview`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct YearOverview {
+    pub samples: Vec<YearSample>,
+}
+
+impl YearOverview {
+    /// The sample with the longest day.
+    pub fn longest_day(&self) -> Option<&YearSample> {
+        self.samples
+            .iter()
+            .max_by(|a, b| a.day_length_secs.total_cmp(&b.day_length_secs))
+    }
+
+    /// The sample with the shortest day.
+    pub fn shortest_day(&self) -> Option<&YearSample> {
+        self.samples
+            .iter()
+            .min_by(|a, b| a.day_length_secs.total_cmp(&b.day_length_secs))
+    }
+
+    /// `[year_fraction, day_length_secs]` points, e.g. for `egui_plot::Line`.
+    pub fn day_length_points(&self) -> Vec<[f64; 2]> {
+        self.points(|sample| Some(sample.day_length_secs))
+    }
+
+    /// `[year_fraction, hour_fraction]` points of sunrise, skipping polar days and nights.
+    pub fn sunrise_points(&self) -> Vec<[f64; 2]> {
+        self.points(|sample| sample.sunrise_hour_fraction)
+    }
+
+    /// `[year_fraction, hour_fraction]` points of sunset, skipping polar days and nights.
+    pub fn sunset_points(&self) -> Vec<[f64; 2]> {
+        self.points(|sample| sample.sunset_hour_fraction)
+    }
+
+    /// `[year_fraction, noon_altitude_deg]` points.
+    pub fn noon_altitude_points(&self) -> Vec<[f64; 2]> {
+        self.points(|sample| Some(sample.noon_altitude_deg))
+    }
+
+    fn points(&self, value: impl Fn(&YearSample) -> Option<f32>) -> Vec<[f64; 2]> {
+        self.samples
+            .iter()
+            .filter_map(|sample| value(sample).map(|v| [sample.year_fraction as f64, v as f64]))
+            .collect()
+    }
+}
+
+/// Splits the year of `sky_center` (latitude, tilt, orbit and cycle duration) into `periods`
+/// equal periods from the Vernal Equinox and samples each one at its middle: 12 for months,
+/// `days_per_year / 7` for weeks.
+pub fn year_overview(sky_center: &SkyCenter, periods: u32) -> YearOverview {
+    let periods = periods.max(1);
+    YearOverview {
+        samples: (0..periods)
+            .map(|period| {
+                let start_year_fraction = period as f32 / periods as f32;
+                let EphemerisRow {
+                    year_fraction,
+                    sunrise_hour_fraction,
+                    sunset_hour_fraction,
+                    day_length_secs,
+                    noon_altitude_deg,
+                    ..
+                } = ephemeris_row(
+                    sky_center,
+                    period,
+                    start_year_fraction + 0.5 / periods as f32,
+                );
+                YearSample {
+                    period,
+                    start_year_fraction,
+                    year_fraction,
+                    sunrise_hour_fraction,
+                    sunset_hour_fraction,
+                    day_fraction: if sky_center.cycle_duration_secs > 0.0 {
+                        day_length_secs / sky_center.cycle_duration_secs
+                    } else {
+                        0.0
+                    },
+                    day_length_secs,
+                    noon_altitude_deg,
+                }
+            })
+            .collect(),
+    }
+}